@@ -0,0 +1,163 @@
+//! `#[derive(AS3Schema)]` for `appcovecompiler`. Use it through the crate's `derive`
+//! feature rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Ident, Lit, LitStr,
+    UnOp,
+};
+
+/// Derives `AS3Schema`, building an `AS3Validator::Object` with one property per field.
+///
+/// Field types map to validators through their own `AS3Schema` impls, and
+/// `#[as3(...)]` attributes add constraints:
+///
+/// - `min = 20` / `max = 99` (also `minimum`/`maximum`) bound Integer and Decimal fields,
+/// - `regex = "^[A-Z]"`, `min_length = 1`, `max_length = 64`, `format = "email"` and
+///   `case = "lower"` constrain String fields, and `min_bytes`/`max_bytes` bound their
+///   size in bytes,
+/// - `optional` lets the key be absent, as `Option` fields already do,
+/// - `rename = "first name"` changes the key the field is expected under.
+#[proc_macro_derive(AS3Schema, attributes(as3))]
+pub fn derive_as3_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "AS3Schema can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "AS3Schema requires a struct with named fields",
+        ));
+    };
+
+    let mut properties = vec![];
+    for field in &fields.named {
+        let ty = &field.ty;
+        let mut key = field.ident.as_ref().unwrap().to_string();
+        let mut constraints = vec![];
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("as3"))
+        {
+            attr.parse_nested_meta(|meta| {
+                let name = meta
+                    .path
+                    .get_ident()
+                    .map(Ident::to_string)
+                    .unwrap_or_default();
+                let constraint = match name.as_str() {
+                    "min" | "minimum" => {
+                        let value = bound(&meta.value()?.parse()?);
+                        quote!(minimum(&mut validator, #value))
+                    }
+                    "max" | "maximum" => {
+                        let value = bound(&meta.value()?.parse()?);
+                        quote!(maximum(&mut validator, #value))
+                    }
+                    "min_length" | "max_length" | "min_bytes" | "max_bytes" => {
+                        let setter = Ident::new(&name, Span::call_site());
+                        let value: Expr = meta.value()?.parse()?;
+                        quote!(#setter(&mut validator, (#value) as usize))
+                    }
+                    "regex" => {
+                        let value: LitStr = meta.value()?.parse()?;
+                        quote!(regex(&mut validator, #value))
+                    }
+                    "format" => {
+                        let variant = variant_ident(&meta.value()?.parse()?);
+                        quote!(format(&mut validator, ::appcovecompiler::StringFormat::#variant))
+                    }
+                    "case" => {
+                        let variant = variant_ident(&meta.value()?.parse()?);
+                        quote!(case(&mut validator, ::appcovecompiler::StringCase::#variant))
+                    }
+                    "optional" => quote!(optional(&mut validator)),
+                    "rename" => {
+                        key = meta.value()?.parse::<LitStr>()?.value();
+                        return Ok(());
+                    }
+                    _ => return Err(meta.error("unsupported as3 attribute")),
+                };
+                constraints.push(constraint);
+                Ok(())
+            })?;
+        }
+        properties.push(quote! {
+            (::std::string::String::from(#key), {
+                #[allow(unused_mut)]
+                let mut validator =
+                    <#ty as ::appcovecompiler::AS3Schema>::as3_validator();
+                #(::appcovecompiler::__derive::#constraints;)*
+                validator
+            })
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::appcovecompiler::AS3Schema for #ident #ty_generics #where_clause {
+            fn as3_validator() -> ::appcovecompiler::AS3Validator {
+                ::appcovecompiler::AS3Validator::Object {
+                    properties: ::std::collections::HashMap::from([#(#properties),*]),
+                    property_names: None,
+                    when_present: None,
+                    dependent_required: ::std::collections::HashMap::new(),
+                    additional_properties: true,
+                    additional_properties_schema: None,
+                    require_sorted_keys: false,
+                    non_empty: false,
+                    rules: ::std::vec::Vec::new(),
+                }
+            }
+        }
+    })
+}
+
+/// Turns a lowercase name such as `"email"` into its enum variant, `Email`, so that an
+/// unknown name fails to compile.
+fn variant_ident(name: &LitStr) -> Ident {
+    let span = name.span();
+    let name = name.value();
+    let mut chars = name.chars();
+    let variant = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    Ident::new(&variant, span)
+}
+
+/// A `min`/`max` value as a `__derive::Bound`. Integer literals stay `i64`, so bounds
+/// past 2^53 are exact, and float literals `f64`; other expressions convert by type.
+fn bound(value: &Expr) -> TokenStream2 {
+    let literal = match value {
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => expr.as_ref(),
+        value => value,
+    };
+    match literal {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(_), ..
+        }) => quote!(::appcovecompiler::__derive::Bound::Integer(#value)),
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(_), ..
+        }) => quote!(::appcovecompiler::__derive::Bound::Decimal(#value)),
+        _ => quote!(::appcovecompiler::__derive::Bound::from(#value)),
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use appcovecompiler::{as3_validator, AS3Data, AS3Validator};
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde_json::json;
+
+fn small_object(c: &mut Criterion) {
+    let validator = as3_validator!({
+        age: Integer(min = 20),
+        name: String(regex = "^[A-Z][a-z]"),
+        vehicles: { list: [{ maker: String(regex = "^[A-Z][a-z]"), year: Integer }] },
+    });
+    let data = AS3Data::from(&json!({
+        "age": 25,
+        "name": "Dilec",
+        "vehicles": { "list": [{ "maker": "Tesla", "year": 2018 }] }
+    }));
+    c.bench_function("small object", |b| {
+        b.iter(|| validator.validate(black_box(&data)))
+    });
+}
+
+fn large_list(c: &mut Criterion) {
+    let validator =
+        as3_validator!([{ maker: String(regex = "^[A-Z][a-z]"), year: Integer(min = 1900) }]);
+    let data = AS3Data::List(
+        (0..10_000)
+            .map(|year| AS3Data::from(&json!({ "maker": "Tesla", "year": 1900 + year % 100 })))
+            .collect(),
+    );
+    c.bench_function("10k element list", |b| {
+        b.iter(|| validator.validate(black_box(&data)))
+    });
+}
+
+fn deeply_nested(c: &mut Criterion) {
+    let validator = (0..100).fold(as3_validator!(Integer), |inner, _| AS3Validator::Object {
+        properties: [("inner".to_string(), inner)].into(),
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    });
+    let data = AS3Data::from(&(0..100).fold(json!(1), |inner, _| json!({ "inner": inner })));
+    c.bench_function("100 levels deep", |b| {
+        b.iter(|| validator.validate(black_box(&data)))
+    });
+}
+
+fn schema_loading(c: &mut Criterion) {
+    let schema = include_str!("../validator_schema.yml");
+    c.bench_function("load schema", |b| {
+        b.iter(|| AS3Validator::from_yaml_str(black_box(schema)).unwrap())
+    });
+}
+
+fn clone_validator(c: &mut Criterion) {
+    let validator = AS3Validator::from_yaml_str(include_str!("../validator_schema.yml")).unwrap();
+    c.bench_function("clone validator", |b| {
+        b.iter(|| black_box(&validator).clone())
+    });
+}
+
+criterion_group!(
+    benches,
+    small_object,
+    large_list,
+    deeply_nested,
+    schema_loading,
+    clone_validator
+);
+criterion_main!(benches);
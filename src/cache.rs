@@ -0,0 +1,76 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+
+use crate::{AS3Data, AS3ValidationError, AS3Validator};
+
+type Outcome = Result<(), Vec<AS3ValidationError>>;
+
+/// An [`AS3Validator`] that remembers the outcome for the last few documents it checked,
+/// for loops that re-validate mostly unchanged data, such as a config reloaded whenever
+/// its file is touched.
+///
+/// Entries are looked up by the hash of the data and then compared in full, so a hash
+/// collision never returns another document's result. The least recently used entry is
+/// dropped once `capacity` is reached.
+#[derive(Debug)]
+pub struct CachedValidator {
+    validator: AS3Validator,
+    capacity: usize,
+    /// Most recently used last.
+    entries: VecDeque<(u64, AS3Data, Outcome)>,
+    hits: usize,
+}
+
+impl CachedValidator {
+    pub fn new(validator: AS3Validator, capacity: usize) -> Self {
+        CachedValidator {
+            validator,
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+            hits: 0,
+        }
+    }
+
+    pub fn validator(&self) -> &AS3Validator {
+        &self.validator
+    }
+
+    /// How many validations were answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Like [`AS3Validator::validate_all`], reusing the outcome for data equal to a
+    /// cached document.
+    pub fn validate_all(&mut self, data: &AS3Data) -> Outcome {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let cached = self
+            .entries
+            .iter()
+            .position(|(entry_hash, entry, _)| *entry_hash == hash && entry == data);
+        if let Some(index) = cached {
+            self.hits += 1;
+            let entry = self
+                .entries
+                .remove(index)
+                .expect("the index was just found");
+            let outcome = entry.2.clone();
+            self.entries.push_back(entry);
+            return outcome;
+        }
+
+        let outcome = self.validator.validate_all(data);
+        if self.capacity > 0 {
+            if self.entries.len() == self.capacity {
+                self.entries.pop_front();
+            }
+            self.entries
+                .push_back((hash, data.clone(), outcome.clone()));
+        }
+        outcome
+    }
+}
@@ -0,0 +1,589 @@
+use std::fmt;
+
+use crate::AS3Validator;
+
+/// A change found by [`AS3Validator::is_backward_compatible_with`] that can reject data
+/// the older schema accepted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incompatibility {
+    /// Where the change sits, in the format of [`AS3Validator::walk`].
+    pub path: Vec<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "Root: {}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path.join("/"), self.message)
+        }
+    }
+}
+
+impl AS3Validator {
+    /// Checks that every value `older` accepts is still accepted by `self`: no new
+    /// required fields, no changed types and no tightened constraints. Breaking changes
+    /// are sorted by path.
+    ///
+    /// Changes to regexes and `OneOf` branches are reported whenever they differ at all,
+    /// as whether one pattern accepts everything another does cannot be decided here.
+    pub fn is_backward_compatible_with(
+        &self,
+        older: &AS3Validator,
+    ) -> Result<(), Vec<Incompatibility>> {
+        let mut incompatibilities = vec![];
+        compare(self, older, &mut vec![], &mut incompatibilities);
+        incompatibilities.sort_by(|a, b| a.path.cmp(&b.path));
+        if incompatibilities.is_empty() {
+            Ok(())
+        } else {
+            Err(incompatibilities)
+        }
+    }
+}
+
+fn compare(
+    newer: &AS3Validator,
+    older: &AS3Validator,
+    path: &mut Vec<String>,
+    found: &mut Vec<Incompatibility>,
+) {
+    let mut messages = vec![];
+    match (newer.unannotated(), older.unannotated()) {
+        (AS3Validator::Any, _) => {}
+        (
+            AS3Validator::Object {
+                properties,
+                property_names,
+                when_present,
+                dependent_required,
+                additional_properties,
+                additional_properties_schema,
+                require_sorted_keys,
+                non_empty,
+                rules,
+            },
+            AS3Validator::Object {
+                properties: old_properties,
+                property_names: old_property_names,
+                when_present: old_when_present,
+                dependent_required: old_dependent_required,
+                additional_properties: old_additional_properties,
+                additional_properties_schema: old_additional_properties_schema,
+                require_sorted_keys: old_require_sorted_keys,
+                non_empty: old_non_empty,
+                rules: old_rules,
+            },
+        ) => {
+            for rule in rules.iter().filter(|rule| !old_rules.contains(rule)) {
+                messages.push(format!(
+                    "new rule on `{}` when `{}` is {}",
+                    rule.then.0,
+                    rule.when.0,
+                    rule.when.1.describe()
+                ));
+            }
+            if !additional_properties && *old_additional_properties {
+                messages.push("undeclared keys are now rejected".to_string());
+            }
+            match (
+                additional_properties_schema,
+                old_additional_properties_schema,
+            ) {
+                (Some(schema), Some(old_schema)) => {
+                    path.push("+AdditionalProperties".to_string());
+                    compare(schema, old_schema, path, found);
+                    path.pop();
+                }
+                (Some(_), None) if *additional_properties => {
+                    messages
+                        .push("undeclared keys must now match +AdditionalProperties".to_string());
+                }
+                _ => {}
+            }
+            if *require_sorted_keys && !old_require_sorted_keys {
+                messages.push("keys must now be sorted".to_string());
+            }
+            if *non_empty && !old_non_empty {
+                messages.push("empty objects are now rejected".to_string());
+            }
+            // A guard only skips checks, so adding one loosens the object while removing
+            // or moving it starts checking data that used to pass.
+            if let Some(old_key) = old_when_present
+                .as_ref()
+                .filter(|_| when_present != old_when_present)
+            {
+                messages.push(match when_present {
+                    Some(key) => format!("+WhenPresent moved from `{old_key}` to `{key}`"),
+                    None => {
+                        format!("+WhenPresent `{old_key}` removed, so the object is always checked")
+                    }
+                });
+            }
+            if property_names.is_some() && property_names != old_property_names {
+                messages.push("+PropertyNames now restricts keys further".to_string());
+            }
+            let mut triggers: Vec<_> = dependent_required.keys().collect();
+            triggers.sort();
+            for trigger in triggers {
+                let old = old_dependent_required.get(trigger);
+                for required in &dependent_required[trigger] {
+                    if !old.is_some_and(|old| old.contains(required)) {
+                        messages.push(format!("`{required}` is now required with `{trigger}`"));
+                    }
+                }
+            }
+            let mut keys: Vec<_> = properties.keys().collect();
+            keys.sort();
+            for key in keys {
+                let validator = &properties[key];
+                let optional = |validator: &AS3Validator| {
+                    validator
+                        .annotations()
+                        .is_some_and(|annotations| annotations.optional)
+                };
+                // Either one rejects the field in a direction that used to accept it.
+                let read_only = |validator: &AS3Validator| {
+                    validator
+                        .annotations()
+                        .is_some_and(|annotations| annotations.read_only)
+                };
+                let write_only = |validator: &AS3Validator| {
+                    validator
+                        .annotations()
+                        .is_some_and(|annotations| annotations.write_only)
+                };
+                match old_properties.get(key) {
+                    Some(AS3Validator::Flatten(old)) => match validator {
+                        AS3Validator::Flatten(new) => compare(new, old, path, found),
+                        _ => messages.push(format!("flattened `{key}` is now a field")),
+                    },
+                    Some(old) => {
+                        if optional(old) && !optional(validator) {
+                            messages.push(format!("field `{key}` is now required"));
+                        }
+                        if read_only(validator) && !read_only(old) {
+                            messages.push(format!(
+                                "field `{key}` is now read-only, so requests may no longer send it"
+                            ));
+                        }
+                        if write_only(validator) && !write_only(old) {
+                            messages.push(format!(
+                                "field `{key}` is now write-only, so responses may no longer include it"
+                            ));
+                        }
+                        path.push(key.clone());
+                        compare(validator, old, path, found);
+                        path.pop();
+                    }
+                    None if !optional(validator) => {
+                        messages.push(format!("new required field `{key}`"));
+                    }
+                    None => {}
+                }
+            }
+        }
+        (
+            AS3Validator::String {
+                regex,
+                min_length,
+                max_length,
+                format,
+                case,
+                no_surrounding_whitespace,
+                non_empty,
+                min_bytes,
+                max_bytes,
+                normalize,
+                grammar,
+            },
+            AS3Validator::String {
+                regex: old_regex,
+                min_length: old_min_length,
+                max_length: old_max_length,
+                format: old_format,
+                case: old_case,
+                no_surrounding_whitespace: old_no_surrounding_whitespace,
+                non_empty: old_non_empty,
+                min_bytes: old_min_bytes,
+                max_bytes: old_max_bytes,
+                normalize: old_normalize,
+                grammar: old_grammar,
+            },
+        ) => {
+            // Normalizing can turn a value the checks accepted into one they reject, so
+            // any change of form is reported.
+            if normalize != old_normalize {
+                messages.push(format!(
+                    "normalization changed from {} to {}",
+                    old_normalize.map_or("none", |form| form.name()),
+                    normalize.map_or("none", |form| form.name())
+                ));
+            }
+            if regex.is_some() && regex != old_regex {
+                messages.push(tightened("regex", old_regex, regex));
+            }
+            if grammar.is_some() && grammar != old_grammar {
+                messages.push(tightened("grammar", old_grammar, grammar));
+            }
+            if raised(min_length, old_min_length) {
+                messages.push(tightened("minimum length", old_min_length, min_length));
+            }
+            if lowered(max_length, old_max_length) {
+                messages.push(tightened("maximum length", old_max_length, max_length));
+            }
+            if format.is_some() && format != old_format {
+                messages.push(tightened("format", old_format, format));
+            }
+            if case.is_some() && case != old_case {
+                messages.push(tightened("case", old_case, case));
+            }
+            if *no_surrounding_whitespace && !old_no_surrounding_whitespace {
+                messages.push("surrounding whitespace is now rejected".to_string());
+            }
+            if *non_empty && !old_non_empty {
+                messages.push("empty strings are now rejected".to_string());
+            }
+            if raised(min_bytes, old_min_bytes) {
+                messages.push(tightened("minimum size in bytes", old_min_bytes, min_bytes));
+            }
+            if lowered(max_bytes, old_max_bytes) {
+                messages.push(tightened("maximum size in bytes", old_max_bytes, max_bytes));
+            }
+        }
+        (
+            AS3Validator::Integer {
+                minimum,
+                maximum,
+                step,
+                step_origin,
+            },
+            AS3Validator::Integer {
+                minimum: old_minimum,
+                maximum: old_maximum,
+                step: old_step,
+                step_origin: old_step_origin,
+            },
+        ) => {
+            // Every old step is still on a new one when the new step divides the old step
+            // and the origins are a whole number of new steps apart.
+            if let Some(step) = step.filter(|step| *step > 0) {
+                let offset =
+                    old_step_origin.unwrap_or(0) as i128 - step_origin.unwrap_or(0) as i128;
+                let still_on_step = old_step
+                    .is_some_and(|old_step| old_step % step == 0 && offset % step as i128 == 0);
+                if !still_on_step {
+                    messages.push(format!(
+                        "step tightened to every {step} from {}",
+                        step_origin.unwrap_or(0)
+                    ));
+                }
+            }
+            if raised(minimum, old_minimum) {
+                messages.push(tightened("minimum", old_minimum, minimum));
+            }
+            if lowered(maximum, old_maximum) {
+                messages.push(tightened("maximum", old_maximum, maximum));
+            }
+        }
+        (
+            AS3Validator::Decimal {
+                minimum,
+                maximum,
+                max_decimal_places,
+            },
+            AS3Validator::Decimal {
+                minimum: old_minimum,
+                maximum: old_maximum,
+                max_decimal_places: old_max_decimal_places,
+            },
+        ) => {
+            if raised(minimum, old_minimum) {
+                messages.push(tightened("minimum", old_minimum, minimum));
+            }
+            if lowered(maximum, old_maximum) {
+                messages.push(tightened("maximum", old_maximum, maximum));
+            }
+            if lowered(max_decimal_places, old_max_decimal_places) {
+                messages.push(tightened(
+                    "maximum decimal places",
+                    old_max_decimal_places,
+                    max_decimal_places,
+                ));
+            }
+        }
+        (
+            AS3Validator::NumericString { format_mask },
+            AS3Validator::NumericString {
+                format_mask: old_format_mask,
+            },
+        ) => {
+            if format_mask.is_some() && format_mask != old_format_mask {
+                messages.push(tightened("format mask", old_format_mask, format_mask));
+            }
+        }
+        (
+            AS3Validator::List {
+                items,
+                max_validated,
+                non_empty,
+            },
+            AS3Validator::List {
+                items: old_items,
+                max_validated: old_max_validated,
+                non_empty: old_non_empty,
+            },
+        ) => {
+            if *non_empty && !old_non_empty {
+                messages.push("empty lists are now rejected".to_string());
+            }
+            if lowered(max_validated, old_max_validated) {
+                messages.push(tightened(
+                    "maximum list length",
+                    old_max_validated,
+                    max_validated,
+                ));
+            }
+            path.push("*".to_string());
+            compare(items, old_items, path, found);
+            path.pop();
+        }
+        (
+            AS3Validator::Tuple { items, rest },
+            AS3Validator::Tuple {
+                items: old_items,
+                rest: old_rest,
+            },
+        ) => {
+            if items.len() != old_items.len() {
+                messages.push(tightened(
+                    "tuple length",
+                    &Some(old_items.len()),
+                    &Some(items.len()),
+                ));
+            }
+            for (index, (item, old_item)) in items.iter().zip(old_items).enumerate() {
+                path.push(index.to_string());
+                compare(item, old_item, path, found);
+                path.pop();
+            }
+            match (rest, old_rest) {
+                (Some(rest), Some(old_rest)) => compare(rest, old_rest, path, found),
+                (None, Some(_)) => {
+                    messages.push("extra tuple elements are no longer accepted".to_string())
+                }
+                _ => {}
+            }
+        }
+        (AS3Validator::Positional { fields }, AS3Validator::Positional { fields: old_fields }) => {
+            let names = |fields: &[(String, AS3Validator)]| -> Vec<String> {
+                fields.iter().map(|(name, _)| name.clone()).collect()
+            };
+            if names(fields) != names(old_fields) {
+                messages.push(format!(
+                    "positional fields changed from {:?} to {:?}",
+                    names(old_fields),
+                    names(fields)
+                ));
+            } else {
+                for ((name, validator), (_, old)) in fields.iter().zip(old_fields) {
+                    path.push(name.clone());
+                    compare(validator, old, path, found);
+                    path.pop();
+                }
+            }
+        }
+        (
+            AS3Validator::Map {
+                key_type,
+                value_type,
+            },
+            AS3Validator::Map {
+                key_type: old_key_type,
+                value_type: old_value_type,
+            },
+        ) => {
+            path.push("+KeyType".to_string());
+            compare(key_type, old_key_type, path, found);
+            path.pop();
+            path.push("*".to_string());
+            compare(value_type, old_value_type, path, found);
+            path.pop();
+        }
+        (AS3Validator::Flatten(validator), AS3Validator::Flatten(old)) => {
+            compare(validator, old, path, found)
+        }
+        #[cfg(feature = "chrono")]
+        (
+            AS3Validator::Date { after, before },
+            AS3Validator::Date {
+                after: old_after,
+                before: old_before,
+            },
+        ) => {
+            // ISO-8601 dates order the same as their text.
+            if raised(after, old_after) {
+                messages.push(tightened("after date", old_after, after));
+            }
+            if lowered(before, old_before) {
+                messages.push(tightened("before date", old_before, before));
+            }
+        }
+        #[cfg(feature = "chrono")]
+        (
+            AS3Validator::DateTime {
+                require_timezone,
+                utc_only,
+            },
+            AS3Validator::DateTime {
+                require_timezone: old_require_timezone,
+                utc_only: old_utc_only,
+            },
+        ) => {
+            if require_timezone.is_some() && require_timezone != old_require_timezone {
+                messages.push(tightened(
+                    "+RequireTimezone",
+                    old_require_timezone,
+                    require_timezone,
+                ));
+            }
+            if *utc_only && !old_utc_only {
+                messages.push("datetimes must now be in UTC".to_string());
+            }
+        }
+        #[cfg(feature = "bigdecimal")]
+        (
+            AS3Validator::BigNumber { minimum, maximum },
+            AS3Validator::BigNumber {
+                minimum: old_minimum,
+                maximum: old_maximum,
+            },
+        ) => {
+            use std::str::FromStr;
+
+            let parse = |bound: &Option<String>| {
+                bound
+                    .as_deref()
+                    .and_then(|bound| bigdecimal::BigDecimal::from_str(bound.trim()).ok())
+            };
+            if raised(&parse(minimum), &parse(old_minimum)) {
+                messages.push(tightened("minimum", old_minimum, minimum));
+            }
+            if lowered(&parse(maximum), &parse(old_maximum)) {
+                messages.push(tightened("maximum", old_maximum, maximum));
+            }
+        }
+        (AS3Validator::Const(value), AS3Validator::Const(old_value)) => {
+            if value != old_value {
+                messages.push(format!("now requires {}", value.describe()));
+            }
+        }
+        (AS3Validator::Enum(entries), AS3Validator::Enum(old_entries)) => {
+            for old_entry in old_entries {
+                let still_accepted = match old_entry {
+                    AS3Validator::Const(value) => entries.iter().any(|entry| entry.is_valid(value)),
+                    // Whether other entries now accept everything this one did cannot be
+                    // decided in general, so only an identical entry keeps it.
+                    old_entry => entries.contains(old_entry),
+                };
+                if !still_accepted {
+                    messages.push(match old_entry {
+                        AS3Validator::Const(value) => {
+                            format!("enum no longer accepts {}", value.describe())
+                        }
+                        old_entry => {
+                            format!("enum no longer has its {} entry", old_entry.type_name())
+                        }
+                    });
+                }
+            }
+        }
+        (AS3Validator::OneOf(branches), AS3Validator::OneOf(old_branches)) => {
+            if branches != old_branches {
+                messages.push("OneOf branches changed".to_string());
+            }
+        }
+        (AS3Validator::AnyOf(branches), AS3Validator::AnyOf(old_branches)) => {
+            for old_branch in old_branches {
+                if !branches.contains(old_branch) {
+                    messages.push(format!(
+                        "AnyOf no longer has its {} branch",
+                        old_branch.type_name()
+                    ));
+                }
+            }
+        }
+        (AS3Validator::AllOf(validators), AS3Validator::AllOf(old_validators)) => {
+            for validator in validators {
+                if !old_validators.contains(validator) {
+                    messages.push(format!(
+                        "AllOf has a new {} validator",
+                        validator.type_name()
+                    ));
+                }
+            }
+        }
+        (AS3Validator::Ref(url), AS3Validator::Ref(old_url)) => {
+            if url != old_url {
+                messages.push(format!("reference changed from `{old_url}` to `{url}`"));
+            }
+        }
+        (
+            AS3Validator::Discriminated { key, mapping },
+            AS3Validator::Discriminated {
+                key: old_key,
+                mapping: old_mapping,
+            },
+        ) => {
+            if key != old_key {
+                messages.push(format!("discriminator changed from `{old_key}` to `{key}`"));
+            }
+            let mut tags: Vec<_> = old_mapping.keys().collect();
+            tags.sort();
+            for tag in tags {
+                match mapping.get(tag) {
+                    Some(validator) => compare(validator, &old_mapping[tag], path, found),
+                    None => messages.push(format!("discriminator value `{tag}` was removed")),
+                }
+            }
+        }
+        (newer, older) => messages.push(format!(
+            "type changed from {} to {}",
+            older.type_name(),
+            newer.type_name()
+        )),
+    }
+    for message in messages {
+        found.push(Incompatibility {
+            path: path.clone(),
+            message,
+        });
+    }
+}
+
+/// Whether a lower bound now excludes values the old one allowed.
+fn raised<T: PartialOrd>(newer: &Option<T>, older: &Option<T>) -> bool {
+    match (newer, older) {
+        (Some(newer), Some(older)) => newer > older,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+/// Whether an upper bound now excludes values the old one allowed.
+fn lowered<T: PartialOrd>(newer: &Option<T>, older: &Option<T>) -> bool {
+    match (newer, older) {
+        (Some(newer), Some(older)) => newer < older,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+fn tightened<T: fmt::Debug>(what: &str, older: &Option<T>, newer: &Option<T>) -> String {
+    let show = |bound: &Option<T>| match bound {
+        Some(bound) => format!("{bound:?}"),
+        None => "none".to_string(),
+    };
+    format!("{what} tightened from {} to {}", show(older), show(newer))
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::options::Validation;
+use crate::AS3ValidationError;
+
+/// Checks that `value` is an ISO-8601 calendar date within the inclusive `after`/`before` bounds.
+pub(crate) fn check_date(
+    value: &str,
+    after: Option<&str>,
+    before: Option<&str>,
+    validation: &mut Validation,
+) {
+    let Some(date) = parse_date(value, validation) else {
+        return;
+    };
+    let after_bound = after.and_then(|after| parse_date(after, validation));
+    let before_bound = before.and_then(|before| parse_date(before, validation));
+
+    let too_early = after_bound.is_some_and(|after| date < after);
+    let too_late = before_bound.is_some_and(|before| date > before);
+    if too_early || too_late {
+        validation.push(AS3ValidationError::DateRange {
+            date: value.to_string(),
+            after: after.map(|after| after.to_string()),
+            before: before.map(|before| before.to_string()),
+        });
+    }
+}
+
+fn parse_date(value: &str, validation: &mut Validation) -> Option<NaiveDate> {
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => Some(date),
+        Err(_) => {
+            validation.push(AS3ValidationError::FormatError {
+                value: value.to_string(),
+                format: "date",
+            });
+            None
+        }
+    }
+}
+
+/// Checks that `value` is an ISO-8601 date and time whose offset, or lack of one, is
+/// allowed by `require_timezone` and `utc_only`.
+pub(crate) fn check_date_time(
+    value: &str,
+    require_timezone: Option<bool>,
+    utc_only: bool,
+    validation: &mut Validation,
+) {
+    let has_offset = if DateTime::parse_from_rfc3339(value).is_ok() {
+        true
+    } else if NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").is_ok() {
+        false
+    } else {
+        validation.push(AS3ValidationError::FormatError {
+            value: value.to_string(),
+            format: "date-time",
+        });
+        return;
+    };
+
+    let value = value.to_string();
+    match (has_offset, require_timezone) {
+        (false, Some(true)) => validation.push(AS3ValidationError::MissingTimezone { value }),
+        (false, _) if utc_only => validation.push(AS3ValidationError::MissingTimezone { value }),
+        (true, Some(false)) => validation.push(AS3ValidationError::UnexpectedTimezone { value }),
+        (true, _) if utc_only && !value.ends_with(['Z', 'z']) => {
+            validation.push(AS3ValidationError::NotUtc { value })
+        }
+        _ => {}
+    }
+}
+
+/// Whether `value` is an ISO-8601 duration: `P` followed by at least one date or time
+/// component, as in `P1Y2M`, `P3W` or `PT1H30.5S`.
+pub(crate) fn is_duration(value: &str) -> bool {
+    let pattern = r"^P(\d+Y)?(\d+M)?(\d+W)?(\d+D)?(T(\d+H)?(\d+M)?(\d+([.,]\d+)?S)?)?$";
+    crate::strings::compiled(pattern).is_some_and(|duration| duration.is_match(value))
+        && value != "P"
+        && !value.ends_with('T')
+}
+
+/// Whether `value` is an ISO-8601 time of day, with optional fractional seconds.
+pub(crate) fn is_time(value: &str) -> bool {
+    NaiveTime::parse_from_str(value, "%H:%M:%S%.f").is_ok()
+}
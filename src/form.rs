@@ -0,0 +1,251 @@
+use serde_json::{json, Map, Value};
+
+use crate::AS3Validator;
+
+impl AS3Validator {
+    /// Describes the schema for rendering an input form, as JSON a frontend can walk.
+    ///
+    /// Each field is an object with its `name`, dotted `path`, `type`, whether it is
+    /// `required`, a `title` (the `+Title`, falling back to the name), any `description`,
+    /// and the `constraints` that are set. Objects list their `fields` sorted by name,
+    /// with flattened fields inlined, and lists describe their elements under `items`:
+    ///
+    /// ```json
+    /// { "name": "age", "path": "age", "type": "Integer", "required": true,
+    ///   "title": "Age", "constraints": { "minimum": 20 } }
+    /// ```
+    pub fn to_form_descriptor(&self) -> Value {
+        describe(self, "", "")
+    }
+}
+
+fn describe(validator: &AS3Validator, name: &str, path: &str) -> Value {
+    let annotations = validator.annotations().cloned().unwrap_or_default();
+    let mut field = Map::new();
+    field.insert("name".to_string(), json!(name));
+    field.insert("path".to_string(), json!(path));
+    field.insert("type".to_string(), json!(validator.type_name()));
+    field.insert("required".to_string(), json!(!annotations.optional));
+    field.insert(
+        "title".to_string(),
+        json!(annotations.title.as_deref().unwrap_or(name)),
+    );
+    if let Some(description) = &annotations.description {
+        field.insert("description".to_string(), json!(description));
+    }
+    if !annotations.examples.is_empty() {
+        let examples = annotations.examples.iter().map(Value::from).collect();
+        field.insert("examples".to_string(), Value::Array(examples));
+    }
+    for (flag, set) in [
+        ("deprecated", annotations.deprecated),
+        ("read_only", annotations.read_only),
+        ("write_only", annotations.write_only),
+    ] {
+        if set {
+            field.insert(flag.to_string(), json!(true));
+        }
+    }
+
+    let mut constraints = Map::new();
+    let mut constrain = |name: &str, value: Value| {
+        if !value.is_null() && value != json!(false) {
+            constraints.insert(name.to_string(), value);
+        }
+    };
+    match validator.unannotated() {
+        AS3Validator::Object { non_empty, .. } => {
+            constrain("non_empty", json!(non_empty));
+            let mut fields = vec![];
+            collect_fields(validator, path, &mut fields);
+            fields.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+            field.insert("fields".to_string(), Value::Array(fields));
+        }
+        AS3Validator::String {
+            regex,
+            min_length,
+            max_length,
+            format,
+            case,
+            no_surrounding_whitespace,
+            non_empty,
+            min_bytes,
+            max_bytes,
+            normalize,
+            grammar,
+        } => {
+            constrain("regex", json!(regex));
+            constrain("min_length", json!(min_length));
+            constrain("max_length", json!(max_length));
+            constrain("format", json!(format.map(|format| format.name())));
+            constrain("case", json!(case.map(|case| case.name())));
+            constrain(
+                "no_surrounding_whitespace",
+                json!(no_surrounding_whitespace),
+            );
+            constrain("non_empty", json!(non_empty));
+            constrain("min_bytes", json!(min_bytes));
+            constrain("max_bytes", json!(max_bytes));
+            constrain("normalize", json!(normalize.map(|form| form.name())));
+            constrain("grammar", json!(grammar));
+        }
+        AS3Validator::Integer {
+            minimum,
+            maximum,
+            step,
+            step_origin,
+        } => {
+            constrain("minimum", json!(minimum));
+            constrain("maximum", json!(maximum));
+            constrain("step", json!(step));
+            constrain("step_origin", json!(step_origin));
+        }
+        AS3Validator::Decimal {
+            minimum,
+            maximum,
+            max_decimal_places,
+        } => {
+            constrain("minimum", json!(minimum));
+            constrain("maximum", json!(maximum));
+            constrain("max_decimal_places", json!(max_decimal_places));
+        }
+        AS3Validator::NumericString { format_mask } => {
+            constrain("format_mask", json!(format_mask));
+        }
+        AS3Validator::List {
+            items,
+            max_validated,
+            non_empty,
+        } => {
+            constrain("max_items", json!(max_validated));
+            constrain("non_empty", json!(non_empty));
+            field.insert(
+                "items".to_string(),
+                describe(items, name, &format!("{path}[]")),
+            );
+        }
+        AS3Validator::Tuple { items, rest } => {
+            let items = items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| describe(item, name, &format!("{path}[{index}]")))
+                .collect();
+            field.insert("items".to_string(), Value::Array(items));
+            if let Some(rest) = rest {
+                field.insert(
+                    "rest".to_string(),
+                    describe(rest, name, &format!("{path}[]")),
+                );
+            }
+        }
+        AS3Validator::Positional { fields } => {
+            let fields = fields
+                .iter()
+                .map(|(key, property)| {
+                    let path = match path {
+                        "" => key.clone(),
+                        path => format!("{path}.{key}"),
+                    };
+                    describe(property, key, &path)
+                })
+                .collect();
+            field.insert("fields".to_string(), Value::Array(fields));
+        }
+        AS3Validator::Map { value_type, .. } => {
+            field.insert(
+                "values".to_string(),
+                describe(value_type, name, &format!("{path}.*")),
+            );
+        }
+        #[cfg(feature = "chrono")]
+        AS3Validator::Date { after, before } => {
+            constrain("after", json!(after));
+            constrain("before", json!(before));
+        }
+        #[cfg(feature = "chrono")]
+        AS3Validator::DateTime {
+            require_timezone,
+            utc_only,
+        } => {
+            let timezone = require_timezone.map(|required| match required {
+                true => "required",
+                false => "forbidden",
+            });
+            constrain("timezone", json!(timezone));
+            constrain("utc_only", json!(utc_only));
+        }
+        #[cfg(feature = "bigdecimal")]
+        AS3Validator::BigNumber { minimum, maximum } => {
+            constrain("minimum", json!(minimum));
+            constrain("maximum", json!(maximum));
+        }
+        AS3Validator::Const(value) => constrain("value", Value::from(value)),
+        AS3Validator::Enum(entries) => {
+            let mut values = vec![];
+            let mut others = vec![];
+            for entry in entries {
+                match entry.unannotated() {
+                    AS3Validator::Const(value) => values.push(Value::from(value)),
+                    _ => others.push(describe(entry, name, path)),
+                }
+            }
+            if !values.is_empty() {
+                constrain("values", Value::Array(values));
+            }
+            if !others.is_empty() {
+                field.insert("entries".to_string(), Value::Array(others));
+            }
+        }
+        AS3Validator::OneOf(branches) => {
+            let branches = branches
+                .iter()
+                .map(|branch| describe(branch, name, path))
+                .collect();
+            field.insert("branches".to_string(), Value::Array(branches));
+        }
+        AS3Validator::AnyOf(branches) => {
+            let branches = branches
+                .iter()
+                .map(|branch| describe(branch, name, path))
+                .collect();
+            field.insert("any_of".to_string(), Value::Array(branches));
+        }
+        AS3Validator::AllOf(validators) => {
+            let validators = validators
+                .iter()
+                .map(|validator| describe(validator, name, path))
+                .collect();
+            field.insert("all_of".to_string(), Value::Array(validators));
+        }
+        AS3Validator::Discriminated { key, mapping } => {
+            field.insert("discriminator".to_string(), json!(key));
+            let variants = mapping
+                .iter()
+                .map(|(tag, variant)| (tag.clone(), describe(variant, name, path)))
+                .collect();
+            field.insert("variants".to_string(), Value::Object(variants));
+        }
+        AS3Validator::Ref(url) => {
+            field.insert("ref".to_string(), json!(url));
+        }
+        AS3Validator::Flatten(_) | AS3Validator::Annotated { .. } | AS3Validator::Any => {}
+    }
+    if !constraints.is_empty() {
+        field.insert("constraints".to_string(), Value::Object(constraints));
+    }
+    Value::Object(field)
+}
+
+/// The fields of an object, including those of the objects it flattens.
+fn collect_fields(validator: &AS3Validator, path: &str, fields: &mut Vec<Value>) {
+    let AS3Validator::Object { properties, .. } = validator.unannotated() else {
+        return;
+    };
+    for (key, property) in properties {
+        match property {
+            AS3Validator::Flatten(flattened) => collect_fields(flattened, path, fields),
+            _ if path.is_empty() => fields.push(describe(property, key, key)),
+            _ => fields.push(describe(property, key, &format!("{path}.{key}"))),
+        }
+    }
+}
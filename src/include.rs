@@ -0,0 +1,130 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{strip_bom, AS3SchemaError, AS3Validator};
+
+impl AS3Validator {
+    /// Loads a YAML schema from `path`, resolving `+Include` and `$ref` directives.
+    ///
+    /// `+Include: other.yml` splices in the `Root` of another file, while
+    /// `$ref: "./other.yml#/Root/vehicles"` splices in the node at a JSON Pointer inside
+    /// it. Paths are relative to the file containing the directive, and any other keys
+    /// next to the directive override those of the included node.
+    pub fn from_yaml_file(path: impl AsRef<Path>) -> Result<AS3Validator, AS3SchemaError> {
+        let mut stack = vec![];
+        let schema = load_resolved(path.as_ref(), &mut stack)?;
+        AS3Validator::from(&schema)
+    }
+
+    /// Loads a schema from `path`, picking the parser from its extension: `.yml` and
+    /// `.yaml` are read as YAML and `.json` as JSON, both laid out as in
+    /// [`AS3Validator::from`] and resolving includes like
+    /// [`AS3Validator::from_yaml_file`]. Any other extension is an error.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<AS3Validator, AS3SchemaError> {
+        let path = path.as_ref();
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yml" | "yaml" | "json") => AS3Validator::from_yaml_file(path),
+            extension => Err(format!(
+                "Unable to tell the format of `{}` from {}; expected .yml, .yaml or .json",
+                path.display(),
+                extension.map_or("no extension".to_string(), |extension| format!(
+                    "its extension `.{extension}`"
+                )),
+            )
+            .into()),
+        }
+    }
+}
+
+fn load_resolved(path: &Path, stack: &mut Vec<PathBuf>) -> Result<serde_yaml::Value, String> {
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("Unable to read `{}`: {e}", path.display()))?;
+    if stack.contains(&path) {
+        let cycle: Vec<String> = stack
+            .iter()
+            .chain([&path])
+            .map(|path| path.display().to_string())
+            .collect();
+        return Err(format!("Include cycle: {}", cycle.join(" -> ")));
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Unable to read `{}`: {e}", path.display()))?;
+    let mut schema = parse(&path, strip_bom(&content))
+        .map_err(|e| format!("Unable to parse `{}`: {e}", path.display()))?;
+
+    stack.push(path.clone());
+    resolve(&mut schema, path.parent().unwrap_or(Path::new(".")), stack)?;
+    stack.pop();
+    Ok(schema)
+}
+
+fn resolve(
+    node: &mut serde_yaml::Value,
+    base: &Path,
+    stack: &mut Vec<PathBuf>,
+) -> Result<(), String> {
+    match node {
+        serde_yaml::Value::Mapping(mapping) => {
+            let target = if let Some(include) = mapping.remove("+Include") {
+                let file = include.as_str().ok_or("+Include must be a file path")?;
+                Some((file.to_string(), "/Root".to_string()))
+            } else if let Some(reference) = mapping.remove("$ref") {
+                let reference = reference.as_str().ok_or("$ref must be a string")?;
+                let (file, pointer) = reference.split_once('#').unwrap_or((reference, "/Root"));
+                Some((file.to_string(), pointer.to_string()))
+            } else {
+                None
+            };
+
+            for (_, value) in mapping.iter_mut() {
+                resolve(value, base, stack)?;
+            }
+
+            if let Some((file, pointer)) = target {
+                let included = load_resolved(&base.join(&file), stack)?;
+                let serde_yaml::Value::Mapping(mut merged) = lookup(&included, &pointer)
+                    .ok_or_else(|| format!("`{file}#{pointer}` does not exist"))?
+                    .clone()
+                else {
+                    return Err(format!("`{file}#{pointer}` is not a mapping"));
+                };
+                for (key, value) in mapping.iter() {
+                    merged.insert(key.clone(), value.clone());
+                }
+                *mapping = merged;
+            }
+            Ok(())
+        }
+        serde_yaml::Value::Sequence(sequence) => sequence
+            .iter_mut()
+            .try_for_each(|value| resolve(value, base, stack)),
+        _ => Ok(()),
+    }
+}
+
+/// Reads `.json` files with the JSON parser, for its stricter syntax and error messages,
+/// and anything else as YAML.
+fn parse(path: &Path, content: &str) -> Result<serde_yaml::Value, String> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => {
+            let json: serde_json::Value =
+                serde_json::from_str(content).map_err(|e| e.to_string())?;
+            serde_yaml::to_value(json).map_err(|e| e.to_string())
+        }
+        _ => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+    }
+}
+
+/// Follows an RFC 6901 JSON Pointer through a YAML document.
+fn lookup<'a>(node: &'a serde_yaml::Value, pointer: &str) -> Option<&'a serde_yaml::Value> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .try_fold(node, |node, segment| match node {
+            serde_yaml::Value::Sequence(sequence) => sequence.get(segment.parse::<usize>().ok()?),
+            _ => node.get(segment.as_str()),
+        })
+}
@@ -15,39 +15,126 @@ fn should_run() {
       }
     });
 
-    let validator = AS3Validator::Object(HashMap::from([
-        (
-            "age".to_owned(),
-            AS3Validator::Integer { minimum: Some(20) },
-        ),
-        (
-            "children".to_owned(),
-            AS3Validator::Integer { minimum: Some(2) },
-        ),
-        (
-            "name".to_owned(),
-            AS3Validator::String {
-                // The name should start with an Uppercase letter
-                regex: Some("^[A-Z][a-z]".to_owned()),
-            },
-        ),
-        (
-            "vehicles".to_owned(),
-            AS3Validator::Object(HashMap::from([(
-                "list".to_owned(),
-                AS3Validator::List(Box::new(AS3Validator::Object(HashMap::from([
-                    ("name".to_owned(), AS3Validator::String { regex: None }),
-                    (
-                        "maker".to_owned(),
-                        AS3Validator::String {
-                            regex: Some("^[A-Z][a-z]".to_owned()),
+    let validator = AS3Validator::Object {
+        properties: HashMap::from([
+            (
+                "age".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(20),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "children".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(2),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "name".to_owned(),
+                AS3Validator::String {
+                    // The name should start with an Uppercase letter
+                    regex: Some("^[A-Z][a-z]".to_owned()),
+                    min_length: None,
+                    max_length: None,
+                    format: None,
+                    case: None,
+                    no_surrounding_whitespace: false,
+                    non_empty: false,
+                    min_bytes: None,
+                    max_bytes: None,
+                    normalize: None,
+                    grammar: None,
+                },
+            ),
+            (
+                "vehicles".to_owned(),
+                AS3Validator::Object {
+                    properties: HashMap::from([(
+                        "list".to_owned(),
+                        AS3Validator::List {
+                            items: Arc::new(AS3Validator::Object {
+                                properties: HashMap::from([
+                                    (
+                                        "name".to_owned(),
+                                        AS3Validator::String {
+                                            regex: None,
+                                            min_length: None,
+                                            max_length: None,
+                                            format: None,
+                                            case: None,
+                                            no_surrounding_whitespace: false,
+                                            non_empty: false,
+                                            min_bytes: None,
+                                            max_bytes: None,
+                                            normalize: None,
+                                            grammar: None,
+                                        },
+                                    ),
+                                    (
+                                        "maker".to_owned(),
+                                        AS3Validator::String {
+                                            regex: Some("^[A-Z][a-z]".to_owned()),
+                                            min_length: None,
+                                            max_length: None,
+                                            format: None,
+                                            case: None,
+                                            no_surrounding_whitespace: false,
+                                            non_empty: false,
+                                            min_bytes: None,
+                                            max_bytes: None,
+                                            normalize: None,
+                                            grammar: None,
+                                        },
+                                    ),
+                                    (
+                                        "year".to_owned(),
+                                        AS3Validator::Integer {
+                                            minimum: None,
+                                            maximum: None,
+                                            step: None,
+                                            step_origin: None,
+                                        },
+                                    ),
+                                ]),
+                                property_names: None,
+                                when_present: None,
+                                dependent_required: HashMap::new(),
+                                additional_properties: true,
+                                additional_properties_schema: None,
+                                require_sorted_keys: false,
+                                non_empty: false,
+                                rules: vec![],
+                            }),
+                            max_validated: None,
+                            non_empty: false,
                         },
-                    ),
-                    ("year".to_owned(), AS3Validator::Integer { minimum: None }),
-                ])))),
-            )])),
-        ),
-    ]));
+                    )]),
+                    property_names: None,
+                    when_present: None,
+                    dependent_required: HashMap::new(),
+                    additional_properties: true,
+                    additional_properties_schema: None,
+                    require_sorted_keys: false,
+                    non_empty: false,
+                    rules: vec![],
+                },
+            ),
+        ]),
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    };
 
     assert_eq!(validator.validate(&AS3Data::from(&json)), Ok(()));
 }
@@ -66,45 +153,133 @@ fn with_decimal_error() {
       }
     });
 
-    let validator = AS3Validator::Object(HashMap::from([
-        (
-            "age".to_owned(),
-            AS3Validator::Integer { minimum: Some(20) },
-        ),
-        (
-            "children".to_owned(),
-            AS3Validator::Integer { minimum: Some(2) },
-        ),
-        (
-            "name".to_owned(),
-            AS3Validator::String {
-                // The name should start with an Uppercase letter
-                regex: Some("^[A-Z][a-z]".to_owned()),
-            },
-        ),
-        (
-            "vehicles".to_owned(),
-            AS3Validator::Object(HashMap::from([(
-                "list".to_owned(),
-                AS3Validator::List(Box::new(AS3Validator::Object(HashMap::from([
-                    ("name".to_owned(), AS3Validator::String { regex: None }),
-                    (
-                        "maker".to_owned(),
-                        AS3Validator::String {
-                            regex: Some("^[A-Z][a-z]".to_owned()),
+    let validator = AS3Validator::Object {
+        properties: HashMap::from([
+            (
+                "age".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(20),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "children".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(2),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "name".to_owned(),
+                AS3Validator::String {
+                    // The name should start with an Uppercase letter
+                    regex: Some("^[A-Z][a-z]".to_owned()),
+                    min_length: None,
+                    max_length: None,
+                    format: None,
+                    case: None,
+                    no_surrounding_whitespace: false,
+                    non_empty: false,
+                    min_bytes: None,
+                    max_bytes: None,
+                    normalize: None,
+                    grammar: None,
+                },
+            ),
+            (
+                "vehicles".to_owned(),
+                AS3Validator::Object {
+                    properties: HashMap::from([(
+                        "list".to_owned(),
+                        AS3Validator::List {
+                            items: Arc::new(AS3Validator::Object {
+                                properties: HashMap::from([
+                                    (
+                                        "name".to_owned(),
+                                        AS3Validator::String {
+                                            regex: None,
+                                            min_length: None,
+                                            max_length: None,
+                                            format: None,
+                                            case: None,
+                                            no_surrounding_whitespace: false,
+                                            non_empty: false,
+                                            min_bytes: None,
+                                            max_bytes: None,
+                                            normalize: None,
+                                            grammar: None,
+                                        },
+                                    ),
+                                    (
+                                        "maker".to_owned(),
+                                        AS3Validator::String {
+                                            regex: Some("^[A-Z][a-z]".to_owned()),
+                                            min_length: None,
+                                            max_length: None,
+                                            format: None,
+                                            case: None,
+                                            no_surrounding_whitespace: false,
+                                            non_empty: false,
+                                            min_bytes: None,
+                                            max_bytes: None,
+                                            normalize: None,
+                                            grammar: None,
+                                        },
+                                    ),
+                                    (
+                                        "year".to_owned(),
+                                        AS3Validator::Integer {
+                                            minimum: None,
+                                            maximum: None,
+                                            step: None,
+                                            step_origin: None,
+                                        },
+                                    ),
+                                ]),
+                                property_names: None,
+                                when_present: None,
+                                dependent_required: HashMap::new(),
+                                additional_properties: true,
+                                additional_properties_schema: None,
+                                require_sorted_keys: false,
+                                non_empty: false,
+                                rules: vec![],
+                            }),
+                            max_validated: None,
+                            non_empty: false,
                         },
-                    ),
-                    ("year".to_owned(), AS3Validator::Integer { minimum: None }),
-                ])))),
-            )])),
-        ),
-    ]));
+                    )]),
+                    property_names: None,
+                    when_present: None,
+                    dependent_required: HashMap::new(),
+                    additional_properties: true,
+                    additional_properties_schema: None,
+                    require_sorted_keys: false,
+                    non_empty: false,
+                    rules: vec![],
+                },
+            ),
+        ]),
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    };
 
     assert_eq!(
         validator.validate(&AS3Data::from(&json)),
         Err(AS3ValidationError::TypeError {
-            expected: AS3Validator::Integer { minimum: None },
-            got: AS3Data::Decimal(20.18)
+            expected: "Integer",
+            got: "Decimal",
+            preview: Some("20.18".to_string())
         })
     );
 }
@@ -122,45 +297,133 @@ fn with_string_error() {
       }
     });
 
-    let validator = AS3Validator::Object(HashMap::from([
-        (
-            "age".to_owned(),
-            AS3Validator::Integer { minimum: Some(20) },
-        ),
-        (
-            "children".to_owned(),
-            AS3Validator::Integer { minimum: Some(2) },
-        ),
-        (
-            "name".to_owned(),
-            AS3Validator::String {
-                // The name should start with an Uppercase letter
-                regex: Some("^[A-Z][a-z]".to_owned()),
-            },
-        ),
-        (
-            "vehicles".to_owned(),
-            AS3Validator::Object(HashMap::from([(
-                "list".to_owned(),
-                AS3Validator::List(Box::new(AS3Validator::Object(HashMap::from([
-                    ("name".to_owned(), AS3Validator::String { regex: None }),
-                    (
-                        "maker".to_owned(),
-                        AS3Validator::String {
-                            regex: Some("^[A-Z][a-z]".to_owned()),
+    let validator = AS3Validator::Object {
+        properties: HashMap::from([
+            (
+                "age".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(20),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "children".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(2),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "name".to_owned(),
+                AS3Validator::String {
+                    // The name should start with an Uppercase letter
+                    regex: Some("^[A-Z][a-z]".to_owned()),
+                    min_length: None,
+                    max_length: None,
+                    format: None,
+                    case: None,
+                    no_surrounding_whitespace: false,
+                    non_empty: false,
+                    min_bytes: None,
+                    max_bytes: None,
+                    normalize: None,
+                    grammar: None,
+                },
+            ),
+            (
+                "vehicles".to_owned(),
+                AS3Validator::Object {
+                    properties: HashMap::from([(
+                        "list".to_owned(),
+                        AS3Validator::List {
+                            items: Arc::new(AS3Validator::Object {
+                                properties: HashMap::from([
+                                    (
+                                        "name".to_owned(),
+                                        AS3Validator::String {
+                                            regex: None,
+                                            min_length: None,
+                                            max_length: None,
+                                            format: None,
+                                            case: None,
+                                            no_surrounding_whitespace: false,
+                                            non_empty: false,
+                                            min_bytes: None,
+                                            max_bytes: None,
+                                            normalize: None,
+                                            grammar: None,
+                                        },
+                                    ),
+                                    (
+                                        "maker".to_owned(),
+                                        AS3Validator::String {
+                                            regex: Some("^[A-Z][a-z]".to_owned()),
+                                            min_length: None,
+                                            max_length: None,
+                                            format: None,
+                                            case: None,
+                                            no_surrounding_whitespace: false,
+                                            non_empty: false,
+                                            min_bytes: None,
+                                            max_bytes: None,
+                                            normalize: None,
+                                            grammar: None,
+                                        },
+                                    ),
+                                    (
+                                        "year".to_owned(),
+                                        AS3Validator::Integer {
+                                            minimum: None,
+                                            maximum: None,
+                                            step: None,
+                                            step_origin: None,
+                                        },
+                                    ),
+                                ]),
+                                property_names: None,
+                                when_present: None,
+                                dependent_required: HashMap::new(),
+                                additional_properties: true,
+                                additional_properties_schema: None,
+                                require_sorted_keys: false,
+                                non_empty: false,
+                                rules: vec![],
+                            }),
+                            max_validated: None,
+                            non_empty: false,
                         },
-                    ),
-                    ("year".to_owned(), AS3Validator::Integer { minimum: None }),
-                ])))),
-            )])),
-        ),
-    ]));
+                    )]),
+                    property_names: None,
+                    when_present: None,
+                    dependent_required: HashMap::new(),
+                    additional_properties: true,
+                    additional_properties_schema: None,
+                    require_sorted_keys: false,
+                    non_empty: false,
+                    rules: vec![],
+                },
+            ),
+        ]),
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    };
 
     assert_eq!(
         validator.validate(&AS3Data::from(&json)),
         Err(AS3ValidationError::TypeError {
-            expected: AS3Validator::Integer { minimum: None },
-            got: AS3Data::String("2018".to_string())
+            expected: "Integer",
+            got: "String",
+            preview: Some("\"2018\"".to_string())
         })
     );
 }
@@ -178,45 +441,133 @@ fn with_regex_error() {
       }
     });
 
-    let validator = AS3Validator::Object(HashMap::from([
-        (
-            "age".to_owned(),
-            AS3Validator::Integer { minimum: Some(20) },
-        ),
-        (
-            "children".to_owned(),
-            AS3Validator::Integer { minimum: Some(2) },
-        ),
-        (
-            "name".to_owned(),
-            AS3Validator::String {
-                // The name should start with an Uppercase letter
-                regex: Some("^[A-Z][a-z]".to_owned()),
-            },
-        ),
-        (
-            "vehicles".to_owned(),
-            AS3Validator::Object(HashMap::from([(
-                "list".to_owned(),
-                AS3Validator::List(Box::new(AS3Validator::Object(HashMap::from([
-                    ("name".to_owned(), AS3Validator::String { regex: None }),
-                    (
-                        "maker".to_owned(),
-                        AS3Validator::String {
-                            regex: Some("^[A-Z][a-z]".to_owned()),
+    let validator = AS3Validator::Object {
+        properties: HashMap::from([
+            (
+                "age".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(20),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "children".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(2),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "name".to_owned(),
+                AS3Validator::String {
+                    // The name should start with an Uppercase letter
+                    regex: Some("^[A-Z][a-z]".to_owned()),
+                    min_length: None,
+                    max_length: None,
+                    format: None,
+                    case: None,
+                    no_surrounding_whitespace: false,
+                    non_empty: false,
+                    min_bytes: None,
+                    max_bytes: None,
+                    normalize: None,
+                    grammar: None,
+                },
+            ),
+            (
+                "vehicles".to_owned(),
+                AS3Validator::Object {
+                    properties: HashMap::from([(
+                        "list".to_owned(),
+                        AS3Validator::List {
+                            items: Arc::new(AS3Validator::Object {
+                                properties: HashMap::from([
+                                    (
+                                        "name".to_owned(),
+                                        AS3Validator::String {
+                                            regex: None,
+                                            min_length: None,
+                                            max_length: None,
+                                            format: None,
+                                            case: None,
+                                            no_surrounding_whitespace: false,
+                                            non_empty: false,
+                                            min_bytes: None,
+                                            max_bytes: None,
+                                            normalize: None,
+                                            grammar: None,
+                                        },
+                                    ),
+                                    (
+                                        "maker".to_owned(),
+                                        AS3Validator::String {
+                                            regex: Some("^[A-Z][a-z]".to_owned()),
+                                            min_length: None,
+                                            max_length: None,
+                                            format: None,
+                                            case: None,
+                                            no_surrounding_whitespace: false,
+                                            non_empty: false,
+                                            min_bytes: None,
+                                            max_bytes: None,
+                                            normalize: None,
+                                            grammar: None,
+                                        },
+                                    ),
+                                    (
+                                        "year".to_owned(),
+                                        AS3Validator::Integer {
+                                            minimum: None,
+                                            maximum: None,
+                                            step: None,
+                                            step_origin: None,
+                                        },
+                                    ),
+                                ]),
+                                property_names: None,
+                                when_present: None,
+                                dependent_required: HashMap::new(),
+                                additional_properties: true,
+                                additional_properties_schema: None,
+                                require_sorted_keys: false,
+                                non_empty: false,
+                                rules: vec![],
+                            }),
+                            max_validated: None,
+                            non_empty: false,
                         },
-                    ),
-                    ("year".to_owned(), AS3Validator::Integer { minimum: None }),
-                ])))),
-            )])),
-        ),
-    ]));
+                    )]),
+                    property_names: None,
+                    when_present: None,
+                    dependent_required: HashMap::new(),
+                    additional_properties: true,
+                    additional_properties_schema: None,
+                    require_sorted_keys: false,
+                    non_empty: false,
+                    rules: vec![],
+                },
+            ),
+        ]),
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    };
 
     assert_eq!(
         validator.validate(&AS3Data::from(&json)),
         Err(AS3ValidationError::RegexError {
             word: "ford".to_string(),
-            regex: "^[A-Z][a-z]".to_string()
+            regex: "^[A-Z][a-z]".to_string(),
+            position: Some(0),
         })
     )
 }
@@ -228,16 +579,36 @@ fn with_minimum_error() {
       "children": 5,
     });
 
-    let validator = AS3Validator::Object(HashMap::from([
-        (
-            "age".to_owned(),
-            AS3Validator::Integer { minimum: Some(20) },
-        ),
-        (
-            "children".to_owned(),
-            AS3Validator::Integer { minimum: Some(2) },
-        ),
-    ]));
+    let validator = AS3Validator::Object {
+        properties: HashMap::from([
+            (
+                "age".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(20),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "children".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(2),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+        ]),
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    };
 
     assert_eq!(
         validator.validate(&AS3Data::from(&json)),
@@ -285,7 +656,7 @@ fn with_missing_field_error_validator_derive() {
     let data = AS3Data::from(&json);
 
     let yaml: serde_yaml::Value = serde_yaml::from_str(
-        &r#"
+        r#"
         Root:
             +Type: Object
             vehicles:
@@ -321,3 +692,5452 @@ fn with_missing_field_error_validator_derive() {
 
     assert_eq!(validator.validate(&AS3Data::from(&json)), Ok(()))
 }
+
+#[test]
+fn parse_returns_validated_data_only_on_success() {
+    let validator = AS3Validator::Object {
+        properties: HashMap::from([
+            (
+                "age".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(20),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "name".to_owned(),
+                AS3Validator::String {
+                    regex: Some("^[A-Z][a-z]".to_owned()),
+                    min_length: None,
+                    max_length: None,
+                    format: None,
+                    case: None,
+                    no_surrounding_whitespace: false,
+                    non_empty: false,
+                    min_bytes: None,
+                    max_bytes: None,
+                    normalize: None,
+                    grammar: None,
+                },
+            ),
+        ]),
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    };
+
+    let data = AS3Data::from(&json!({ "age": 25, "name": "Dilec" }));
+    let validated = validator.parse(data.clone()).unwrap();
+    assert_eq!(validated.as_data(), &data);
+    assert_eq!(validated.into_inner(), data);
+
+    let data = AS3Data::from(&json!({ "age": 18, "name": "dilec" }));
+    let errors: Vec<AS3ValidationError> = validator
+        .parse(data)
+        .unwrap_err()
+        .into_iter()
+        .map(AS3ValidationError::without_path)
+        .collect();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.contains(&AS3ValidationError::Minimum {
+        number: 18.0,
+        minimum: 20.0
+    }));
+    assert!(errors.contains(&AS3ValidationError::RegexError {
+        word: "dilec".to_string(),
+        regex: "^[A-Z][a-z]".to_string(),
+        position: Some(0),
+    }));
+}
+
+#[test]
+fn with_property_names() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            metadata:
+                +Type: Object
+                +PropertyNames:
+                    +Type: String
+                    +Regex: ^[a-z]+(_[a-z]+)*$
+                    "#,
+    )
+    .unwrap();
+
+    let validator = AS3Validator::from(&yaml).unwrap();
+
+    let json = json!({
+      "metadata": { "created_by": "Dilec", "owner": "Ford" }
+    });
+    assert_eq!(validator.validate(&AS3Data::from(&json)), Ok(()));
+
+    let json = json!({
+      "metadata": { "created_by": "Dilec", "Owner": "Ford" }
+    });
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json)),
+        Err(AS3ValidationError::PropertyName {
+            key: "Owner".to_string(),
+            error: Box::new(AS3ValidationError::RegexError {
+                word: "Owner".to_string(),
+                regex: "^[a-z]+(_[a-z]+)*$".to_string(),
+                position: Some(0),
+            })
+        })
+    );
+}
+
+#[test]
+fn any_accepts_everything() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            metadata:
+                +Type: Any
+                    "#,
+    )
+    .unwrap();
+
+    let validator = AS3Validator::from(&yaml).unwrap();
+
+    for metadata in [
+        json!({ "owner": "Dilec", "tags": ["a", "b"] }),
+        json!("opaque"),
+        json!([1, "two", 3.0]),
+        json!(null),
+    ] {
+        let json = json!({ "metadata": metadata });
+        assert_eq!(validator.validate(&AS3Data::from(&json)), Ok(()));
+    }
+
+    assert_eq!(AS3Validator::Any.validate(&AS3Data::Null), Ok(()));
+}
+
+#[test]
+fn data_try_from_json_str() {
+    let data = AS3Data::try_from(r#"{ "name": "Dilec", "age": 25, "tags": [null] }"#).unwrap();
+    assert_eq!(
+        data,
+        AS3Data::from(&json!({ "name": "Dilec", "age": 25, "tags": [null] }))
+    );
+
+    let error = AS3Data::try_from(r#"{ "name": "Dilec", "#).unwrap_err();
+    assert!(error.is_eof());
+    assert!(AS3Data::try_from("not json").unwrap_err().is_syntax());
+}
+
+#[test]
+fn with_flattened_fields() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+            +Flatten:
+                +Type: Object
+                created_by:
+                    +Type: String
+                    +Regex: ^[A-Z][a-z]
+                revision:
+                    +Type: Integer
+                    "#,
+    )
+    .unwrap();
+
+    let validator = AS3Validator::from(&yaml).unwrap();
+
+    let json = json!({ "name": "model3", "created_by": "Dilec", "revision": 3 });
+    assert_eq!(validator.validate(&AS3Data::from(&json)), Ok(()));
+
+    let json = json!({ "name": "model3", "created_by": "dilec", "revision": 3 });
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json)),
+        Err(AS3ValidationError::RegexError {
+            word: "dilec".to_string(),
+            regex: "^[A-Z][a-z]".to_string(),
+            position: Some(0),
+        })
+    );
+
+    let json = json!({ "name": "model3", "created_by": "Dilec" });
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json)),
+        Err(AS3ValidationError::MissingKey {
+            key: "revision".to_string()
+        })
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_matches_sequential_on_large_list() {
+    let validator = AS3Validator::List {
+        items: Arc::new(AS3Validator::Integer {
+            minimum: Some(10),
+            maximum: None,
+            step: None,
+            step_origin: None,
+        }),
+        max_validated: None,
+        non_empty: false,
+    };
+
+    let data = AS3Data::List((0..100_000).map(AS3Data::Integer).collect());
+    let sequential = validator.validate_all(&data);
+    assert_eq!(sequential.as_ref().unwrap_err().len(), 10);
+    assert_eq!(validator.validate_parallel(&data), sequential);
+
+    let data = AS3Data::List((10..100_000).map(AS3Data::Integer).collect());
+    assert_eq!(validator.validate_parallel(&data), Ok(()));
+}
+
+#[test]
+fn with_map_validator() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Map
+            +KeyType:
+                +Type: String
+            +ValueType:
+                +Type: Integer
+                    "#,
+    )
+    .unwrap();
+
+    let validator = AS3Validator::from(&yaml).unwrap();
+    assert_eq!(
+        validator,
+        AS3Validator::Map {
+            key_type: Arc::new(AS3Validator::String {
+                regex: None,
+                min_length: None,
+                max_length: None,
+                format: None,
+                case: None,
+                no_surrounding_whitespace: false,
+                non_empty: false,
+                min_bytes: None,
+                max_bytes: None,
+                normalize: None,
+                grammar: None,
+            }),
+            value_type: Arc::new(AS3Validator::Integer {
+                minimum: None,
+                maximum: None,
+                step: None,
+                step_origin: None,
+            }),
+        }
+    );
+
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!({ "a": 1, "b": 2 }))),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!({ "a": 1, "b": "two" }))),
+        Err(AS3ValidationError::TypeError {
+            expected: "Integer",
+            got: "String",
+            preview: Some("\"two\"".to_string())
+        })
+    );
+}
+
+#[test]
+fn soft_errors_become_warnings() {
+    let validator = AS3Validator::Object {
+        properties: HashMap::from([
+            (
+                "age".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(20),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "name".to_owned(),
+                AS3Validator::String {
+                    regex: None,
+                    min_length: None,
+                    max_length: None,
+                    format: None,
+                    case: None,
+                    no_surrounding_whitespace: false,
+                    non_empty: false,
+                    min_bytes: None,
+                    max_bytes: None,
+                    normalize: None,
+                    grammar: None,
+                },
+            ),
+        ]),
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    };
+    let data = AS3Data::from(&json!({ "age": 18, "name": "Dilec" }));
+    let minimum = AS3ValidationError::AtPath {
+        path: vec!["age".to_string()],
+        error: Box::new(AS3ValidationError::Minimum {
+            number: 18.0,
+            minimum: 20.0,
+        }),
+    };
+
+    let report = validator.validate_with_options(&data, &ValidationOptions::default());
+    assert!(!report.is_valid());
+    assert_eq!(report.errors, vec![minimum.clone()]);
+    assert!(report.warnings.is_empty());
+
+    let options = ValidationOptions::default().soft("minimum");
+    let report = validator.validate_with_options(&data, &options);
+    assert!(report.is_valid());
+    assert!(report.errors.is_empty());
+    assert_eq!(report.warnings, vec![minimum]);
+
+    let data = AS3Data::from(&json!({ "age": 18 }));
+    let report = validator.validate_with_options(&data, &options);
+    assert_eq!(
+        report.errors,
+        vec![AS3ValidationError::MissingKey {
+            key: "name".to_string()
+        }]
+    );
+}
+
+#[test]
+fn tolerates_utf8_bom() {
+    let schema = "\u{feff}Root:\n  +Type: Object\n  name:\n    +Type: String\n";
+    let validator = AS3Validator::from_yaml_str(schema).unwrap();
+
+    assert_eq!(
+        validator.validate_json_str("\u{feff}{ \"name\": \"Dilec\" }"),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str("\u{feff}{ \"name\": 3 }"),
+        Err(AS3ValidationError::TypeError {
+            expected: "String",
+            got: "Integer",
+            preview: Some("3".to_string())
+        })
+    );
+    assert!(matches!(
+        validator.validate_json_str("{ \"name\": "),
+        Err(AS3ValidationError::ParseError { .. })
+    ));
+}
+
+#[test]
+#[allow(clippy::approx_constant)]
+fn with_max_decimal_places() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            price:
+                +Type: Decimal
+                maxDecimalPlaces: 2
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(validator.validate_json_str(r#"{ "price": 3.14 }"#), Ok(()));
+    assert_eq!(validator.validate_json_str(r#"{ "price": 3.1 }"#), Ok(()));
+    assert_eq!(
+        validator.validate_json_str(r#"{ "price": 3.141 }"#),
+        Err(AS3ValidationError::TooManyDecimals {
+            value: 3.141,
+            max: 2
+        })
+    );
+}
+
+#[test]
+fn errors_carry_json_pointer() {
+    let validator = AS3Validator::Object {
+        properties: HashMap::from([
+            (
+                "vehicles".to_owned(),
+                AS3Validator::Object {
+                    properties: HashMap::from([(
+                        "list".to_owned(),
+                        AS3Validator::List {
+                            items: Arc::new(AS3Validator::Object {
+                                properties: HashMap::from([(
+                                    "maker".to_owned(),
+                                    AS3Validator::String {
+                                        regex: Some("^[A-Z][a-z]".to_owned()),
+                                        min_length: None,
+                                        max_length: None,
+                                        format: None,
+                                        case: None,
+                                        no_surrounding_whitespace: false,
+                                        non_empty: false,
+                                        min_bytes: None,
+                                        max_bytes: None,
+                                        normalize: None,
+                                        grammar: None,
+                                    },
+                                )]),
+                                property_names: None,
+                                when_present: None,
+                                dependent_required: HashMap::new(),
+                                additional_properties: true,
+                                additional_properties_schema: None,
+                                require_sorted_keys: false,
+                                non_empty: false,
+                                rules: vec![],
+                            }),
+                            max_validated: None,
+                            non_empty: false,
+                        },
+                    )]),
+                    property_names: None,
+                    when_present: None,
+                    dependent_required: HashMap::new(),
+                    additional_properties: true,
+                    additional_properties_schema: None,
+                    require_sorted_keys: false,
+                    non_empty: false,
+                    rules: vec![],
+                },
+            ),
+            (
+                "a/b~c".to_owned(),
+                AS3Validator::Integer {
+                    minimum: None,
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+        ]),
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    };
+
+    let json = json!({
+      "vehicles": {
+        "list": [
+          { "maker": "Tesla" },
+          { "maker": "ford" }
+        ]
+      },
+      "a/b~c": "one"
+    });
+
+    let mut pointers: Vec<String> = validator
+        .validate_all(&AS3Data::from(&json))
+        .unwrap_err()
+        .iter()
+        .map(|error| error.json_pointer().unwrap())
+        .collect();
+    pointers.sort();
+    assert_eq!(pointers, vec!["/a~1b~0c", "/vehicles/list/1/maker"]);
+
+    let root_error = AS3Validator::Integer {
+        minimum: None,
+        maximum: None,
+        step: None,
+        step_origin: None,
+    }
+    .validate_all(&AS3Data::Null)
+    .unwrap_err();
+    assert_eq!(root_error[0].json_pointer(), None);
+}
+
+#[test]
+fn deprecated_field_warns() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+            nickname:
+                +Type: String
+                +Deprecated: true
+                    "#,
+    )
+    .unwrap();
+
+    let data = AS3Data::from(&json!({ "name": "Dilec", "nickname": "Dile" }));
+    assert_eq!(validator.validate(&data), Ok(()));
+
+    let report = validator.validate_with_options(&data, &ValidationOptions::default());
+    assert!(report.is_valid());
+    assert_eq!(
+        report.warnings,
+        vec![AS3ValidationError::AtPath {
+            path: vec!["nickname".to_string()],
+            error: Box::new(AS3ValidationError::Deprecated)
+        }]
+    );
+}
+
+#[test]
+fn macro_matches_hand_built_validator() {
+    let hand_built = AS3Validator::Object {
+        properties: HashMap::from([
+            (
+                "age".to_owned(),
+                AS3Validator::Integer {
+                    minimum: Some(20),
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                },
+            ),
+            (
+                "name".to_owned(),
+                AS3Validator::String {
+                    regex: Some("^[A-Z][a-z]".to_owned()),
+                    min_length: None,
+                    max_length: None,
+                    format: None,
+                    case: None,
+                    no_surrounding_whitespace: false,
+                    non_empty: false,
+                    min_bytes: None,
+                    max_bytes: None,
+                    normalize: None,
+                    grammar: None,
+                },
+            ),
+            (
+                "price".to_owned(),
+                AS3Validator::Decimal {
+                    minimum: Some(0.5),
+                    max_decimal_places: Some(2),
+                    maximum: None,
+                },
+            ),
+            ("metadata".to_owned(), AS3Validator::Any),
+            (
+                "vehicles".to_owned(),
+                AS3Validator::Object {
+                    properties: HashMap::from([(
+                        "list".to_owned(),
+                        AS3Validator::List {
+                            items: Arc::new(AS3Validator::Object {
+                                properties: HashMap::from([
+                                    (
+                                        "name".to_owned(),
+                                        AS3Validator::String {
+                                            regex: None,
+                                            min_length: None,
+                                            max_length: None,
+                                            format: None,
+                                            case: None,
+                                            no_surrounding_whitespace: false,
+                                            non_empty: false,
+                                            min_bytes: None,
+                                            max_bytes: None,
+                                            normalize: None,
+                                            grammar: None,
+                                        },
+                                    ),
+                                    (
+                                        "maker".to_owned(),
+                                        AS3Validator::String {
+                                            regex: Some("^[A-Z][a-z]".to_owned()),
+                                            min_length: None,
+                                            max_length: None,
+                                            format: None,
+                                            case: None,
+                                            no_surrounding_whitespace: false,
+                                            non_empty: false,
+                                            min_bytes: None,
+                                            max_bytes: None,
+                                            normalize: None,
+                                            grammar: None,
+                                        },
+                                    ),
+                                    (
+                                        "year".to_owned(),
+                                        AS3Validator::Integer {
+                                            minimum: None,
+                                            maximum: None,
+                                            step: None,
+                                            step_origin: None,
+                                        },
+                                    ),
+                                ]),
+                                property_names: None,
+                                when_present: None,
+                                dependent_required: HashMap::new(),
+                                additional_properties: true,
+                                additional_properties_schema: None,
+                                require_sorted_keys: false,
+                                non_empty: false,
+                                rules: vec![],
+                            }),
+                            max_validated: None,
+                            non_empty: false,
+                        },
+                    )]),
+                    property_names: None,
+                    when_present: None,
+                    dependent_required: HashMap::new(),
+                    additional_properties: true,
+                    additional_properties_schema: None,
+                    require_sorted_keys: false,
+                    non_empty: false,
+                    rules: vec![],
+                },
+            ),
+        ]),
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    };
+
+    let from_macro = as3_validator!({
+        age: Integer(min = 20),
+        name: String(regex = "^[A-Z][a-z]"),
+        price: Decimal(min = 0.5, max_decimal_places = 2),
+        metadata: Any,
+        vehicles: {
+            list: [{
+                name: String,
+                maker: String(regex = "^[A-Z][a-z]"),
+                year: Integer,
+            }],
+        },
+    });
+    assert_eq!(from_macro, hand_built);
+
+    assert_eq!(
+        as3_validator!({ "first name": Decimal }),
+        AS3Validator::Object {
+            properties: HashMap::from([(
+                "first name".to_owned(),
+                AS3Validator::Decimal {
+                    minimum: None,
+                    max_decimal_places: None,
+                    maximum: None,
+                }
+            )]),
+            property_names: None,
+            when_present: None,
+            dependent_required: HashMap::new(),
+            additional_properties: true,
+            additional_properties_schema: None,
+            require_sorted_keys: false,
+            non_empty: false,
+            rules: vec![],
+        }
+    );
+    assert_eq!(
+        as3_validator!([[Integer]]),
+        AS3Validator::List {
+            items: Arc::new(AS3Validator::List {
+                items: Arc::new(AS3Validator::Integer {
+                    minimum: None,
+                    maximum: None,
+                    step: None,
+                    step_origin: None,
+                }),
+                max_validated: None,
+                non_empty: false,
+            }),
+            max_validated: None,
+            non_empty: false,
+        }
+    );
+}
+
+#[test]
+fn to_yaml_string_keeps_validator_usable() {
+    let validator = as3_validator!({
+        age: Integer(min = 20),
+        name: String(regex = "^[A-Z][a-z]"),
+    });
+
+    let yaml = validator.to_yaml_string().unwrap();
+    assert!(yaml.contains("+Object"));
+    assert!(yaml.contains("regex: ^[A-Z][a-z]"));
+
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!({ "age": 25, "name": "Dilec" }))),
+        Ok(())
+    );
+}
+
+/// Serves `body` with `ETag: "v1"` and answers `304` when the client already has it.
+#[cfg(feature = "ureq")]
+fn serve_schema(body: &'static str, requests: usize) -> String {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().take(requests) {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut cached = false;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line
+                    .to_ascii_lowercase()
+                    .starts_with("if-none-match: \"v1\"")
+                {
+                    cached = true;
+                }
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            let response = if cached {
+                "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nContent-Length: 0\r\n\r\n".to_string()
+            } else {
+                format!(
+                    "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            };
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    format!("http://{address}/schema.yml")
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn from_url_fetches_and_caches_schema() {
+    let url = serve_schema("Root:\n  +Type: Object\n  name:\n    +Type: String\n", 3);
+
+    let validator = AS3Validator::from_url(&url).unwrap();
+    assert_eq!(validator, as3_validator!({ name: String }));
+
+    let mut cache = MemorySchemaCache::default();
+    let fetched = AS3Validator::from_url_cached(&url, &mut cache).unwrap();
+    assert_eq!(cache.get(&url).map(|(etag, _)| etag), Some("\"v1\""));
+    let revalidated = AS3Validator::from_url_cached(&url, &mut cache).unwrap();
+    assert_eq!(fetched, revalidated);
+
+    let url = serve_schema("Root: [not, a, schema]", 1);
+    assert!(matches!(
+        AS3Validator::from_url(&url),
+        Err(SchemaFetchError::Parse(_))
+    ));
+
+    assert!(matches!(
+        AS3Validator::from_url("http://127.0.0.1:1/schema.yml"),
+        Err(SchemaFetchError::Network(_))
+    ));
+}
+
+#[test]
+fn object_checked_only_when_discriminator_present() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            payment:
+                +Type: Object
+                +WhenPresent: card_number
+                card_number:
+                    +Type: String
+                    +Regex: ^[0-9]{16}$
+                holder:
+                    +Type: String
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validator.validate_json_str(r#"{ "payment": { "iban": "IT60X0542811101000000123456" } }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(
+            r#"{ "payment": { "card_number": "4111111111111111", "holder": "Dilec" } }"#
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "payment": { "card_number": "4111111111111111" } }"#),
+        Err(AS3ValidationError::MissingKey {
+            key: "holder".to_string()
+        })
+    );
+}
+
+#[test]
+fn type_error_is_concise() {
+    let validator = as3_validator!({ year: Integer });
+    let error = validator
+        .validate_json_str(r#"{ "year": "2018" }"#)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Mismatched types. Expected `Integer` got `String` (\"2018\") . "
+    );
+
+    let error = validator
+        .validate_json_str(r#"{ "year": { "value": 2018, "era": "AD" } }"#)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Expected an integer but found an object with 2 keys . "
+    );
+
+    let long = "x".repeat(100);
+    let error = validator
+        .validate(&AS3Data::from(&json!({ "year": long })))
+        .unwrap_err();
+    assert_eq!(
+        error,
+        AS3ValidationError::TypeError {
+            expected: "Integer",
+            got: "String",
+            preview: Some(format!("\"{}…", "x".repeat(31)))
+        }
+    );
+}
+
+#[test]
+fn with_tuple_and_rest() {
+    let exact = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Tuple
+            +Items:
+                - +Type: String
+                - +Type: Integer
+                    "#,
+    )
+    .unwrap();
+    assert_eq!(exact.validate_json_str(r#"["Dilec", 25]"#), Ok(()));
+    assert_eq!(
+        exact.validate_json_str(r#"["Dilec", 25, 26]"#),
+        Err(AS3ValidationError::TupleLength {
+            expected: 2,
+            got: 3
+        })
+    );
+
+    let with_rest = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Tuple
+            +Items:
+                - +Type: String
+                - +Type: Integer
+            +Rest:
+                +Type: Decimal
+                    "#,
+    )
+    .unwrap();
+    assert_eq!(with_rest.validate_json_str(r#"["Dilec", 25]"#), Ok(()));
+    assert_eq!(
+        with_rest.validate_json_str(r#"["Dilec", 25, 1.5, 2.5]"#),
+        Ok(())
+    );
+    assert_eq!(
+        with_rest.validate_all(&AS3Data::try_from(r#"["Dilec", 25, 1.5, "2.5"]"#).unwrap()),
+        Err(vec![AS3ValidationError::AtPath {
+            path: vec!["3".to_string()],
+            error: Box::new(AS3ValidationError::TypeError {
+                expected: "Decimal",
+                got: "String",
+                preview: Some("\"2.5\"".to_string())
+            })
+        }])
+    );
+    assert_eq!(
+        with_rest.validate_json_str(r#"["Dilec"]"#),
+        Err(AS3ValidationError::TupleLength {
+            expected: 2,
+            got: 1
+        })
+    );
+}
+
+#[test]
+fn serde_round_trips_every_variant() {
+    #[allow(unused_mut)]
+    let mut validators = vec![
+        as3_validator!({
+            age: Integer(min = 20),
+            name: String(regex = "^[A-Z][a-z]"),
+            price: Decimal(min = 0.5, max_decimal_places = 2),
+            metadata: Any,
+            vehicles: [{ maker: String, year: Integer }],
+        }),
+        AS3Validator::Object {
+            properties: HashMap::from([(
+                "+Flatten".to_owned(),
+                AS3Validator::Flatten(Arc::new(as3_validator!({ revision: Integer }))),
+            )]),
+            property_names: Some(Arc::new(as3_validator!(String(regex = "^[a-z]+$")))),
+            when_present: Some("revision".to_owned()),
+            dependent_required: HashMap::new(),
+            additional_properties: true,
+            additional_properties_schema: None,
+            require_sorted_keys: false,
+            non_empty: false,
+            rules: vec![],
+        },
+        AS3Validator::Map {
+            key_type: Arc::new(as3_validator!(String)),
+            value_type: Arc::new(as3_validator!(Integer)),
+        },
+        AS3Validator::Tuple {
+            items: vec![as3_validator!(String), as3_validator!(Integer)],
+            rest: Some(Arc::new(as3_validator!(Decimal))),
+        },
+        AS3Validator::Annotated {
+            validator: Arc::new(as3_validator!(String)),
+            annotations: Annotations {
+                deprecated: true,
+                ..Annotations::default()
+            },
+        },
+        AS3Validator::Positional {
+            fields: vec![
+                ("id".to_owned(), as3_validator!(Integer)),
+                ("name".to_owned(), as3_validator!(String)),
+            ],
+        },
+        AS3Validator::NumericString {
+            format_mask: Some("000000".to_owned()),
+        },
+        AS3Validator::Const(AS3Data::String("draft".to_owned())),
+        AS3Validator::Enum(vec![
+            AS3Validator::Const(AS3Data::Integer(200)),
+            as3_validator!(Integer(min = 500, max = 599)),
+        ]),
+        AS3Validator::OneOf(vec![as3_validator!(String), as3_validator!(Integer)]),
+        AS3Validator::AnyOf(vec![as3_validator!(String), as3_validator!(Integer)]),
+        AS3Validator::AllOf(vec![
+            as3_validator!({ id: Integer }),
+            as3_validator!({ name: String }),
+        ]),
+        AS3Validator::Discriminated {
+            key: "kind".to_owned(),
+            mapping: HashMap::from([
+                ("car".to_owned(), as3_validator!({ wheels: Integer })),
+                ("boat".to_owned(), as3_validator!({ sails: Integer })),
+            ]),
+        },
+        AS3Validator::Ref("#".to_owned()),
+        AS3Validator::Ref("https://example.com/schema.yml".to_owned()),
+        AS3Validator::Any,
+    ];
+    #[cfg(feature = "chrono")]
+    validators.extend([
+        AS3Validator::Date {
+            after: Some("2020-01-01".to_owned()),
+            before: None,
+        },
+        AS3Validator::DateTime {
+            require_timezone: Some(true),
+            utc_only: true,
+        },
+    ]);
+    #[cfg(feature = "bigdecimal")]
+    validators.push(AS3Validator::BigNumber {
+        minimum: Some("-1e40".to_owned()),
+        maximum: Some("12345678901234567890.5".to_owned()),
+    });
+
+    for validator in validators {
+        let yaml = validator.to_yaml_string().unwrap();
+        let deserialized = AS3Validator::from_yaml_string(&yaml).unwrap();
+        assert_eq!(deserialized, validator, "round trip of\n{yaml}");
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn with_date_range() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            check_in:
+                +Type: Date
+                +After: "2020-01-01"
+                +Before: "2030-12-31"
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validator.validate_json_str(r#"{ "check_in": "2024-02-29" }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "check_in": "2030-12-31" }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "check_in": "2019-12-31" }"#),
+        Err(AS3ValidationError::DateRange {
+            date: "2019-12-31".to_string(),
+            after: Some("2020-01-01".to_string()),
+            before: Some("2030-12-31".to_string())
+        })
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "check_in": "2023-02-29" }"#),
+        Err(AS3ValidationError::FormatError {
+            value: "2023-02-29".to_string(),
+            format: "date"
+        })
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn with_datetime_timezones() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            created_at:
+                +Type: DateTime
+                +UtcOnly: true
+            scheduled_for:
+                +Type: DateTime
+                +RequireTimezone: true
+            local_time:
+                +Type: DateTime
+                +RequireTimezone: false
+                    "#,
+    )
+    .unwrap();
+    let valid = json!({
+        "created_at": "2024-05-01T12:30:00Z",
+        "scheduled_for": "2024-05-01T12:30:00.250+02:00",
+        "local_time": "2024-05-01T12:30:00",
+    });
+    assert_eq!(validator.validate(&AS3Data::from(&valid)), Ok(()));
+
+    let check = |key: &str, value: &str| {
+        let mut data = valid.clone();
+        data[key] = json!(value);
+        validator.validate(&AS3Data::from(&data))
+    };
+    assert_eq!(
+        check("created_at", "2024-05-01T12:30:00+02:00"),
+        Err(AS3ValidationError::NotUtc {
+            value: "2024-05-01T12:30:00+02:00".to_string()
+        })
+    );
+    assert_eq!(
+        check("created_at", "2024-05-01T12:30:00"),
+        Err(AS3ValidationError::MissingTimezone {
+            value: "2024-05-01T12:30:00".to_string()
+        })
+    );
+    assert_eq!(
+        check("scheduled_for", "2024-05-01T12:30:00"),
+        Err(AS3ValidationError::MissingTimezone {
+            value: "2024-05-01T12:30:00".to_string()
+        })
+    );
+    assert_eq!(
+        check("local_time", "2024-05-01T12:30:00Z"),
+        Err(AS3ValidationError::UnexpectedTimezone {
+            value: "2024-05-01T12:30:00Z".to_string()
+        })
+    );
+    assert_eq!(
+        check("created_at", "2024-05-01 noon"),
+        Err(AS3ValidationError::FormatError {
+            value: "2024-05-01 noon".to_string(),
+            format: "date-time"
+        })
+    );
+}
+
+#[test]
+fn numeric_strings_fill_their_format_mask() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            account:
+                +Type: NumericString
+                formatMask: "000000"
+            sort_code:
+                +Type: NumericString
+                formatMask: "00-00-00"
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validator.validate_json_str(r#"{ "account": "000042", "sort_code": "12-34-56" }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "account": "42", "sort_code": "12-34-56" }"#),
+        Err(AS3ValidationError::MaskMismatch {
+            value: "42".to_string(),
+            mask: "000000".to_string()
+        })
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "account": "00004a", "sort_code": "12-34-56" }"#),
+        Err(AS3ValidationError::MaskMismatch {
+            value: "00004a".to_string(),
+            mask: "000000".to_string()
+        })
+    );
+    assert_eq!(
+        validator.to_json_schema()["properties"]["sort_code"]["pattern"],
+        json!("^[0-9][0-9]\\-[0-9][0-9]\\-[0-9][0-9]$")
+    );
+
+    let digits = AS3Validator::NumericString { format_mask: None };
+    assert_eq!(
+        digits.validate(&AS3Data::String("0042".to_string())),
+        Ok(())
+    );
+    assert_eq!(
+        digits.validate(&AS3Data::String("-42".to_string())),
+        Err(AS3ValidationError::FormatError {
+            value: "-42".to_string(),
+            format: "digits"
+        })
+    );
+}
+
+#[test]
+fn patch_mode_skips_missing_keys() {
+    let validator = as3_validator!({
+        age: Integer(min = 20),
+        name: String(regex = "^[A-Z][a-z]"),
+        address: { city: String, zip: String },
+    });
+
+    let data = AS3Data::from(&json!({ "age": 30, "address": { "city": "Padova" } }));
+    assert_eq!(validator.validate_patch(&data), Ok(()));
+    assert!(matches!(
+        validator.validate(&data),
+        Err(AS3ValidationError::MissingKey { .. })
+    ));
+
+    let data = AS3Data::from(&json!({ "name": "dilec" }));
+    assert_eq!(
+        validator.validate_patch(&data),
+        Err(AS3ValidationError::RegexError {
+            word: "dilec".to_string(),
+            regex: "^[A-Z][a-z]".to_string(),
+            position: Some(0),
+        })
+    );
+}
+
+#[test]
+fn clones_share_nested_validators() {
+    let validator = as3_validator!([{
+        name: String,
+        maker: String(regex = "^[A-Z][a-z]"),
+        year: Integer,
+    }]);
+    let copy = validator.clone();
+
+    let (
+        AS3Validator::List {
+            items: original, ..
+        },
+        AS3Validator::List { items: copied, .. },
+    ) = (&validator, &copy)
+    else {
+        panic!("expected list validators");
+    };
+    assert!(Arc::ptr_eq(original, copied));
+    assert_eq!(Arc::strong_count(original), 2);
+
+    let shared = Arc::new(validator);
+    let handles: Vec<_> = (0..4)
+        .map(|year| {
+            let shared = Arc::clone(&shared);
+            std::thread::spawn(move || {
+                shared.validate(&AS3Data::from(&json!([
+                    { "name": "model3", "maker": "Tesla", "year": 2018 + year }
+                ])))
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Ok(()));
+    }
+}
+
+#[test]
+fn read_only_and_write_only_follow_direction() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            id:
+                +Type: Integer
+                +ReadOnly: true
+            password:
+                +Type: String
+                +WriteOnly: true
+            name:
+                +Type: String
+                    "#,
+    )
+    .unwrap();
+    let request = ValidationOptions {
+        direction: Some(Direction::Request),
+        ..ValidationOptions::default()
+    };
+    let response = ValidationOptions {
+        direction: Some(Direction::Response),
+        ..ValidationOptions::default()
+    };
+    let at = |key: &str, error| AS3ValidationError::AtPath {
+        path: vec![key.to_string()],
+        error: Box::new(error),
+    };
+
+    let data = AS3Data::from(&json!({ "name": "Dilec", "password": "hunter2" }));
+    assert!(validator.validate_with_options(&data, &request).is_valid());
+
+    let data = AS3Data::from(&json!({ "id": 7, "name": "Dilec" }));
+    assert!(validator.validate_with_options(&data, &response).is_valid());
+
+    let data = AS3Data::from(&json!({ "id": 7, "name": "Dilec", "password": "hunter2" }));
+    assert_eq!(
+        validator.validate_with_options(&data, &request).errors,
+        vec![at("id", AS3ValidationError::ReadOnlyViolation)]
+    );
+    assert_eq!(
+        validator.validate_with_options(&data, &response).errors,
+        vec![at("password", AS3ValidationError::WriteOnlyViolation)]
+    );
+}
+
+#[test]
+fn validate_raw_bytes() {
+    let validator = as3_validator!({ name: String(regex = "^[A-Z][a-z]"), year: Integer });
+
+    assert_eq!(
+        validator.validate_bytes(br#"{ "name": "Dilec", "year": 2018 }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_bytes(br#"{ "name": "dilec", "year": 2018 }"#),
+        Err(AS3ValidationError::RegexError {
+            word: "dilec".to_string(),
+            regex: "^[A-Z][a-z]".to_string(),
+            position: Some(0),
+        })
+    );
+    assert!(matches!(
+        validator.validate_bytes(b"{ \"name\": \"\xff\xfe\", \"year\": 2018 }"),
+        Err(AS3ValidationError::ParseError { .. })
+    ));
+}
+
+#[test]
+fn string_checks_combine() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            username:
+                +Type: String
+                +Regex: ^[a-zA-Z]+$
+                +MinLength: 3
+                +MaxLength: 8
+                +Case: lower
+            email:
+                +Type: String
+                +Format: email
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validator.validate_json_str(r#"{ "username": "dilec", "email": "dilec@appcove.com" }"#),
+        Ok(())
+    );
+
+    let data =
+        AS3Data::try_from(r#"{ "username": "DilecPadovani", "email": "dilec@appcove.com" }"#)
+            .unwrap();
+    assert_eq!(
+        validator.validate(&data),
+        Err(AS3ValidationError::StringLength {
+            length: 13,
+            min: Some(3),
+            max: Some(8)
+        })
+    );
+    let errors: Vec<AS3ValidationError> = validator
+        .validate_all(&data)
+        .unwrap_err()
+        .into_iter()
+        .map(AS3ValidationError::without_path)
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            AS3ValidationError::StringLength {
+                length: 13,
+                min: Some(3),
+                max: Some(8)
+            },
+            AS3ValidationError::CaseError {
+                word: "DilecPadovani".to_string(),
+                case: "lower"
+            }
+        ]
+    );
+
+    assert_eq!(
+        validator.validate_json_str(r#"{ "username": "dilec", "email": "dilec.appcove.com" }"#),
+        Err(AS3ValidationError::FormatError {
+            value: "dilec.appcove.com".to_string(),
+            format: "email"
+        })
+    );
+    assert_eq!(
+        as3_validator!(String(
+            format = StringFormat::Uuid,
+            case = StringCase::Lower
+        ))
+        .validate(&AS3Data::String(
+            "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string()
+        )),
+        Ok(())
+    );
+}
+
+#[test]
+fn problem_json_document() {
+    let validator = as3_validator!({
+        age: Integer(min = 20),
+        vehicles: [{ maker: String(regex = "^[A-Z][a-z]") }],
+    });
+    let data = AS3Data::from(&json!({
+      "age": 18,
+      "vehicles": [{ "maker": "Tesla" }, { "maker": "ford" }]
+    }));
+
+    let mut errors = validator.validate_all(&data).unwrap_err();
+    errors.sort_by_key(|error| error.json_pointer());
+
+    assert_eq!(
+        to_problem_json(&errors),
+        json!({
+            "type": "about:blank",
+            "title": "Validation failed",
+            "status": 422,
+            "errors": [
+                {
+                    "pointer": "/age",
+                    "code": "minimum",
+                    "detail": " `18` is under the minumum of `20` . "
+                },
+                {
+                    "pointer": "/vehicles/1/maker",
+                    "code": "regex",
+                    "detail": "Word ford is not following the `^[A-Z][a-z]` regex from character 0 "
+                }
+            ]
+        })
+    );
+}
+
+/// Creates an empty directory under the system temp dir for a test's schema files.
+fn schema_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("appcovecompiler-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn from_yaml_file_resolves_includes() {
+    let dir = schema_dir("includes");
+    std::fs::create_dir_all(dir.join("shared")).unwrap();
+    std::fs::write(
+        dir.join("shared/vehicle.yml"),
+        r#"
+Root:
+  +Type: Object
+  maker:
+    +Type: String
+    +Regex: ^[A-Z][a-z]
+  year:
+    +Type: Integer
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("main.yml"),
+        r#"
+Root:
+  +Type: Object
+  vehicle:
+    +Include: shared/vehicle.yml
+  maker:
+    $ref: "./shared/vehicle.yml#/Root/maker"
+"#,
+    )
+    .unwrap();
+
+    let validator = AS3Validator::from_yaml_file(dir.join("main.yml")).unwrap();
+    assert_eq!(
+        validator,
+        as3_validator!({
+            vehicle: { maker: String(regex = "^[A-Z][a-z]"), year: Integer },
+            maker: String(regex = "^[A-Z][a-z]"),
+        })
+    );
+}
+
+#[test]
+fn from_yaml_file_rejects_include_cycles() {
+    let dir = schema_dir("cycle");
+    std::fs::write(
+        dir.join("a.yml"),
+        "Root:\n  +Type: Object\n  b:\n    +Include: b.yml\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("b.yml"),
+        "Root:\n  +Type: Object\n  a:\n    +Include: a.yml\n",
+    )
+    .unwrap();
+
+    let error = AS3Validator::from_yaml_file(dir.join("a.yml")).unwrap_err();
+    let AS3SchemaError::Invalid(error) = error else {
+        panic!("expected an include cycle, got {error:?}");
+    };
+    assert!(error.starts_with("Include cycle: "), "{error}");
+    assert!(error.ends_with("a.yml"), "{error}");
+}
+
+#[test]
+fn container_mismatches_describe_the_found_shape() {
+    let validator = as3_validator!({ vehicles: [{ maker: String }], maker: String });
+    let data = AS3Data::try_from(
+        r#"{"vehicles": {"maker": "Fiat", "year": 2018, "plate": "AB123"}, "maker": ["Fiat"]}"#,
+    )
+    .unwrap();
+
+    let mut errors = validator.validate_all(&data).unwrap_err();
+    errors.sort_by_key(|error| error.path().to_vec());
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| error.clone().without_path().to_string())
+            .collect::<Vec<_>>(),
+        [
+            "Expected a string but found a list with 1 item . ",
+            "Expected a list but found an object with 3 keys . ",
+        ]
+    );
+    assert!(errors.iter().all(|error| error.code() == "type_error"));
+    assert_eq!(
+        as3_validator!([Integer]).validate(&AS3Data::String("2018".to_string())),
+        Err(AS3ValidationError::ShapeMismatch {
+            expected: "a list",
+            found: "the string \"2018\"".to_string()
+        })
+    );
+}
+
+#[test]
+fn walk_visits_every_node_with_its_path() {
+    let validator = as3_validator!({
+        name: String(regex = "^[A-Z][a-z]"),
+        vehicles: {
+            list: [{ name: String, maker: String(regex = "^[A-Z]"), year: Integer }],
+        },
+    });
+
+    let mut regexes = vec![];
+    validator.walk(|node, path| {
+        if let AS3Validator::String {
+            regex: Some(regex), ..
+        } = node
+        {
+            regexes.push((path.join("/"), regex.clone()));
+        }
+    });
+    regexes.sort();
+    assert_eq!(
+        regexes,
+        [
+            ("name".to_string(), "^[A-Z][a-z]".to_string()),
+            ("vehicles/list/*/maker".to_string(), "^[A-Z]".to_string()),
+        ]
+    );
+
+    let mut nodes = 0;
+    validator.walk(|_, _| nodes += 1);
+    assert_eq!(nodes, 8);
+}
+
+#[cfg(feature = "bigdecimal")]
+#[test]
+fn big_number_bounds_are_exact_around_two_to_the_53() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            id:
+                +Type: BigNumber
+                minimum: "9007199254740993"
+                    "#,
+    )
+    .unwrap();
+
+    // 2^53 + 1 rounds to 2^53 as an f64, so only an exact comparison rejects 2^53.
+    assert_eq!(
+        validator.validate_json_str(r#"{ "id": "9007199254740993" }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "id": "9007199254740992" }"#),
+        Err(AS3ValidationError::NumberRange {
+            value: "9007199254740992".to_string(),
+            minimum: Some("9007199254740993".to_string()),
+            maximum: None
+        })
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "id": 9007199254740992 }"#),
+        Err(AS3ValidationError::NumberRange {
+            value: "9007199254740992".to_string(),
+            minimum: Some("9007199254740993".to_string()),
+            maximum: None
+        })
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "id": "123456789012345678901234567890" }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "id": "12e" }"#),
+        Err(AS3ValidationError::FormatError {
+            value: "12e".to_string(),
+            format: "number"
+        })
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "id": " 12.5 " }"#),
+        Err(AS3ValidationError::FormatError {
+            value: " 12.5 ".to_string(),
+            format: "number"
+        })
+    );
+
+    let capped = AS3Validator::BigNumber {
+        minimum: None,
+        maximum: Some("9007199254740992.5".to_string()),
+    };
+    assert_eq!(
+        capped.validate(&AS3Data::String("9007199254740992.5".to_string())),
+        Ok(())
+    );
+    assert!(capped
+        .validate(&AS3Data::String("9007199254740992.50001".to_string()))
+        .is_err());
+}
+
+#[cfg(feature = "sample")]
+#[test]
+fn samples_pass_validation() {
+    use rand::SeedableRng;
+
+    let validator = as3_validator!({
+        age: Integer(min = 20),
+        price: Decimal(minimum = 0.5, max_decimal_places = 2),
+        name: String(regex = "^[A-Z][a-z]+$"),
+        code: String(min_length = 3, max_length = 5, case = StringCase::Upper),
+        contact: String(format = StringFormat::Email),
+        vehicles: [{ maker: String(regex = "^[A-Z][a-z]"), year: Integer }],
+    });
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    for _ in 0..50 {
+        let sample = validator.sample(&mut rng);
+        assert_eq!(validator.validate(&sample), Ok(()), "{sample:?}");
+    }
+}
+
+#[test]
+fn lint_flags_unsatisfiable_constraints() {
+    // Inverted bounds are rejected when loading YAML, so build this one in code.
+    let validator = as3_validator!({
+        age: Integer(min = 10, max = 5),
+        status: Any,
+        name: String(regex = "^[A-Z]"),
+    });
+    let AS3Validator::Object { mut properties, .. } = validator.clone() else {
+        unreachable!()
+    };
+    properties.insert("status".to_string(), AS3Validator::Enum(vec![]));
+    let validator = AS3Validator::Object {
+        properties,
+        property_names: None,
+        when_present: None,
+        dependent_required: HashMap::new(),
+        additional_properties: true,
+        additional_properties_schema: None,
+        require_sorted_keys: false,
+        non_empty: false,
+        rules: vec![],
+    };
+
+    let warnings = validator.lint();
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect::<Vec<_>>(),
+        [
+            "age: minimum 10 is greater than 5, so no value can match",
+            "status: enum has no values, so no value can match",
+        ]
+    );
+
+    let validator = as3_validator!({ age: Integer(min = 1, max = 130), name: String });
+    assert_eq!(validator.lint(), []);
+    assert_eq!(
+        validator.validate_json_str(r#"{ "age": 131, "name": "Ann" }"#),
+        Err(AS3ValidationError::Maximum {
+            number: 131.0,
+            maximum: 130.0
+        })
+    );
+}
+
+#[test]
+fn enum_accepts_only_listed_values() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            status:
+                +Type: Enum
+                +Values: [active, 3, null]
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validator.validate_json_str(r#"{ "status": "active" }"#),
+        Ok(())
+    );
+    assert_eq!(validator.validate_json_str(r#"{ "status": null }"#), Ok(()));
+    assert_eq!(
+        validator.validate_json_str(r#"{ "status": "archived" }"#),
+        Err(AS3ValidationError::NotInEnum {
+            preview: Some("\"archived\"".to_string())
+        })
+    );
+}
+
+#[test]
+fn one_of_requires_exactly_one_matching_branch() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            id:
+                +OneOf:
+                    - +Type: Integer
+                      minimum: 0
+                    - +Type: Integer
+                      maximum: 100
+                    - +Type: String
+                      +Regex: ^[a-z]+$
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(validator.validate_json_str(r#"{ "id": "abc" }"#), Ok(()));
+    assert_eq!(validator.validate_json_str(r#"{ "id": -5 }"#), Ok(()));
+    assert_eq!(
+        validator.validate_json_str(r#"{ "id": 50 }"#),
+        Err(AS3ValidationError::MultipleBranchesMatched { count: 2 })
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "id": "ABC" }"#),
+        Err(AS3ValidationError::NoBranchMatched {
+            closest: Some(2),
+            errors: vec![AS3ValidationError::RegexError {
+                word: "ABC".to_string(),
+                regex: "^[a-z]+$".to_string(),
+                position: Some(0),
+            }]
+        })
+    );
+}
+
+#[test]
+fn validate_with_stats_counts_the_visited_tree() {
+    let validator = as3_validator!({
+        age: Integer(min = 20),
+        children: Integer(min = 2),
+        name: String(regex = "^[A-Z][a-z]"),
+        vehicles: {
+            list: [{ name: String, maker: String(regex = "^[A-Z][a-z]"), year: Integer }],
+        },
+    });
+    let data = AS3Data::from(&json!({
+      "age": 25,
+      "children": 5,
+      "name": "Dilec",
+      "vehicles": {
+        "list": [
+          { "name": "model3", "maker": "Tesla", "year": 2018 },
+          { "name": "Raptor", "maker": "Ford", "year": 2018 }
+        ]
+      }
+    }));
+
+    let (result, stats) = validator.validate_with_stats(&data);
+    assert_eq!(result, Ok(()));
+    assert_eq!(
+        stats,
+        ValidationStats {
+            objects: 4,
+            lists: 1,
+            scalars: 9,
+            regex_evaluations: 3,
+        }
+    );
+}
+
+#[cfg(feature = "rmp-serde")]
+#[test]
+fn validates_msgpack_documents() {
+    let validator = as3_validator!({
+        name: String(regex = "^[A-Z][a-z]"),
+        vehicles: [{ maker: String, year: Integer }],
+    });
+
+    let document = json!({ "name": "Dilec", "vehicles": [{ "maker": "Tesla", "year": 2018 }] });
+    let bytes = rmp_serde::to_vec(&document).unwrap();
+    assert_eq!(
+        rmp_serde::from_slice::<serde_json::Value>(&bytes).unwrap(),
+        document
+    );
+    assert_eq!(validator.validate_msgpack(&bytes), Ok(()));
+
+    let bytes = rmp_serde::to_vec(&json!({ "name": "dilec", "vehicles": [] })).unwrap();
+    assert!(matches!(
+        validator.validate_msgpack(&bytes),
+        Err(AS3ValidationError::RegexError { .. })
+    ));
+
+    let mut bytes = vec![];
+    rmpv::encode::write_value(
+        &mut bytes,
+        &rmpv::Value::Map(vec![
+            ("name".into(), "Dilec".into()),
+            ("vehicles".into(), rmpv::Value::Array(vec![])),
+            ("avatar".into(), rmpv::Value::Binary(b"png".to_vec())),
+        ]),
+    )
+    .unwrap();
+    assert!(matches!(
+        validator.validate_msgpack(&bytes),
+        Err(AS3ValidationError::ParseError { .. })
+    ));
+    let with_avatar = as3_validator!({ avatar: String(regex = "^cG5n$") });
+    assert_eq!(
+        with_avatar.validate_msgpack_with(&bytes, BinaryPolicy::Base64),
+        Ok(())
+    );
+}
+
+#[test]
+fn required_fields_lists_every_leaf() {
+    let validator = as3_validator!({
+        age: Integer(min = 20),
+        children: Integer(min = 2),
+        name: String(regex = "^[A-Z][a-z]"),
+        vehicles: {
+            list: [{ name: String, maker: String(regex = "^[A-Z][a-z]"), year: Integer }],
+        },
+    });
+
+    assert_eq!(
+        validator.required_fields(),
+        [
+            ("age".to_string(), "Integer"),
+            ("children".to_string(), "Integer"),
+            ("name".to_string(), "String"),
+            ("vehicles.list[].maker".to_string(), "String"),
+            ("vehicles.list[].name".to_string(), "String"),
+            ("vehicles.list[].year".to_string(), "Integer"),
+        ]
+    );
+}
+
+#[test]
+fn scalar_and_list_roots_validate() {
+    let validator = AS3Validator::from_yaml_str("Root:\n  +Type: Integer\n  minimum: 0\n").unwrap();
+    assert_eq!(validator.validate(&AS3Data::from(&json!(42))), Ok(()));
+    assert_eq!(validator.validate_json_str("42"), Ok(()));
+    assert_eq!(
+        validator.validate_json_str("-1"),
+        Err(AS3ValidationError::Minimum {
+            number: -1.0,
+            minimum: 0.0
+        })
+    );
+    assert_eq!(
+        validator.validate_json_str(r#""42""#),
+        Err(AS3ValidationError::TypeError {
+            expected: "Integer",
+            got: "String",
+            preview: Some("\"42\"".to_string())
+        })
+    );
+
+    let validator = as3_validator!([Integer(min = 1)]);
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!([1, 2, 3]))),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_all(&AS3Data::from(&json!([1, 0, 3]))),
+        Err(vec![AS3ValidationError::AtPath {
+            path: vec!["1".to_string()],
+            error: Box::new(AS3ValidationError::Minimum {
+                number: 0.0,
+                minimum: 1.0
+            })
+        }])
+    );
+
+    let validator = as3_validator!({ name: String });
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!("Dilec"))),
+        Err(AS3ValidationError::ShapeMismatch {
+            expected: "an object",
+            found: "the string \"Dilec\"".to_string()
+        })
+    );
+}
+
+#[test]
+fn one_of_failure_highlights_the_closest_branch() {
+    let validator = AS3Validator::OneOf(vec![
+        as3_validator!({ kind: String(regex = "^car$"), wheels: Integer(min = 3) }),
+        as3_validator!({ kind: String(regex = "^boat$"), length: Decimal, crew: Integer }),
+        as3_validator!([String]),
+    ]);
+
+    let error = validator
+        .validate_json_str(r#"{ "kind": "car", "wheels": 2 }"#)
+        .unwrap_err();
+    assert_eq!(
+        error,
+        AS3ValidationError::NoBranchMatched {
+            closest: Some(0),
+            errors: vec![AS3ValidationError::AtPath {
+                path: vec!["wheels".to_string()],
+                error: Box::new(AS3ValidationError::Minimum {
+                    number: 2.0,
+                    minimum: 3.0
+                })
+            }]
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        concat!(
+            "Value does not match any of the alternatives; closest is #0: ",
+            " `2` is under the minumum of `3` .  at `wheels` . "
+        )
+    );
+}
+
+#[test]
+fn descriptions_survive_load_and_round_trip() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            +Description: A customer and their vehicles
+            name:
+                +Type: String
+                +Description: Full legal name
+            age:
+                +Type: Integer
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validator.description(),
+        Some("A customer and their vehicles")
+    );
+    let mut descriptions = vec![];
+    validator.walk(|node, path| {
+        if let Some(description) = node.description() {
+            descriptions.push((path.join("/"), description.to_string()));
+        }
+    });
+    descriptions.sort();
+    assert_eq!(
+        descriptions,
+        [
+            (String::new(), "A customer and their vehicles".to_string()),
+            ("name".to_string(), "Full legal name".to_string()),
+        ]
+    );
+
+    let yaml = validator.to_yaml_string().unwrap();
+    assert!(yaml.contains("description: Full legal name"), "{yaml}");
+    assert_eq!(AS3Validator::from_yaml_string(&yaml).unwrap(), validator);
+}
+
+#[test]
+fn whole_float_policy_pins_number_classification() {
+    let numbers: serde_json::Value = serde_json::from_str("[2, 2.0, 2.5]").unwrap();
+
+    assert_eq!(
+        AS3Data::from_value_with(&numbers, &ConversionOptions::default()),
+        AS3Data::List(vec![
+            AS3Data::Integer(2),
+            AS3Data::Decimal(2.0),
+            AS3Data::Decimal(2.5)
+        ])
+    );
+    assert_eq!(
+        AS3Data::from(&numbers),
+        AS3Data::from_value_with(&numbers, &ConversionOptions::default())
+    );
+
+    let options = ConversionOptions {
+        whole_floats_as_integers: true,
+    };
+    assert_eq!(
+        AS3Data::from_value_with(&numbers, &options),
+        AS3Data::List(vec![
+            AS3Data::Integer(2),
+            AS3Data::Integer(2),
+            AS3Data::Decimal(2.5)
+        ])
+    );
+}
+
+#[test]
+fn dependent_required_keys_follow_their_trigger() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            +DependentRequired:
+                credit_card: [billing_address]
+            name:
+                +Type: String
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validator.validate_json_str(r#"{ "name": "Dilec" }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(
+            r#"{ "name": "Dilec", "credit_card": "4111", "billing_address": "Rome" }"#
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "name": "Dilec", "credit_card": "4111" }"#),
+        Err(AS3ValidationError::DependencyMissing {
+            trigger: "credit_card".to_string(),
+            required: "billing_address".to_string()
+        })
+    );
+}
+
+#[test]
+fn validates_yaml_documents() {
+    let validator = as3_validator!({
+        name: String(regex = "^[A-Z][a-z]"),
+        nickname: Any,
+        home: { city: String, zip: String },
+        work: { city: String, zip: String },
+    });
+
+    let document = r#"
+name: Dilec
+nickname: ~
+home: &home
+  city: Rome
+  zip: "00100"
+work:
+  <<: *home
+  zip: "00184"
+"#;
+    assert_eq!(validator.validate_yaml_str(document), Ok(()));
+    assert_eq!(
+        AS3Data::try_from(&serde_yaml::from_str::<serde_yaml::Value>("nickname: null").unwrap())
+            .unwrap(),
+        AS3Data::Object(IndexMap::from([(
+            "nickname".to_string(),
+            Box::new(AS3Data::Null)
+        )]))
+    );
+
+    assert_eq!(
+        validator.validate_yaml_str(&document.replace("Dilec", "dilec")),
+        Err(AS3ValidationError::RegexError {
+            word: "dilec".to_string(),
+            regex: "^[A-Z][a-z]".to_string(),
+            position: Some(0),
+        })
+    );
+    assert!(matches!(
+        validator.validate_yaml_str("name: [unclosed"),
+        Err(AS3ValidationError::ParseError { .. })
+    ));
+}
+
+#[test]
+fn is_valid_matches_validate() {
+    let validator = as3_validator!({ age: Integer(min = 20), name: String });
+    for data in [
+        json!({ "age": 25, "name": "Dilec" }),
+        json!({ "age": 15, "name": "Dilec" }),
+        json!({ "age": 25 }),
+        json!([]),
+    ] {
+        let data = AS3Data::from(&data);
+        assert_eq!(validator.is_valid(&data), validator.validate(&data).is_ok());
+    }
+    assert!(validator.is_valid(&AS3Data::from(&json!({ "age": 20, "name": "" }))));
+    assert!(!validator.is_valid(&AS3Data::from(&json!({ "age": 19, "name": "" }))));
+}
+
+#[cfg(feature = "prost")]
+#[test]
+fn validates_prost_messages() {
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, prost::Message, Serialize)]
+    struct Vehicle {
+        #[prost(string, tag = "1")]
+        maker: String,
+        #[prost(int64, tag = "2")]
+        year: i64,
+    }
+
+    let validator =
+        as3_validator!({ maker: String(regex = "^[A-Z][a-z]"), year: Integer(min = 1900) });
+    let bytes = Vehicle {
+        maker: "Tesla".to_string(),
+        year: 2018,
+    }
+    .encode_to_vec();
+    let decoded = Vehicle::decode(bytes.as_slice()).unwrap();
+    assert_eq!(validator.validate_message(&decoded), Ok(()));
+
+    let vehicle = Vehicle {
+        maker: "Tesla".to_string(),
+        year: 1800,
+    };
+    assert_eq!(
+        validator.validate_message(&vehicle),
+        Err(AS3ValidationError::Minimum {
+            number: 1800.0,
+            minimum: 1900.0
+        })
+    );
+}
+
+#[test]
+fn messages_render_in_english_and_italian() {
+    let error = AS3ValidationError::Minimum {
+        number: 15.0,
+        minimum: 20.0,
+    };
+    assert_eq!(error.message("en"), error.to_string());
+    assert_eq!(error.message("en"), " `15` is under the minumum of `20` . ");
+    assert_eq!(error.message("it"), "`15` è sotto il minimo di `20`.");
+
+    let located = AS3ValidationError::AtPath {
+        path: vec!["vehicles".to_string(), "0".to_string(), "year".to_string()],
+        error: Box::new(error),
+    };
+    assert_eq!(located.message("en"), located.to_string());
+    assert_eq!(
+        located.message("it"),
+        "`15` è sotto il minimo di `20`. in `vehicles.0.year`"
+    );
+
+    // Languages and errors without a translation fall back to English.
+    assert_eq!(located.message("fr"), located.to_string());
+    let untranslated = AS3ValidationError::TupleLength {
+        expected: 2,
+        got: 3,
+    };
+    assert_eq!(untranslated.message("it"), untranslated.to_string());
+}
+
+#[test]
+fn schema_mistakes_fail_at_load_not_at_validate() {
+    let error = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+                +Regex: ^[A-Z
+                    "#,
+    )
+    .unwrap_err();
+    assert!(
+        matches!(
+            &error,
+            AS3SchemaError::InvalidRegex { path, regex, .. } if path == &["name"] && regex == "^[A-Z"
+        ),
+        "{error:?}"
+    );
+    assert_eq!(
+        AS3Validator::from_yaml_str(
+            r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: Strnig
+                    "#,
+        ),
+        Err(AS3SchemaError::UnknownType {
+            name: "Strnig".to_string()
+        })
+    );
+    assert_eq!(
+        AS3Validator::from_yaml_str(
+            r#"
+        Root:
+            +Type: Object
+            age:
+                +Type: Integer
+                minimum: 10
+                maximum: 5
+                    "#,
+        ),
+        Err(AS3SchemaError::Contradiction {
+            path: vec!["age".to_string()],
+            message: "minimum 10 is greater than 5, so no value can match".to_string()
+        })
+    );
+
+    // Data violating a valid schema is still a validation error.
+    let validator =
+        AS3Validator::from_yaml_str("Root:\n  +Type: String\n  +Regex: ^[A-Z]\n").unwrap();
+    assert!(matches!(
+        validator.validate_json_str(r#""dilec""#),
+        Err(AS3ValidationError::RegexError { .. })
+    ));
+}
+
+#[test]
+fn validates_ndjson_line_by_line() {
+    let validator = as3_validator!({ level: String, code: Integer(min = 100) });
+    let ndjson = concat!(
+        r#"{ "level": "info", "code": 200 }"#,
+        "\n\n",
+        r#"{ "level": "warn", "code": 42 }"#,
+        "\n",
+        r#"{ "level": "error", "#,
+        "\n",
+        r#"{ "level": "error", "code": 500 }"#,
+        "\n",
+    );
+
+    let results: Vec<_> = validator.validate_ndjson(ndjson.as_bytes()).collect();
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0], (1, Ok(())));
+    assert_eq!(
+        results[1],
+        (
+            3,
+            Err(AS3ValidationError::Minimum {
+                number: 42.0,
+                minimum: 100.0
+            })
+        )
+    );
+    assert!(matches!(
+        results[2],
+        (4, Err(AS3ValidationError::ParseError { .. }))
+    ));
+    assert_eq!(results[3], (5, Ok(())));
+}
+
+#[test]
+fn get_extracts_nested_validators() {
+    let validator = as3_validator!({
+        name: String(regex = "^[A-Z][a-z]"),
+        vehicles: {
+            list: [{ name: String, maker: String(regex = "^[A-Z][a-z]"), year: Integer }],
+        },
+    });
+
+    assert_eq!(
+        validator.get("vehicles.list[].maker"),
+        Some(&as3_validator!(String(regex = "^[A-Z][a-z]")))
+    );
+    assert_eq!(
+        validator.get("vehicles.list"),
+        Some(
+            &as3_validator!([{ name: String, maker: String(regex = "^[A-Z][a-z]"), year: Integer }])
+        )
+    );
+    assert_eq!(validator.get(""), Some(&validator));
+    assert_eq!(validator.get("vehicles.list.maker"), None);
+    assert_eq!(validator.get("vehicles.cars"), None);
+    assert_eq!(validator.get("name[]"), None);
+}
+
+#[test]
+fn decimal_errors_render_without_float_noise() {
+    let validator = as3_validator!(Decimal(minimum = 0.1 + 0.2));
+    let error = validator.validate(&AS3Data::Decimal(0.1)).unwrap_err();
+    assert_eq!(
+        error,
+        AS3ValidationError::Minimum {
+            number: 0.1,
+            minimum: 0.30000000000000004
+        }
+    );
+    assert_eq!(error.to_string(), " `0.1` is under the minumum of `0.3` . ");
+    assert_eq!(error.message("it"), "`0.1` è sotto il minimo di `0.3`.");
+
+    let error = AS3ValidationError::Maximum {
+        number: 1e21,
+        maximum: 123456.789,
+    };
+    assert_eq!(
+        error.to_string(),
+        " `1000000000000000000000` is over the maximum of `123456.789` . "
+    );
+}
+
+#[test]
+fn discriminated_dispatches_on_the_tag() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Discriminator: type
+            +Mapping:
+                car:
+                    +Type: Object
+                    wheels:
+                        +Type: Integer
+                        minimum: 3
+                boat:
+                    +Type: Object
+                    length:
+                        +Type: Decimal
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validator.validate_json_str(r#"{ "type": "car", "wheels": 4 }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "type": "boat", "length": 7.5 }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "type": "car", "wheels": 2 }"#),
+        Err(AS3ValidationError::Minimum {
+            number: 2.0,
+            minimum: 3.0
+        })
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "type": "boat", "wheels": 4 }"#),
+        Err(AS3ValidationError::MissingKey {
+            key: "length".to_string()
+        })
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "type": "plane", "wings": 2 }"#),
+        Err(AS3ValidationError::UnknownDiscriminator {
+            key: "type".to_string(),
+            preview: Some("\"plane\"".to_string())
+        })
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "wheels": 4 }"#),
+        Err(AS3ValidationError::MissingKey {
+            key: "type".to_string()
+        })
+    );
+}
+
+#[test]
+fn capped_list_stops_validating_past_the_limit() {
+    let validator = AS3Validator::List {
+        items: Arc::new(AS3Validator::Integer {
+            minimum: Some(0),
+            maximum: None,
+            step: None,
+            step_origin: None,
+        }),
+        max_validated: Some(3),
+        non_empty: false,
+    };
+
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!([1, 2, 3]))),
+        Ok(())
+    );
+
+    let data = AS3Data::from(&json!([1, 2, 3, -4, -5]));
+    let (result, stats) = validator.validate_with_stats(&data);
+    assert_eq!(
+        result,
+        Err(AS3ValidationError::ListTooLong { length: 5, max: 3 })
+    );
+    assert_eq!(stats.scalars, 3);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn duration_and_time_formats() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            timeout:
+                +Type: String
+                +Format: duration
+            opens:
+                +Type: String
+                +Format: time
+                    "#,
+    )
+    .unwrap();
+
+    for (timeout, opens) in [
+        ("P1Y2M", "13:45:00"),
+        ("PT1H30M", "00:00:00"),
+        ("P3W", "23:59:59.250"),
+        ("P1DT0.5S", "08:05:00"),
+    ] {
+        let data = json!({ "timeout": timeout, "opens": opens });
+        assert_eq!(validator.validate(&AS3Data::from(&data)), Ok(()), "{data}");
+    }
+
+    for timeout in ["P", "PT", "1Y", "P1H", "PT1D", "P1.5Y"] {
+        let data = json!({ "timeout": timeout, "opens": "13:45:00" });
+        assert_eq!(
+            validator
+                .validate(&AS3Data::from(&data))
+                .map_err(|error| error.without_path()),
+            Err(AS3ValidationError::FormatError {
+                value: timeout.to_string(),
+                format: "duration"
+            })
+        );
+    }
+    for opens in ["24:00:00", "13:45", "1:2:3pm", "13:60:00"] {
+        let data = json!({ "timeout": "P1D", "opens": opens });
+        assert!(
+            matches!(
+                validator
+                    .validate(&AS3Data::from(&data))
+                    .map_err(|error| error.without_path()),
+                Err(AS3ValidationError::FormatError { format: "time", .. })
+            ),
+            "{opens}"
+        );
+    }
+}
+
+#[test]
+fn validator_converts_to_yaml_and_json_values() {
+    let validator = as3_validator!({
+        name: String,
+        year: Integer(minimum = 1900),
+    });
+
+    let yaml = serde_yaml::Value::from(&validator);
+    let properties = &yaml["+Object"]["properties"];
+    assert_eq!(
+        properties["name"]["+String"]["regex"],
+        serde_yaml::Value::Null
+    );
+    assert_eq!(
+        properties["year"]["+Integer"]["minimum"],
+        serde_yaml::Value::from(1900)
+    );
+    assert_eq!(
+        serde_yaml::to_string(&yaml).unwrap(),
+        validator.to_yaml_string().unwrap()
+    );
+
+    let json = serde_json::Value::from(&validator);
+    assert_eq!(
+        json["+Object"]["properties"]["year"],
+        json!({ "+Integer": { "minimum": 1900, "maximum": null } })
+    );
+    assert_eq!(AS3Validator::deserialize(&json).unwrap(), validator);
+}
+
+#[test]
+fn empty_optional_strings_can_count_as_missing() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+                +Regex: ^.+$
+            nickname:
+                +Type: String
+                +Regex: ^.+$
+                +Optional: true
+                    "#,
+    )
+    .unwrap();
+    let form = AS3Data::from(&json!({ "name": "", "nickname": "" }));
+
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!({ "name": "Ada" }))),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!({ "name": "Ada", "nickname": "" }))),
+        Err(AS3ValidationError::RegexError {
+            word: "".to_string(),
+            regex: "^.+$".to_string(),
+            position: Some(0),
+        })
+    );
+
+    let options = ValidationOptions {
+        treat_empty_as_missing: true,
+        ..ValidationOptions::default()
+    };
+    let report = validator.validate_with_options(&form, &options);
+    // Only optional fields are skipped; a required one still has to match.
+    assert_eq!(
+        report.errors,
+        vec![AS3ValidationError::AtPath {
+            path: vec!["name".to_string()],
+            error: Box::new(AS3ValidationError::RegexError {
+                word: "".to_string(),
+                regex: "^.+$".to_string(),
+                position: Some(0),
+            })
+        }]
+    );
+    assert_eq!(
+        validator.required_fields(),
+        vec![("name".to_string(), "String")]
+    );
+}
+
+#[test]
+fn backward_compatibility_between_schema_versions() {
+    let older = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+            year:
+                +Type: Integer
+                minimum: 1900
+                    "#,
+    )
+    .unwrap();
+    let with_optional_field = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+            year:
+                +Type: Integer
+                minimum: 1800
+            color:
+                +Type: String
+                +Optional: true
+                    "#,
+    )
+    .unwrap();
+    let breaking = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: Integer
+            year:
+                +Type: Integer
+                minimum: 2000
+            color:
+                +Type: String
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        with_optional_field.is_backward_compatible_with(&older),
+        Ok(())
+    );
+    let incompatibilities = breaking.is_backward_compatible_with(&older).unwrap_err();
+    assert_eq!(
+        incompatibilities
+            .iter()
+            .map(|incompatibility| incompatibility.to_string())
+            .collect::<Vec<_>>(),
+        vec![
+            "Root: new required field `color`",
+            "name: type changed from String to Integer",
+            "year: minimum tightened from 1900 to 2000",
+        ]
+    );
+    // Going back drops the optional field, which older data never relied on.
+    assert_eq!(
+        older
+            .is_backward_compatible_with(&with_optional_field)
+            .unwrap_err(),
+        vec![Incompatibility {
+            path: vec!["year".to_string()],
+            message: "minimum tightened from 1800 to 1900".to_string()
+        }]
+    );
+
+    let directional = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+                +WriteOnly: true
+            year:
+                +Type: Integer
+                minimum: 1900
+                +ReadOnly: true
+                    "#,
+    )
+    .unwrap();
+    assert_eq!(
+        directional
+            .is_backward_compatible_with(&older)
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec![
+            "Root: field `name` is now write-only, so responses may no longer include it",
+            "Root: field `year` is now read-only, so requests may no longer send it",
+        ]
+    );
+    assert_eq!(older.is_backward_compatible_with(&directional), Ok(()));
+}
+
+#[test]
+fn try_from_reports_every_schema_mistake() {
+    let schema: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+                +Regex: 42
+            vehicles:
+                +Type: Map
+                +KeyType:
+                    +Type: String
+                +ValueType:
+                    +Type: Strnig
+            age:
+                +Type: Integer
+                minimum: 10
+                maximum: 5
+                    "#,
+    )
+    .unwrap();
+
+    let errors = AS3Validator::try_from(&schema).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            AS3SchemaError::AtPath {
+                path: vec!["name".to_string()],
+                error: Box::new(AS3SchemaError::Invalid(
+                    "+Regex must be a string".to_string()
+                ))
+            },
+            AS3SchemaError::AtPath {
+                path: vec!["vehicles".to_string(), "*".to_string()],
+                error: Box::new(AS3SchemaError::UnknownType {
+                    name: "Strnig".to_string()
+                })
+            },
+            AS3SchemaError::Contradiction {
+                path: vec!["age".to_string()],
+                message: "minimum 10 is greater than 5, so no value can match".to_string()
+            },
+        ]
+    );
+    assert_eq!(
+        errors[1].to_string(),
+        "Unsupported type `Strnig` at `vehicles.*`"
+    );
+    assert_eq!(
+        AS3Validator::from(&schema),
+        Err(AS3SchemaError::Invalid(
+            "+Regex must be a string".to_string()
+        ))
+    );
+}
+
+#[test]
+fn strings_without_surrounding_whitespace() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: String
+            +NoSurroundingWhitespace: true
+            +NonEmpty: true
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(validator.validate(&AS3Data::from(&json!("hello"))), Ok(()));
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!(" hello "))),
+        Err(AS3ValidationError::SurroundingWhitespace {
+            word: " hello ".to_string()
+        })
+    );
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!(""))),
+        Err(AS3ValidationError::EmptyString)
+    );
+    assert_eq!(
+        as3_validator!(String(no_surrounding_whitespace = true))
+            .validate(&AS3Data::from(&json!(""))),
+        Ok(())
+    );
+}
+
+#[test]
+fn unknown_keys_are_reported_at_every_level() {
+    let validator = as3_validator!({
+        name: String,
+        vehicles: [{ maker: String, year: Integer }],
+    });
+    let data = AS3Data::from(&json!({
+        "name": "Ada",
+        "nickname": "A",
+        "vehicles": [
+            { "maker": "Fiat", "year": 1990 },
+            { "maker": "Ford", "year": "soon", "color": "red", "wheels": 4 },
+        ],
+    }));
+
+    assert_eq!(
+        validator.unknown_keys(&data),
+        vec!["nickname", "vehicles.1.color", "vehicles.1.wheels"]
+    );
+    assert!(validator
+        .unknown_keys(&AS3Data::from(&json!({ "name": "Ada", "vehicles": [] })))
+        .is_empty());
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_schema_matches_hand_built_validator() {
+    #[derive(AS3Schema)]
+    #[allow(dead_code)]
+    struct Vehicle {
+        name: String,
+        #[as3(regex = "^[A-Z][a-z]")]
+        maker: String,
+        year: i64,
+    }
+
+    #[derive(AS3Schema)]
+    #[allow(dead_code)]
+    struct Garage {
+        #[as3(min = 20)]
+        age: i64,
+        vehicles: Vec<Vehicle>,
+        #[as3(rename = "e-mail", format = "email")]
+        email: Option<String>,
+    }
+
+    let mut expected = as3_validator!({
+        age: Integer(min = 20),
+        vehicles: [{
+            name: String,
+            maker: String(regex = "^[A-Z][a-z]"),
+            year: Integer,
+        }],
+    });
+    if let AS3Validator::Object { properties, .. } = &mut expected {
+        properties.insert(
+            "e-mail".to_string(),
+            AS3Validator::Annotated {
+                validator: Arc::new(as3_validator!(String(format = StringFormat::Email))),
+                annotations: Annotations {
+                    optional: true,
+                    ..Annotations::default()
+                },
+            },
+        );
+    }
+    assert_eq!(Garage::as3_validator(), expected);
+
+    let validator = Garage::as3_validator();
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!({
+            "age": 30,
+            "vehicles": [{ "name": "Panda", "maker": "Fiat", "year": 1990 }],
+        }))),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!({
+            "age": 10,
+            "vehicles": [],
+            "e-mail": "someone@example.com",
+        }))),
+        Err(AS3ValidationError::Minimum {
+            number: 10.0,
+            minimum: 20.0
+        })
+    );
+}
+
+#[test]
+fn deep_nesting_errors_instead_of_overflowing() {
+    let mut validator = as3_validator!(Integer);
+    let mut data = AS3Data::Integer(1);
+    for _ in 0..2_000 {
+        validator = AS3Validator::List {
+            items: Arc::new(validator),
+            max_validated: None,
+            non_empty: false,
+        };
+        data = AS3Data::List(vec![data]);
+    }
+
+    assert_eq!(
+        validator.validate(&data),
+        Err(AS3ValidationError::MaxDepthExceeded { depth: 256 })
+    );
+    let errors = validator.validate_all(&data).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path().len(), 257);
+
+    let options = ValidationOptions {
+        max_depth: 3,
+        ..ValidationOptions::default()
+    };
+    let shallow = as3_validator!([[Integer]]);
+    let report = shallow.validate_with_options(&AS3Data::from(&json!([[1, 2]])), &options);
+    assert!(report.is_valid());
+    let options = ValidationOptions {
+        max_depth: 1,
+        ..ValidationOptions::default()
+    };
+    let report = shallow.validate_with_options(&AS3Data::from(&json!([[1]])), &options);
+    assert_eq!(
+        report.errors,
+        vec![AS3ValidationError::AtPath {
+            path: vec!["0".to_string(), "0".to_string()],
+            error: Box::new(AS3ValidationError::MaxDepthExceeded { depth: 1 })
+        }]
+    );
+}
+
+#[test]
+fn data_round_trips_through_pretty_json() {
+    let document = json!({
+        "name": "Ada",
+        "nickname": null,
+        "vehicles": [
+            { "maker": "Fiat", "year": 1990, "price": 1500.5, "electric": false },
+            null,
+        ],
+    });
+    let data = AS3Data::from(&document);
+
+    let pretty = data.to_json_string_pretty();
+    assert!(pretty.contains("\n  \"nickname\": null"), "{pretty}");
+    assert_eq!(AS3Data::try_from(pretty.as_str()).unwrap(), data);
+    assert_eq!(serde_json::Value::from(&data), document);
+    assert_eq!(
+        AS3Data::Decimal(f64::NAN).to_json_string_pretty(),
+        "null".to_string()
+    );
+}
+
+#[test]
+fn validate_value_accepts_null_optional_fields() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+            nickname:
+                +Type: String
+                +Optional: true
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validator.validate_value(&json!({ "name": "Ada", "nickname": null })),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_value(&json!({ "name": "Ada", "nickname": "A" })),
+        Ok(())
+    );
+    assert!(matches!(
+        validator.validate_value(&json!({ "name": null, "nickname": null })),
+        Err(AS3ValidationError::TypeError {
+            expected: "String",
+            got: "Null",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn form_descriptor_lists_fields_and_constraints() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            age:
+                +Type: Integer
+                +Title: Your age
+                minimum: 20
+            vehicles:
+                +Type: Object
+                +Description: What you drive
+                list:
+                    +Type: Tuple
+                    +Items:
+                        - +Type: String
+                    +Rest:
+                        +Type: Object
+                        maker:
+                            +Type: String
+                            +Regex: ^[A-Z][a-z]
+                        nickname:
+                            +Type: String
+                            +Optional: true
+                    "#,
+    )
+    .unwrap();
+
+    let descriptor = validator.to_form_descriptor();
+    assert_eq!(descriptor["type"], "Object");
+    let fields = descriptor["fields"].as_array().unwrap();
+    assert_eq!(
+        fields[0],
+        json!({
+            "name": "age",
+            "path": "age",
+            "type": "Integer",
+            "required": true,
+            "title": "Your age",
+            "constraints": { "minimum": 20 },
+        })
+    );
+    let vehicles = &fields[1];
+    assert_eq!(vehicles["title"], "vehicles");
+    assert_eq!(vehicles["description"], "What you drive");
+    let rest = &vehicles["fields"][0]["rest"];
+    assert_eq!(
+        rest["fields"],
+        json!([
+            {
+                "name": "maker",
+                "path": "vehicles.list[].maker",
+                "type": "String",
+                "required": true,
+                "title": "maker",
+                "constraints": { "regex": "^[A-Z][a-z]" },
+            },
+            {
+                "name": "nickname",
+                "path": "vehicles.list[].nickname",
+                "type": "String",
+                "required": false,
+                "title": "nickname",
+            },
+        ])
+    );
+}
+
+#[test]
+fn enum_mixes_literals_and_ranges() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Enum
+            +Values:
+                - 200
+                - 404
+                - +Type: Integer
+                  minimum: 500
+                  maximum: 599
+                    "#,
+    )
+    .unwrap();
+    assert_eq!(
+        validator,
+        AS3Validator::Enum(vec![
+            AS3Validator::Const(AS3Data::Integer(200)),
+            AS3Validator::Const(AS3Data::Integer(404)),
+            as3_validator!(Integer(min = 500, max = 599)),
+        ])
+    );
+
+    assert_eq!(validator.validate(&AS3Data::Integer(404)), Ok(()));
+    assert_eq!(validator.validate(&AS3Data::Integer(503)), Ok(()));
+    assert_eq!(
+        validator.validate(&AS3Data::Integer(301)),
+        Err(AS3ValidationError::NotInEnum {
+            preview: Some("301".to_string())
+        })
+    );
+
+    let constant = AS3Validator::from_yaml_str("Root:\n  +Type: Const\n  +Value: v2\n").unwrap();
+    assert_eq!(
+        constant.validate(&AS3Data::String("v2".to_string())),
+        Ok(())
+    );
+    assert_eq!(
+        constant
+            .validate(&AS3Data::String("v1".to_string()))
+            .unwrap_err()
+            .to_string(),
+        "Value (\"v1\") is not \"v2\" . "
+    );
+}
+
+#[test]
+fn maps_accept_objects_and_closed_objects_reject_unknown_keys() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Map
+            +KeyType:
+                +Type: String
+            +ValueType:
+                +Type: Integer
+                minimum: 0
+                    "#,
+    )
+    .unwrap();
+    let scores = AS3Validator::from(&yaml).unwrap();
+    assert!(scores.is_valid(&AS3Data::from(&json!({ "ada": 3, "bob": 0 }))));
+    assert_eq!(
+        scores.validate_all(&AS3Data::from(&json!({ "ada": 3, "bob": -1 }))),
+        Err(vec![AS3ValidationError::AtPath {
+            path: vec!["bob".to_string()],
+            error: Box::new(AS3ValidationError::Minimum {
+                number: -1.0,
+                minimum: 0.0,
+            }),
+        }])
+    );
+
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            +AdditionalProperties: false
+            maker:
+                +Type: String
+            +Flatten:
+                +Type: Object
+                +AdditionalProperties: false
+                year:
+                    +Type: Integer
+                    "#,
+    )
+    .unwrap();
+    let vehicle = AS3Validator::from(&yaml).unwrap();
+    assert!(vehicle.is_valid(&AS3Data::from(&json!({ "maker": "Fiat", "year": 1990 }))));
+    let data =
+        AS3Data::from(&json!({ "maker": "Fiat", "year": 1990, "wheels": 4, "color": "red" }));
+    assert_eq!(
+        vehicle.validate_all(&data),
+        Err(vec![
+            AS3ValidationError::UnknownKey {
+                key: "color".to_string()
+            },
+            AS3ValidationError::UnknownKey {
+                key: "wheels".to_string()
+            },
+        ])
+    );
+    assert_eq!(
+        vehicle.validate(&data).unwrap_err().message("it"),
+        "La chiave `color` non è ammessa."
+    );
+
+    // Objects are open unless told otherwise.
+    let open = as3_validator!({ maker: String });
+    assert!(open.is_valid(&data));
+    assert_eq!(
+        vehicle
+            .is_backward_compatible_with(&as3_validator!({ maker: String, year: Integer }))
+            .unwrap_err()[0]
+            .message,
+        "undeclared keys are now rejected"
+    );
+    let yaml: serde_yaml::Value =
+        serde_yaml::from_str("Root: { +Type: Object, +AdditionalProperties: nope }").unwrap();
+    assert!(AS3Validator::from(&yaml).is_err());
+}
+
+#[test]
+fn json_schema_converts_in_both_directions() {
+    let json_schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "properties": {
+            "maker": { "type": "string", "pattern": "^[A-Z]", "maxLength": 32 },
+            "year": { "type": "integer", "minimum": 1900, "description": "Model year" },
+            "color": { "enum": ["red", "blue"] },
+            "owners": { "type": "array", "items": { "type": "string" }, "maxItems": 5 },
+        },
+        "required": ["maker", "year"],
+        "additionalProperties": false,
+    });
+    let validator = AS3Validator::from_json_schema(&json_schema).unwrap();
+    assert!(validator.is_valid(&AS3Data::from(&json!({ "maker": "Fiat", "year": 1990 }))));
+    assert!(validator.is_valid(&AS3Data::from(
+        &json!({ "maker": "Fiat", "year": 1990, "color": "red", "owners": ["Ada"] })
+    )));
+    assert!(!validator.is_valid(&AS3Data::from(&json!({ "maker": "fiat", "year": 1990 }))));
+    assert!(!validator.is_valid(&AS3Data::from(&json!({ "maker": "Fiat", "year": 1800 }))));
+    assert!(!validator.is_valid(&AS3Data::from(&json!({ "maker": "Fiat" }))));
+    assert!(!validator.is_valid(&AS3Data::from(
+        &json!({ "maker": "Fiat", "year": 1990, "wheels": 4 })
+    )));
+    assert_eq!(validator.to_json_schema(), json_schema);
+
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+                +Optional: true
+            scores:
+                +Type: Map
+                +KeyType:
+                    +Type: String
+                +ValueType:
+                    +Type: Decimal
+                    minimum: 0
+            "#,
+    )
+    .unwrap();
+    let validator = AS3Validator::from(&yaml).unwrap();
+    let converted = AS3Validator::from_json_schema(&validator.to_json_schema()).unwrap();
+    assert_eq!(converted, validator);
+    assert_eq!(
+        AS3Validator::from_yaml_string(&converted.to_yaml_string().unwrap()).unwrap(),
+        validator
+    );
+
+    assert_eq!(
+        AS3Validator::from_json_schema(&json!({
+            "type": "object",
+            "properties": { "tags": { "type": "array", "minItems": 1 } },
+        }))
+        .unwrap_err()
+        .to_string(),
+        "unsupported JSON Schema keyword `minItems` at `properties.tags`"
+    );
+}
+
+#[test]
+fn integers_must_sit_on_a_step() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Integer
+            minimum: 0
+            maximum: 100
+            step: 25
+                    "#,
+    )
+    .unwrap();
+    let slider = AS3Validator::from(&yaml).unwrap();
+    for on_step in [0, 25, 50, 75, 100] {
+        assert!(slider.is_valid(&AS3Data::Integer(on_step)));
+    }
+    assert_eq!(
+        slider.validate(&AS3Data::Integer(30)),
+        Err(AS3ValidationError::NotOnStep {
+            number: 30,
+            step: 25,
+            origin: 0,
+        })
+    );
+
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Integer
+            step: 10
+            stepOrigin: 5
+                    "#,
+    )
+    .unwrap();
+    let offset = AS3Validator::from(&yaml).unwrap();
+    assert!(offset.is_valid(&AS3Data::Integer(15)));
+    assert!(offset.is_valid(&AS3Data::Integer(-5)));
+    assert!(!offset.is_valid(&AS3Data::Integer(10)));
+    assert!(!offset.is_valid(&AS3Data::Integer(i64::MIN)));
+    assert_eq!(offset, as3_validator!(Integer(step = 10, step_origin = 5)));
+
+    assert!(as3_validator!(Integer(step = 50))
+        .is_backward_compatible_with(&slider)
+        .is_err());
+    assert!(slider
+        .is_backward_compatible_with(&as3_validator!(Integer(min = 0, max = 100, step = 50)))
+        .is_ok());
+    let yaml: serde_yaml::Value =
+        serde_yaml::from_str("Root: { +Type: Integer, step: 0 }").unwrap();
+    assert!(AS3Validator::from(&yaml).is_err());
+}
+
+#[test]
+fn every_error_has_a_kind() {
+    let key = || "a".to_string();
+    let cases = [
+        (
+            AS3ValidationError::TypeError {
+                expected: "String",
+                got: "Integer",
+                preview: None,
+            },
+            ErrorKind::Type,
+        ),
+        (
+            AS3ValidationError::ShapeMismatch {
+                expected: "a list",
+                found: "an object".to_string(),
+            },
+            ErrorKind::Type,
+        ),
+        (
+            AS3ValidationError::MissingKey { key: key() },
+            ErrorKind::Structure,
+        ),
+        (
+            AS3ValidationError::UnknownKey { key: key() },
+            ErrorKind::Structure,
+        ),
+        (
+            AS3ValidationError::DependencyMissing {
+                trigger: key(),
+                required: key(),
+            },
+            ErrorKind::Structure,
+        ),
+        (
+            AS3ValidationError::PropertyName {
+                key: key(),
+                error: Box::new(AS3ValidationError::EmptyString),
+            },
+            ErrorKind::Structure,
+        ),
+        (
+            AS3ValidationError::TupleLength {
+                expected: 2,
+                got: 3,
+            },
+            ErrorKind::Structure,
+        ),
+        (
+            AS3ValidationError::Minimum {
+                number: 1.0,
+                minimum: 2.0,
+            },
+            ErrorKind::Range,
+        ),
+        (
+            AS3ValidationError::Maximum {
+                number: 2.0,
+                maximum: 1.0,
+            },
+            ErrorKind::Range,
+        ),
+        (
+            AS3ValidationError::NotOnStep {
+                number: 1,
+                step: 2,
+                origin: 0,
+            },
+            ErrorKind::Range,
+        ),
+        (
+            AS3ValidationError::TooManyDecimals {
+                value: 1.25,
+                max: 1,
+            },
+            ErrorKind::Range,
+        ),
+        (
+            AS3ValidationError::ListTooLong { length: 3, max: 2 },
+            ErrorKind::Range,
+        ),
+        (
+            AS3ValidationError::StringLength {
+                length: 3,
+                min: None,
+                max: Some(2),
+            },
+            ErrorKind::Range,
+        ),
+        (
+            AS3ValidationError::ByteLength {
+                length: 3,
+                min: None,
+                max: Some(2),
+            },
+            ErrorKind::Range,
+        ),
+        (
+            AS3ValidationError::DateRange {
+                date: key(),
+                after: None,
+                before: None,
+            },
+            ErrorKind::Range,
+        ),
+        (
+            AS3ValidationError::NumberRange {
+                value: key(),
+                minimum: None,
+                maximum: None,
+            },
+            ErrorKind::Range,
+        ),
+        (
+            AS3ValidationError::RegexError {
+                word: key(),
+                regex: key(),
+                position: None,
+            },
+            ErrorKind::Format,
+        ),
+        (
+            AS3ValidationError::FormatError {
+                value: key(),
+                format: "email",
+            },
+            ErrorKind::Format,
+        ),
+        (
+            AS3ValidationError::CaseError {
+                word: key(),
+                case: "upper",
+            },
+            ErrorKind::Format,
+        ),
+        (
+            AS3ValidationError::SurroundingWhitespace { word: key() },
+            ErrorKind::Format,
+        ),
+        (AS3ValidationError::EmptyString, ErrorKind::Format),
+        (
+            AS3ValidationError::NotInEnum { preview: None },
+            ErrorKind::Choice,
+        ),
+        (
+            AS3ValidationError::NotConst {
+                expected: key(),
+                preview: None,
+            },
+            ErrorKind::Choice,
+        ),
+        (
+            AS3ValidationError::UnknownDiscriminator {
+                key: key(),
+                preview: None,
+            },
+            ErrorKind::Choice,
+        ),
+        (
+            AS3ValidationError::NoBranchMatched {
+                closest: None,
+                errors: vec![],
+            },
+            ErrorKind::Choice,
+        ),
+        (
+            AS3ValidationError::MultipleBranchesMatched { count: 2 },
+            ErrorKind::Choice,
+        ),
+        (AS3ValidationError::ReadOnlyViolation, ErrorKind::Access),
+        (AS3ValidationError::WriteOnlyViolation, ErrorKind::Access),
+        (AS3ValidationError::Deprecated, ErrorKind::Access),
+        (
+            AS3ValidationError::ParseError { message: key() },
+            ErrorKind::Parse,
+        ),
+        (
+            AS3ValidationError::MaxDepthExceeded { depth: 256 },
+            ErrorKind::Limit,
+        ),
+        (
+            AS3ValidationError::AtPath {
+                path: vec![key()],
+                error: Box::new(AS3ValidationError::MissingKey { key: key() }),
+            },
+            ErrorKind::Structure,
+        ),
+    ];
+    for (error, kind) in cases {
+        assert_eq!(error.kind(), kind, "{error:?}");
+    }
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn base64_strings_decode_within_byte_bounds() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: String
+            +Format: base64
+            +MinBytes: 1
+            +MaxBytes: 4
+                    "#,
+    )
+    .unwrap();
+    let validator = AS3Validator::from(&yaml).unwrap();
+
+    // "AQID" decodes to the 3 bytes 1, 2, 3.
+    assert!(validator.is_valid(&AS3Data::String("AQID".to_string())));
+    assert_eq!(
+        validator.validate(&AS3Data::String("not base64!".to_string())),
+        Err(AS3ValidationError::FormatError {
+            value: "not base64!".to_string(),
+            format: "base64",
+        })
+    );
+    assert_eq!(
+        validator.validate(&AS3Data::String("AQIDBAUG".to_string())),
+        Err(AS3ValidationError::ByteLength {
+            length: 6,
+            min: Some(1),
+            max: Some(4),
+        })
+    );
+    assert_eq!(
+        validator
+            .validate(&AS3Data::String(String::new()))
+            .unwrap_err()
+            .to_string(),
+        "Content of 0 bytes is outside the allowed range 1 to 4 . "
+    );
+}
+
+#[test]
+fn strings_are_normalized_before_checks() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: String
+            +Normalize: NFC
+            +Regex: "^café$"
+            +MaxLength: 4
+                    "#,
+    )
+    .unwrap();
+    let composed = AS3Data::String("caf\u{e9}".to_string());
+    let decomposed = AS3Data::String("cafe\u{301}".to_string());
+
+    #[cfg(feature = "unicode-normalization")]
+    {
+        let validator = AS3Validator::from(&yaml).unwrap();
+        assert!(validator.is_valid(&composed));
+        assert!(validator.is_valid(&decomposed));
+
+        let AS3Validator::String { normalize, .. } = &validator else {
+            panic!("expected a String validator");
+        };
+        assert_eq!(*normalize, Some(NormalizationForm::Nfc));
+        let mut unnormalized = validator.clone();
+        if let AS3Validator::String { normalize, .. } = &mut unnormalized {
+            *normalize = None;
+        }
+        assert!(unnormalized.is_valid(&composed));
+        assert!(!unnormalized.is_valid(&decomposed));
+    }
+    #[cfg(not(feature = "unicode-normalization"))]
+    {
+        let _ = (composed, decomposed);
+        assert!(AS3Validator::from(&yaml)
+            .unwrap_err()
+            .to_string()
+            .contains("requires the `unicode-normalization` feature"));
+    }
+}
+
+#[test]
+fn sections_pass_or_fail_by_top_level_key() {
+    let validator = as3_validator!({
+        server: { host: String, port: Integer(min = 1, max = 65535) },
+        database: { url: String },
+        logging: { level: String },
+    });
+    let data = AS3Data::from(&json!({
+        "server": { "host": "localhost", "port": 8080 },
+        "database": { "url": 5 },
+    }));
+
+    let sections = validator.validate_sections(&data);
+    assert_eq!(sections.len(), 3);
+    assert_eq!(sections["server"], Ok(()));
+    assert_eq!(
+        sections["database"],
+        Err(vec![AS3ValidationError::AtPath {
+            path: vec!["database".to_string(), "url".to_string()],
+            error: Box::new(AS3ValidationError::TypeError {
+                expected: "String",
+                got: "Integer",
+                preview: Some("5".to_string()),
+            }),
+        }])
+    );
+    assert_eq!(
+        sections["logging"],
+        Err(vec![AS3ValidationError::MissingKey {
+            key: "logging".to_string()
+        }])
+    );
+
+    let sections = validator.validate_sections(&AS3Data::Integer(1));
+    assert!(sections[""].is_err());
+    assert!(sections["server"].is_ok());
+}
+
+#[test]
+fn positional_lists_validate_as_named_fields() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Positional
+            age:
+                +Type: Integer
+                minimum: 0
+            name:
+                +Type: String
+                    "#,
+    )
+    .unwrap();
+    let validator = AS3Validator::from(&yaml).unwrap();
+    let AS3Validator::Positional { fields } = &validator else {
+        panic!("expected a Positional validator");
+    };
+    assert_eq!(fields[0].0, "age");
+    assert_eq!(fields[1].0, "name");
+
+    assert!(validator.is_valid(&AS3Data::from(&json!([25, "Dilec"]))));
+    assert_eq!(
+        validator.validate_all(&AS3Data::from(&json!(["Dilec", 25]))),
+        Err(vec![
+            AS3ValidationError::AtPath {
+                path: vec!["age".to_string()],
+                error: Box::new(AS3ValidationError::TypeError {
+                    expected: "Integer",
+                    got: "String",
+                    preview: Some("\"Dilec\"".to_string()),
+                }),
+            },
+            AS3ValidationError::AtPath {
+                path: vec!["name".to_string()],
+                error: Box::new(AS3ValidationError::TypeError {
+                    expected: "String",
+                    got: "Integer",
+                    preview: Some("25".to_string()),
+                }),
+            },
+        ])
+    );
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!([25]))),
+        Err(AS3ValidationError::TupleLength {
+            expected: 2,
+            got: 1
+        })
+    );
+    assert!(!validator.is_valid(&AS3Data::from(&json!({ "age": 25, "name": "Dilec" }))));
+}
+
+#[test]
+fn errors_group_by_field_path() {
+    let validator = as3_validator!({
+        name: String(regex = "^[A-Z]", max_length = 3),
+        age: Integer(min = 20),
+        city: String,
+    });
+    let errors = validator
+        .validate_all(&AS3Data::from(
+            &json!({ "name": "dilec", "age": 7, "city": "Rome" }),
+        ))
+        .unwrap_err();
+
+    let groups = group_by_path(errors);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(
+        groups["name"],
+        vec![
+            AS3ValidationError::RegexError {
+                word: "dilec".to_string(),
+                regex: "^[A-Z]".to_string(),
+                position: Some(0),
+            },
+            AS3ValidationError::StringLength {
+                length: 5,
+                min: None,
+                max: Some(3),
+            },
+        ]
+    );
+    assert_eq!(
+        groups["age"],
+        vec![AS3ValidationError::Minimum {
+            number: 7.0,
+            minimum: 20.0,
+        }]
+    );
+    assert!(group_by_path(vec![AS3ValidationError::EmptyString]).contains_key(""));
+}
+
+#[test]
+fn cross_field_rules_apply_when_their_condition_holds() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            country:
+                +Type: String
+            zip:
+                +Type: String
+                +Optional: true
+            +Rules:
+                - +When: { country: US }
+                  +Then:
+                      zip:
+                          +Type: String
+                          +Regex: "^\\d{5}$"
+                    "#,
+    )
+    .unwrap();
+    let validator = AS3Validator::from(&yaml).unwrap();
+
+    // Triggered and satisfied.
+    assert!(validator.is_valid(&AS3Data::from(&json!({ "country": "US", "zip": "12345" }))));
+    // Not triggered: any zip, or none, will do.
+    assert!(validator.is_valid(&AS3Data::from(
+        &json!({ "country": "IT", "zip": "I-00100" })
+    )));
+    assert!(validator.is_valid(&AS3Data::from(&json!({ "country": "IT" }))));
+
+    // Triggered and violated.
+    let errors = validator
+        .validate_all(&AS3Data::from(&json!({ "country": "US", "zip": "1234" })))
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        vec![AS3ValidationError::AtPath {
+            path: vec!["zip".to_string()],
+            error: Box::new(AS3ValidationError::RuleViolation {
+                when_key: "country".to_string(),
+                when_value: "\"US\"".to_string(),
+                then_key: "zip".to_string(),
+                error: Box::new(AS3ValidationError::RegexError {
+                    word: "1234".to_string(),
+                    regex: "^\\d{5}$".to_string(),
+                    position: Some(4),
+                }),
+            }),
+        }]
+    );
+    assert!(errors[0]
+        .to_string()
+        .starts_with("Because `country` is \"US\", `zip` is invalid: "));
+    assert_eq!(errors[0].kind(), ErrorKind::Format);
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!({ "country": "US" }))),
+        Err(AS3ValidationError::RuleViolation {
+            when_key: "country".to_string(),
+            when_value: "\"US\"".to_string(),
+            then_key: "zip".to_string(),
+            error: Box::new(AS3ValidationError::MissingKey {
+                key: "zip".to_string()
+            }),
+        })
+    );
+}
+
+#[test]
+fn tree_string_draws_nested_fields_with_their_constraints() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            age:
+                +Type: Integer
+                minimum: 20
+            owner:
+                +Type: Object
+                name:
+                    +Type: String
+                    +Regex: ^[A-Z]
+                nickname:
+                    +Type: String
+                    +Optional: true
+            tags:
+                +Type: Tuple
+                +Items:
+                    - +Type: String
+                +Rest:
+                    +Type: String
+        "#,
+    )
+    .unwrap();
+    let validator = AS3Validator::from(&yaml).unwrap();
+
+    assert_eq!(
+        validator.to_tree_string(),
+        [
+            "Object",
+            "├── age: Integer [minimum: 20]",
+            "├── owner: Object",
+            "│   ├── name: String [regex: \"^[A-Z]\"]",
+            "│   └── nickname: String?",
+            "└── tags: Tuple",
+            "    ├── [0]: String",
+            "    └── [..]: String",
+            "",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn from_file_picks_the_parser_from_the_extension() {
+    let dir = schema_dir("extensions");
+    std::fs::write(
+        dir.join("vehicle.yml"),
+        r#"
+Root:
+  +Type: Object
+  maker:
+    +Type: String
+    +Regex: ^[A-Z][a-z]
+  year:
+    +Type: Integer
+    minimum: 1900
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("vehicle.json"),
+        r#"{
+  "Root": {
+    "+Type": "Object",
+    "maker": { "+Type": "String", "+Regex": "^[A-Z][a-z]" },
+    "year": { "+Type": "Integer", "minimum": 1900 }
+  }
+}"#,
+    )
+    .unwrap();
+    std::fs::write(dir.join("vehicle.txt"), "").unwrap();
+
+    let from_yaml = AS3Validator::from_file(dir.join("vehicle.yml")).unwrap();
+    let from_json = AS3Validator::from_file(dir.join("vehicle.json")).unwrap();
+    assert_eq!(from_yaml, from_json);
+    assert_eq!(
+        from_json,
+        as3_validator!({ maker: String(regex = "^[A-Z][a-z]"), year: Integer(min = 1900) })
+    );
+
+    let error = AS3Validator::from_file(dir.join("vehicle.txt")).unwrap_err();
+    assert!(
+        error.to_string().contains("its extension `.txt`"),
+        "{error}"
+    );
+    std::fs::write(dir.join("broken.json"), "{ \"Root\": ").unwrap();
+    let error = AS3Validator::from_file(dir.join("broken.json")).unwrap_err();
+    assert!(error.to_string().starts_with("Unable to parse"), "{error}");
+}
+
+#[test]
+fn byte_bounds_measure_the_utf8_length() {
+    let validator = as3_validator!(String(max_length = 4, max_bytes = 6));
+
+    // Four characters, but "è" takes two bytes in UTF-8.
+    assert!(validator.is_valid(&AS3Data::String("affè".to_string())));
+    assert_eq!(
+        validator.validate(&AS3Data::String("èèèè".to_string())),
+        Err(AS3ValidationError::ByteLength {
+            length: 8,
+            min: None,
+            max: Some(6),
+        })
+    );
+    assert_eq!(
+        validator.validate(&AS3Data::String("caffè".to_string())),
+        Err(AS3ValidationError::StringLength {
+            length: 5,
+            min: None,
+            max: Some(4),
+        })
+    );
+}
+
+#[test]
+fn sorted_keys_are_required_in_order() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            +RequireSortedKeys: true
+            age:
+                +Type: Integer
+            name:
+                +Type: String
+            zone:
+                +Type: String
+        "#,
+    )
+    .unwrap();
+    let validator = AS3Validator::from(&yaml).unwrap();
+
+    assert_eq!(
+        validator.validate_json_str(r#"{ "age": 30, "name": "Ada", "zone": "EU" }"#),
+        Ok(())
+    );
+    let error = validator
+        .validate_json_str(r#"{ "age": 30, "zone": "EU", "name": "Ada" }"#)
+        .unwrap_err();
+    assert_eq!(
+        error,
+        AS3ValidationError::UnsortedKey {
+            key: "name".to_string(),
+            previous: "zone".to_string(),
+        }
+    );
+    assert_eq!(error.to_string(), "Key `name` should come before `zone` . ");
+    assert_eq!(
+        validator.validate_yaml_str("name: Ada\nage: 30\nzone: EU\n"),
+        Err(AS3ValidationError::UnsortedKey {
+            key: "age".to_string(),
+            previous: "name".to_string(),
+        })
+    );
+}
+
+#[test]
+fn non_empty_collections_reject_empty_data() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            settings:
+                +Type: Object
+                +NonEmpty: true
+        "#,
+    )
+    .unwrap();
+    let validator = AS3Validator::from(&yaml).unwrap();
+
+    assert!(validator.is_valid(&AS3Data::from(&json!({ "settings": { "theme": "dark" } }))));
+    let errors = validator
+        .validate_all(&AS3Data::from(&json!({ "settings": {} })))
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        vec![AS3ValidationError::AtPath {
+            path: vec!["settings".to_string()],
+            error: Box::new(AS3ValidationError::EmptyCollection {
+                collection: "object"
+            }),
+        }]
+    );
+    assert_eq!(errors[0].code(), "empty_collection");
+    assert_eq!(
+        validator.validate_patch(&AS3Data::from(&json!({ "settings": {} }))),
+        Ok(())
+    );
+
+    let validator = AS3Validator::List {
+        items: Arc::new(as3_validator!(String)),
+        max_validated: None,
+        non_empty: true,
+    };
+    assert!(validator.is_valid(&AS3Data::from(&json!(["a"]))));
+    let error = validator.validate(&AS3Data::from(&json!([]))).unwrap_err();
+    assert_eq!(
+        error,
+        AS3ValidationError::EmptyCollection { collection: "list" }
+    );
+    assert_eq!(error.to_string(), "Expected a non-empty list . ");
+}
+
+#[test]
+fn validation_stops_at_its_timeout() {
+    // The regex engine runs in linear time, so a pattern that backtracks catastrophically
+    // elsewhere fails promptly here.
+    let validator = as3_validator!(String(regex = "^(a+)+$"));
+    let input = format!("{}!", "a".repeat(50_000));
+    let started = std::time::Instant::now();
+    assert!(!validator.is_valid(&AS3Data::String(input)));
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+    let validator = as3_validator!({ names: [String(regex = "^[a-z]+$")] });
+    let data = AS3Data::from(&json!({ "names": vec!["ada"; 1_000] }));
+    let options = ValidationOptions {
+        timeout: Some(std::time::Duration::ZERO),
+        ..ValidationOptions::default()
+    };
+    let report = validator.validate_with_options(&data, &options);
+    assert_eq!(
+        report.errors,
+        vec![AS3ValidationError::Timeout {
+            timeout: std::time::Duration::ZERO
+        }]
+    );
+    assert_eq!(report.errors[0].kind(), ErrorKind::Limit);
+    assert!(validator
+        .validate_with_options(&data, &ValidationOptions::default())
+        .is_valid());
+}
+
+#[test]
+fn check_examples_reports_examples_the_schema_rejects() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            +Examples:
+                - { age: 30, name: Ada }
+                - { age: 30 }
+            age:
+                +Type: Integer
+                minimum: 20
+                +Examples: [30, 12]
+            name:
+                +Type: String
+                +Optional: true
+        "#,
+    )
+    .unwrap();
+    let validator = AS3Validator::from(&yaml).unwrap();
+
+    assert_eq!(
+        validator.check_examples(),
+        Err(vec![AS3ValidationError::AtPath {
+            path: vec!["age".to_string(), "+Examples".to_string(), "1".to_string()],
+            error: Box::new(AS3ValidationError::Minimum {
+                number: 12.0,
+                minimum: 20.0,
+            }),
+        }])
+    );
+    assert_eq!(
+        validator.to_json_schema()["properties"]["age"]["examples"],
+        json!([30, 12])
+    );
+    assert_eq!(as3_validator!({ age: Integer }).check_examples(), Ok(()));
+}
+
+#[test]
+fn cached_validator_reuses_results_for_equal_data() {
+    use std::hash::{BuildHasher, RandomState};
+
+    let first = AS3Data::from(&json!({ "age": 30, "name": "Ada" }));
+    let reordered = AS3Data::from(&json!({ "name": "Ada", "age": 30 }));
+    let state = RandomState::new();
+    assert_eq!(first, reordered);
+    assert_eq!(state.hash_one(&first), state.hash_one(&reordered));
+    assert_eq!(
+        state.hash_one(AS3Data::Decimal(0.0)),
+        state.hash_one(AS3Data::Decimal(-0.0))
+    );
+
+    let mut cached =
+        CachedValidator::new(as3_validator!({ age: Integer(min = 20), name: String }), 2);
+    let young = AS3Data::from(&json!({ "age": 12, "name": "Bob" }));
+    assert_eq!(cached.validate_all(&first), Ok(()));
+    assert_eq!(cached.validate_all(&reordered), Ok(()));
+    assert_eq!(cached.hits(), 1);
+    let errors = cached.validate_all(&young).unwrap_err();
+    assert_eq!(cached.validate_all(&young), Err(errors));
+    assert_eq!(cached.hits(), 2);
+
+    // A third document evicts the least recently used one.
+    cached
+        .validate_all(&AS3Data::from(&json!({ "age": 40, "name": "Cy" })))
+        .unwrap();
+    cached.validate_all(&first).unwrap();
+    assert_eq!(cached.hits(), 2);
+}
+
+#[test]
+fn regex_errors_report_where_anchored_patterns_stop_matching() {
+    let position = |regex: &str, word: &str| {
+        let validator = as3_validator!(String(regex = regex));
+        match validator.validate(&AS3Data::String(word.to_string())) {
+            Err(AS3ValidationError::RegexError { position, .. }) => position,
+            result => panic!("expected a regex error, got {result:?}"),
+        }
+    };
+
+    assert_eq!(position("^[A-Z]", "dilec"), Some(0));
+    assert_eq!(position("^[A-Z][a-z]+$", "Dilec!"), Some(5));
+    assert_eq!(position("^[a-zè]+$", "caffè7"), Some(5));
+    assert_eq!(position("^\\d{5}$", "123"), Some(3));
+    assert_eq!(position("[0-9]", "dilec"), None);
+    assert_eq!(
+        as3_validator!(String(regex = "^[A-Z]"))
+            .validate(&AS3Data::String("dilec".to_string()))
+            .unwrap_err()
+            .to_string(),
+        "Word dilec is not following the `^[A-Z]` regex from character 0 "
+    );
+}
+
+#[test]
+fn integer_strings_are_parsed_without_overflowing() {
+    let validator = as3_validator!({ page: Integer(min = 1) });
+    let options = ValidationOptions {
+        integer_strings: true,
+        ..ValidationOptions::default()
+    };
+    let check = |page: &str| {
+        validator
+            .validate_with_options(&AS3Data::from(&json!({ "page": page })), &options)
+            .errors
+    };
+
+    assert_eq!(check("42"), vec![]);
+    assert_eq!(
+        check("-3"),
+        vec![AS3ValidationError::AtPath {
+            path: vec!["page".to_string()],
+            error: Box::new(AS3ValidationError::Minimum {
+                number: -3.0,
+                minimum: 1.0,
+            }),
+        }]
+    );
+    let errors = check("99999999999999999999");
+    assert_eq!(
+        errors,
+        vec![AS3ValidationError::AtPath {
+            path: vec!["page".to_string()],
+            error: Box::new(AS3ValidationError::NumberOutOfRange {
+                value: "99999999999999999999".to_string(),
+            }),
+        }]
+    );
+    assert_eq!(errors[0].code(), "number_out_of_range");
+    assert!(matches!(
+        check("4.5")[0].clone().without_path(),
+        AS3ValidationError::TypeError { .. }
+    ));
+    assert!(!validator.is_valid(&AS3Data::from(&json!({ "page": "42" }))));
+}
+
+#[test]
+fn operators_compose_all_of_and_any_of() {
+    let bounded = as3_validator!(Integer(min = 0)) & as3_validator!(Integer(max = 10));
+    let even_and_bounded = bounded.clone() & as3_validator!(Integer(step = 2));
+    assert_eq!(
+        even_and_bounded,
+        AS3Validator::AllOf(vec![
+            as3_validator!(Integer(min = 0)),
+            as3_validator!(Integer(max = 10)),
+            as3_validator!(Integer(step = 2)),
+        ])
+    );
+    assert!(even_and_bounded.is_valid(&AS3Data::Integer(4)));
+    assert_eq!(
+        even_and_bounded.validate_all(&AS3Data::Integer(11)),
+        Err(vec![
+            AS3ValidationError::Maximum {
+                number: 11.0,
+                maximum: 10.0,
+            },
+            AS3ValidationError::NotOnStep {
+                number: 11,
+                step: 2,
+                origin: 0,
+            },
+        ])
+    );
+
+    let code = as3_validator!(String(regex = "^[A-Z]{3}$")) | as3_validator!(Integer);
+    let code_or_null = code | AS3Validator::Const(AS3Data::Null);
+    assert_eq!(
+        code_or_null,
+        AS3Validator::AnyOf(vec![
+            as3_validator!(String(regex = "^[A-Z]{3}$")),
+            as3_validator!(Integer),
+            AS3Validator::Const(AS3Data::Null),
+        ])
+    );
+    assert!(code_or_null.is_valid(&AS3Data::String("EUR".to_string())));
+    assert!(code_or_null.is_valid(&AS3Data::Null));
+    // A rejection carries the errors of the branch that came closest.
+    let error = code_or_null
+        .validate(&AS3Data::String("euro".to_string()))
+        .unwrap_err();
+    assert!(
+        matches!(
+            &error,
+            AS3ValidationError::NoBranchMatched { closest: Some(0), errors }
+                if errors.len() == 1 && errors[0].code() == "regex"
+        ),
+        "{error:?}"
+    );
+
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +AnyOf:
+                - +Type: String
+                  +Regex: "^[A-Z]{3}$"
+                - +Type: Integer
+                - +Type: Const
+                  +Value: null
+        "#,
+    )
+    .unwrap();
+    assert_eq!(AS3Validator::from(&yaml).unwrap(), code_or_null);
+
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +AllOf:
+                - +Type: Integer
+                  minimum: 0
+                - +Type: Integer
+                  maximum: 10
+        "#,
+    )
+    .unwrap();
+    assert_eq!(AS3Validator::from(&yaml).unwrap(), bounded);
+}
+
+#[test]
+fn strict_loading_rejects_unknown_directives() {
+    let schema: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            age:
+                +Type: Integer
+                +Minimun: 20
+            name:
+                +Type: String
+                +MinLength: 1
+                +Description: Full name
+        "#,
+    )
+    .unwrap();
+
+    assert!(AS3Validator::from(&schema).is_ok());
+    let errors = AS3Validator::try_from_strict(&schema).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![AS3SchemaError::AtPath {
+            path: vec!["age".to_string(), "+Minimun".to_string()],
+            error: Box::new(AS3SchemaError::UnknownDirective {
+                name: "+Minimun".to_string()
+            })
+        }]
+    );
+    assert_eq!(
+        errors[0].to_string(),
+        "Unknown directive `+Minimun` at `age.+Minimun`"
+    );
+
+    // Bounds are read without a `+`, so their typos are caught too, while an object's
+    // other keys remain its fields.
+    let schema: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            minimun:
+                +Type: Integer
+                minimum: 0
+                minimun: 20
+                step: 5
+            price:
+                +Type: Decimal
+                maxDecimalPlace: 2
+        "#,
+    )
+    .unwrap();
+    let mut errors: Vec<_> = AS3Validator::try_from_strict(&schema)
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    errors.sort();
+    assert_eq!(
+        errors,
+        vec![
+            "Unknown directive `maxDecimalPlace` at `price.maxDecimalPlace`".to_string(),
+            "Unknown directive `minimun` at `minimun.minimun`".to_string(),
+        ]
+    );
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn validate_async_fetches_refs() {
+    let url = serve_schema("Root:\n  +Type: Object\n  name:\n    +Type: String\n", 1);
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&format!(
+        r#"
+        Root:
+            +Type: Object
+            owner:
+                +Ref: {url}
+        "#
+    ))
+    .unwrap();
+    let validator = AS3Validator::from(&yaml).unwrap();
+    assert_eq!(
+        validator,
+        AS3Validator::Object {
+            properties: HashMap::from([("owner".to_string(), AS3Validator::Ref(url.clone()))]),
+            property_names: None,
+            when_present: None,
+            dependent_required: HashMap::new(),
+            additional_properties: true,
+            additional_properties_schema: None,
+            require_sorted_keys: false,
+            non_empty: false,
+            rules: vec![],
+        }
+    );
+
+    let data = AS3Data::from(&json!({ "owner": { "name": "Ada" } }));
+    assert_eq!(validator.validate_async(&data).await, Ok(()));
+    assert_eq!(
+        validator.validate(&data),
+        Err(AS3ValidationError::RefUnavailable {
+            url: url.clone(),
+            reason: "it is only fetched by validate_async".to_string(),
+        })
+    );
+
+    let url = serve_schema("Root:\n  +Type: Object\n  name:\n    +Type: String\n", 1);
+    let validator = AS3Validator::Ref(url);
+    let data = AS3Data::from(&json!({ "name": 7 }));
+    assert!(matches!(
+        validator.validate_async(&data).await,
+        Err(AS3ValidationError::TypeError { .. })
+    ));
+
+    let validator = AS3Validator::Ref("http://127.0.0.1:1/schema.yml".to_string());
+    assert!(matches!(
+        validator.validate_async(&data).await,
+        Err(AS3ValidationError::RefUnavailable { .. })
+    ));
+}
+
+#[test]
+fn audit_lists_validated_fields_without_values() {
+    let validator = as3_validator!({
+        age: Integer(min = 20),
+        children: Integer(min = 2),
+        name: String(regex = "^[A-Z][a-z]"),
+        vehicles: {
+            list: [{ name: String, maker: String(regex = "^[A-Z][a-z]"), year: Integer }],
+        },
+    });
+    let data = AS3Data::from(&json!({
+      "age": 25,
+      "children": 5,
+      "name": "Dilec",
+      "vehicles": {
+        "list": [
+          { "name": "model3", "maker": "Tesla", "year": 2018 },
+          { "name": "Raptor", "maker": "ford", "year": 2018 }
+        ]
+      }
+    }));
+    let options = ValidationOptions {
+        audit: true,
+        ..ValidationOptions::default()
+    };
+
+    let report = validator.validate_with_options(&data, &options);
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(
+        report.audited,
+        [
+            ("age".to_string(), "Integer"),
+            ("children".to_string(), "Integer"),
+            ("name".to_string(), "String"),
+            ("vehicles.list.0.maker".to_string(), "String"),
+            ("vehicles.list.0.name".to_string(), "String"),
+            ("vehicles.list.0.year".to_string(), "Integer"),
+            ("vehicles.list.1.name".to_string(), "String"),
+            ("vehicles.list.1.year".to_string(), "Integer"),
+        ]
+    );
+
+    let report = validator.validate_with_options(&data, &ValidationOptions::default());
+    assert!(report.audited.is_empty());
+}
+
+#[test]
+fn list_elements_dispatch_on_their_own_tag() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: List
+            +Items:
+                +Discriminator: type
+                +Mapping:
+                    car:
+                        +Type: Object
+                        wheels:
+                            +Type: Integer
+                            minimum: 3
+                    boat:
+                        +Type: Object
+                        length:
+                            +Type: Decimal
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validator.validate_json_str(
+            r#"[{ "type": "car", "wheels": 4 }, { "type": "boat", "length": 7.5 }]"#
+        ),
+        Ok(())
+    );
+    let data = AS3Data::try_from(
+        r#"[
+            { "type": "boat", "length": 7.5 },
+            { "type": "car", "wheels": 2 },
+            { "type": "boat", "wheels": 4 }
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        validator.validate_all(&data),
+        Err(vec![
+            AS3ValidationError::AtPath {
+                path: vec!["1".to_string(), "wheels".to_string()],
+                error: Box::new(AS3ValidationError::Minimum {
+                    number: 2.0,
+                    minimum: 3.0
+                })
+            },
+            AS3ValidationError::AtPath {
+                path: vec!["2".to_string()],
+                error: Box::new(AS3ValidationError::MissingKey {
+                    key: "length".to_string()
+                })
+            },
+        ])
+    );
+    assert_eq!(
+        AS3Validator::from_yaml_str("Root:\n  +Type: List\n"),
+        Err(AS3SchemaError::Invalid(
+            "List requires a +Items validator".to_string()
+        ))
+    );
+}
+
+#[test]
+fn constructors_build_unconstrained_validators() {
+    assert_eq!(AS3Validator::default(), AS3Validator::Any);
+    assert_eq!(
+        AS3Validator::any_string(),
+        AS3Validator::String {
+            regex: None,
+            min_length: None,
+            max_length: None,
+            format: None,
+            case: None,
+            no_surrounding_whitespace: false,
+            non_empty: false,
+            min_bytes: None,
+            max_bytes: None,
+            normalize: None,
+            grammar: None,
+        }
+    );
+    assert_eq!(
+        AS3Validator::any_integer(),
+        AS3Validator::Integer {
+            minimum: None,
+            maximum: None,
+            step: None,
+            step_origin: None,
+        }
+    );
+    assert_eq!(
+        AS3Validator::any_decimal(),
+        AS3Validator::Decimal {
+            minimum: None,
+            maximum: None,
+            max_decimal_places: None,
+        }
+    );
+    assert_eq!(
+        AS3Validator::list_of(AS3Validator::any_integer()),
+        AS3Validator::List {
+            items: Arc::new(AS3Validator::any_integer()),
+            max_validated: None,
+            non_empty: false,
+        }
+    );
+
+    let validator = AS3Validator::object([
+        ("name", AS3Validator::any_string()),
+        ("year", AS3Validator::any_integer()),
+    ]);
+    assert_eq!(validator, as3_validator!({ name: String, year: Integer }));
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!({ "name": "raptor", "year": 2018 }))),
+        Ok(())
+    );
+}
+
+#[test]
+fn openapi_component_schemas_resolve_local_refs() {
+    let doc: serde_yaml::Value = serde_yaml::from_str(
+        r##"
+        openapi: 3.0.3
+        info:
+            title: Garage
+            version: 1.0.0
+        paths: {}
+        components:
+            schemas:
+                Vehicle:
+                    type: object
+                    required: [maker, year]
+                    properties:
+                        maker:
+                            type: string
+                            pattern: "^[A-Z][a-z]"
+                            example: Tesla
+                        year:
+                            type: integer
+                            minimum: 1900
+                        plate:
+                            type: string
+                            nullable: true
+                Owner:
+                    type: object
+                    required: [name, vehicles]
+                    properties:
+                        name:
+                            type: string
+                        vehicles:
+                            type: array
+                            items:
+                                $ref: "#/components/schemas/Vehicle"
+        "##,
+    )
+    .unwrap();
+
+    let validator = AS3Validator::from_openapi_component(&doc, "Owner").unwrap();
+    assert_eq!(
+        validator.validate_json_str(
+            r#"{ "name": "Dilec", "vehicles": [{ "maker": "Tesla", "year": 2018, "plate": null }] }"#
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_all(&AS3Data::from(&json!({
+            "name": "Dilec",
+            "vehicles": [{ "maker": "Ford", "year": 1800 }]
+        }))),
+        Err(vec![AS3ValidationError::AtPath {
+            path: vec!["vehicles".to_string(), "0".to_string(), "year".to_string()],
+            error: Box::new(AS3ValidationError::Minimum {
+                number: 1800.0,
+                minimum: 1900.0
+            })
+        }])
+    );
+    assert_eq!(
+        AS3Validator::from_openapi_component(&doc, "Vehicle")
+            .unwrap()
+            .get("maker")
+            .and_then(AS3Validator::annotations)
+            .map(|annotations| annotations.examples.clone()),
+        Some(vec![AS3Data::String("Tesla".to_string())])
+    );
+    assert_eq!(
+        AS3Validator::from_openapi_component(&doc, "Garage"),
+        Err(AS3SchemaError::Invalid(
+            "The OpenAPI document has no `components.schemas.Garage`".to_string()
+        ))
+    );
+
+    let doc: serde_yaml::Value = serde_yaml::from_str(
+        r##"
+        components:
+            schemas:
+                Node:
+                    type: object
+                    properties:
+                        next:
+                            $ref: "#/components/schemas/Node"
+        "##,
+    )
+    .unwrap();
+    assert_eq!(
+        AS3Validator::from_openapi_component(&doc, "Node"),
+        Err(AS3SchemaError::Invalid(
+            "`#/components/schemas/Node` refers back to itself, which cannot be inlined"
+                .to_string()
+        ))
+    );
+}
+
+#[test]
+fn scientific_notation_decimals_as_numbers_and_strings() {
+    let validator = as3_validator!({ amount: Decimal(max = 1e12) });
+    let data = AS3Data::try_from(r#"{ "amount": 1e10 }"#).unwrap();
+    assert_eq!(data, AS3Data::from(&json!({ "amount": 10_000_000_000.0 })));
+    assert_eq!(validator.validate(&data), Ok(()));
+    assert_eq!(
+        validator.validate_json_str(r#"{ "amount": 2.5E-3 }"#),
+        Ok(())
+    );
+
+    let options = ValidationOptions {
+        decimal_strings: true,
+        ..ValidationOptions::default()
+    };
+    let check = |amount: &str| {
+        validator
+            .validate_with_options(&AS3Data::from(&json!({ "amount": amount })), &options)
+            .errors
+    };
+    assert_eq!(check("1e10"), vec![]);
+    assert_eq!(check("-0.5"), vec![]);
+    assert_eq!(
+        check("1e13"),
+        vec![AS3ValidationError::AtPath {
+            path: vec!["amount".to_string()],
+            error: Box::new(AS3ValidationError::Maximum {
+                number: 1e13,
+                maximum: 1e12,
+            }),
+        }]
+    );
+    assert_eq!(
+        check("1e400"),
+        vec![AS3ValidationError::AtPath {
+            path: vec!["amount".to_string()],
+            error: Box::new(AS3ValidationError::NumberOutOfRange {
+                value: "1e400".to_string(),
+            }),
+        }]
+    );
+    for not_a_number in ["inf", "NaN", "1.", "e5", "1e"] {
+        assert!(matches!(
+            check(not_a_number)[0].clone().without_path(),
+            AS3ValidationError::TypeError { .. }
+        ));
+    }
+    assert!(!validator.is_valid(&AS3Data::from(&json!({ "amount": "1e10" }))));
+}
+
+#[test]
+fn mask_redacts_annotated_fields() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+            cards:
+                +Type: List
+                +Items:
+                    +Type: Object
+                    number:
+                        +Type: String
+                        +Regex: "^[0-9]{16}$"
+                        +Mask: last4
+                    cvv:
+                        +Type: Integer
+                        +Mask: full
+                    "#,
+    )
+    .unwrap();
+    let data = AS3Data::from(&json!({
+        "name": "Dilec",
+        "cards": [{ "number": "4111111111111234", "cvv": 123 }]
+    }));
+
+    assert_eq!(validator.validate(&data), Ok(()));
+    assert_eq!(
+        validator.mask(&data),
+        AS3Data::from(&json!({
+            "name": "Dilec",
+            "cards": [{ "number": "****1234", "cvv": "****" }]
+        }))
+    );
+    assert_eq!(
+        validator.mask(&AS3Data::from(&json!({ "cards": [{ "number": "1234" }] }))),
+        AS3Data::from(&json!({ "cards": [{ "number": "****" }] }))
+    );
+    assert_eq!(
+        AS3Validator::from_yaml_str("Root:\n  +Type: String\n  +Mask: middle\n"),
+        Err(AS3SchemaError::Invalid(
+            "unsupported mask `middle`".to_string()
+        ))
+    );
+}
+
+#[test]
+fn integer_bounds_compare_exactly_past_two_to_the_53() {
+    // 2^53 + 1 rounds to 2^53 as an f64, so an f64 comparison would let this pass.
+    let minimum = (1_i64 << 53) + 1;
+    let validator = AS3Validator::Integer {
+        minimum: Some(minimum),
+        maximum: Some(i64::MAX - 1),
+        step: None,
+        step_origin: None,
+    };
+    assert_eq!((1_i64 << 53) as f64, minimum as f64);
+
+    let error = validator.validate(&AS3Data::Integer(1 << 53)).unwrap_err();
+    assert_eq!(
+        error,
+        AS3ValidationError::IntegerMinimum {
+            number: 1 << 53,
+            minimum,
+        }
+    );
+    assert_eq!(error.code(), "minimum");
+    assert_eq!(
+        error.to_string(),
+        " `9007199254740992` is under the minumum of `9007199254740993` . "
+    );
+    assert_eq!(
+        error.message("it"),
+        "`9007199254740992` è sotto il minimo di `9007199254740993`."
+    );
+    assert_eq!(validator.validate(&AS3Data::Integer(minimum)), Ok(()));
+    assert_eq!(
+        validator.validate(&AS3Data::Integer(i64::MAX)),
+        Err(AS3ValidationError::IntegerMaximum {
+            number: i64::MAX,
+            maximum: i64::MAX - 1,
+        })
+    );
+}
+
+/// Accepts balanced parentheses, which no regex can describe.
+fn balanced(input: &str) -> Result<(), usize> {
+    let mut depth = 0_usize;
+    for (position, character) in input.chars().enumerate() {
+        depth = match character {
+            '(' => depth + 1,
+            ')' if depth > 0 => depth - 1,
+            _ => return Err(position),
+        };
+    }
+    match depth {
+        0 => Ok(()),
+        _ => Err(input.chars().count()),
+    }
+}
+
+#[test]
+fn strings_parsed_by_registered_grammars() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            filter:
+                +Type: String
+                +Grammar: balanced
+                    "#,
+    )
+    .unwrap();
+    let options = ValidationOptions::default().grammar("balanced", balanced);
+    let check = |filter: &str| {
+        validator
+            .validate_with_options(&AS3Data::from(&json!({ "filter": filter })), &options)
+            .errors
+            .into_iter()
+            .map(AS3ValidationError::without_path)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(check("(()())"), vec![]);
+    assert_eq!(
+        check("(()))("),
+        vec![AS3ValidationError::GrammarError {
+            word: "(()))(".to_string(),
+            grammar: "balanced".to_string(),
+            position: 4,
+        }]
+    );
+    let errors = check("((");
+    assert_eq!(
+        errors[0].to_string(),
+        "Word (( does not parse as `balanced` from character 2 . "
+    );
+    assert_eq!(errors[0].code(), "grammar");
+    assert_eq!(
+        validator.validate(&AS3Data::from(&json!({ "filter": "()" }))),
+        Err(AS3ValidationError::UnknownGrammar {
+            grammar: "balanced".to_string()
+        })
+    );
+}
+
+#[test]
+fn self_referencing_trees_validate_at_any_depth() {
+    let validator = AS3Validator::from_yaml_str(
+        r##"
+        Root:
+            +Type: Object
+            name:
+                +Type: String
+            children:
+                +Type: Map
+                +KeyType:
+                    +Type: String
+                +ValueType:
+                    +Ref: "#"
+                    "##,
+    )
+    .unwrap();
+    let node = |name: &str, children: serde_json::Value| json!({ "name": name, "children": { name: children } });
+    let mut tree = json!({ "name": "leaf", "children": {} });
+    for level in ["e", "d", "c", "b", "a"] {
+        tree = node(level, tree);
+    }
+    assert_eq!(validator.validate(&AS3Data::from(&tree)), Ok(()));
+
+    tree["children"]["a"]["children"]["b"]["children"]["c"]["children"]["d"]["name"] = json!(4);
+    assert_eq!(
+        validator.validate_all(&AS3Data::from(&tree)),
+        Err(vec![AS3ValidationError::AtPath {
+            path: ["children", "a", "children", "b", "children", "c", "children", "d", "name"]
+                .map(String::from)
+                .to_vec(),
+            error: Box::new(AS3ValidationError::TypeError {
+                expected: "String",
+                got: "Integer",
+                preview: Some("4".to_string())
+            })
+        }])
+    );
+
+    let mut deep = json!({ "name": "leaf", "children": {} });
+    for _ in 0..200 {
+        deep = node("n", deep);
+    }
+    assert!(matches!(
+        validator.validate(&AS3Data::from(&deep)),
+        Err(AS3ValidationError::MaxDepthExceeded { .. })
+    ));
+
+    let endless = AS3Validator::Ref("#".to_string());
+    assert_eq!(
+        endless.validate(&AS3Data::Null),
+        Err(AS3ValidationError::MaxDepthExceeded { depth: 256 })
+    );
+}
+
+#[test]
+fn objects_validate_undeclared_keys_against_a_recursive_schema() {
+    let validator = AS3Validator::from_yaml_str(
+        r##"
+        Root:
+            +Type: Object
+            label:
+                +Type: String
+            +AdditionalProperties:
+                +Ref: "#"
+                    "##,
+    )
+    .unwrap();
+    let node = |name: &str, child: serde_json::Value| json!({ "label": name, name: child });
+    let mut tree = json!({ "label": "leaf" });
+    for level in ["e", "d", "c", "b", "a"] {
+        tree = node(level, tree);
+    }
+    assert_eq!(validator.validate(&AS3Data::from(&tree)), Ok(()));
+
+    tree["a"]["b"]["c"]["d"]["label"] = json!(4);
+    assert_eq!(
+        validator.validate_all(&AS3Data::from(&tree)),
+        Err(vec![AS3ValidationError::AtPath {
+            path: ["a", "b", "c", "d", "label"].map(String::from).to_vec(),
+            error: Box::new(AS3ValidationError::TypeError {
+                expected: "String",
+                got: "Integer",
+                preview: Some("4".to_string())
+            })
+        }])
+    );
+    assert_eq!(
+        validator.validate_pointer("/a/b/c/d/label", &AS3Data::from(&tree)),
+        Err(AS3ValidationError::TypeError {
+            expected: "String",
+            got: "Integer",
+            preview: Some("4".to_string())
+        })
+    );
+
+    let mut deep = json!({ "label": "leaf" });
+    for _ in 0..300 {
+        deep = node("n", deep);
+    }
+    assert!(matches!(
+        validator.validate(&AS3Data::from(&deep)),
+        Err(AS3ValidationError::MaxDepthExceeded { .. })
+    ));
+
+    let round_tripped = AS3Validator::from_yaml_string(&validator.to_yaml_string().unwrap());
+    assert_eq!(round_tripped.unwrap(), validator);
+}
+
+#[test]
+fn validate_pointer_checks_one_fragment() {
+    let validator = as3_validator!({
+        name: String(regex = "^[A-Z][a-z]"),
+        vehicles: {
+            list: [{ name: String, maker: String(regex = "^[A-Z][a-z]"), year: Integer }],
+        },
+    });
+    let data = AS3Data::from(&json!({
+        "name": "not checked",
+        "vehicles": {
+            "list": [
+                { "name": "Model T", "maker": "Ford", "year": 1908 },
+                { "name": "Beetle", "maker": "volkswagen", "year": 1938 },
+            ]
+        }
+    }));
+
+    assert_eq!(
+        validator.validate_pointer("/vehicles/list/0", &data),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_pointer("/vehicles/list/1", &data),
+        Err(AS3ValidationError::RegexError {
+            word: "volkswagen".to_string(),
+            regex: "^[A-Z][a-z]".to_string(),
+            position: Some(0),
+        })
+    );
+    assert_eq!(
+        validator.validate_pointer("/vehicles/list/0/year", &data),
+        Ok(())
+    );
+    assert!(validator.validate_pointer("", &data).is_err());
+    assert_eq!(
+        validator.validate_pointer("/vehicles/list/2", &data),
+        Err(AS3ValidationError::PointerNotFound {
+            pointer: "/vehicles/list/2".to_string()
+        })
+    );
+    assert_eq!(
+        validator.validate_pointer("/vehicles/list/01", &data),
+        Err(AS3ValidationError::PointerNotFound {
+            pointer: "/vehicles/list/01".to_string()
+        })
+    );
+}
+
+#[test]
+fn enum_entries_are_checked_under_the_run_options() {
+    let status = AS3Validator::Enum(vec![
+        AS3Validator::Const(AS3Data::Integer(200)),
+        as3_validator!(Integer(min = 500, max = 599)),
+    ]);
+    let options = ValidationOptions {
+        integer_strings: true,
+        ..ValidationOptions::default()
+    };
+    let check = |value: &str| {
+        status
+            .validate_with_options(&AS3Data::String(value.to_string()), &options)
+            .errors
+    };
+    assert_eq!(check("503"), vec![]);
+    assert_eq!(
+        check("404"),
+        vec![AS3ValidationError::NotInEnum {
+            preview: Some("\"404\"".to_string())
+        }]
+    );
+
+    let filter = AS3Validator::Const(AS3Data::String("all".to_string()))
+        | AS3Validator::from_yaml_str(
+            r#"
+            Root:
+                +Type: String
+                +Grammar: balanced
+                    "#,
+        )
+        .unwrap();
+    let options = ValidationOptions::default().grammar("balanced", balanced);
+    let report = filter.validate_with_options(&AS3Data::String("(()())".to_string()), &options);
+    assert!(report.is_valid());
+    let report = filter.validate_with_options(&AS3Data::String("(()".to_string()), &options);
+    assert!(!report.is_valid());
+}
+
+#[test]
+fn mask_reaches_into_unions_and_recursive_trees() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            payment:
+                +OneOf:
+                    - +Type: Object
+                      card:
+                          +Type: String
+                          +Mask: last4
+                    - +Type: Object
+                      iban:
+                          +Type: String
+                          +Mask: full
+                    "#,
+    )
+    .unwrap();
+    // Neither branch accepts `card` as a number, yet it is still redacted.
+    let data = AS3Data::from(&json!({ "payment": { "card": 4111111111111234_i64 } }));
+    assert!(!validator.is_valid(&data));
+    assert_eq!(
+        validator.mask(&data),
+        AS3Data::from(&json!({ "payment": { "card": "****1234" } }))
+    );
+
+    let tree = AS3Validator::from_yaml_str(
+        r##"
+        Root:
+            +Type: Object
+            secret:
+                +Type: String
+                +Mask: full
+            children:
+                +Type: Map
+                +KeyType:
+                    +Type: String
+                +ValueType:
+                    +Ref: "#"
+                    "##,
+    )
+    .unwrap();
+    let data = AS3Data::from(&json!({
+        "secret": "a",
+        "children": { "x": { "secret": "b", "children": { "y": { "secret": "c", "children": {} } } } }
+    }));
+    assert_eq!(
+        tree.mask(&data),
+        AS3Data::from(&json!({
+            "secret": "****",
+            "children": {
+                "x": { "secret": "****", "children": { "y": { "secret": "****", "children": {} } } }
+            }
+        }))
+    );
+    assert_eq!(
+        AS3Validator::Ref("#".to_string()).mask(&AS3Data::Null),
+        AS3Data::Null
+    );
+}
+
+#[test]
+fn removing_when_present_breaks_compatibility() {
+    let guarded = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            card:
+                +Type: Object
+                +WhenPresent: number
+                number:
+                    +Type: String
+                    "#,
+    )
+    .unwrap();
+    let unguarded = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            card:
+                +Type: Object
+                number:
+                    +Type: String
+                    "#,
+    )
+    .unwrap();
+
+    assert_eq!(guarded.is_backward_compatible_with(&unguarded), Ok(()));
+    assert_eq!(
+        unguarded.is_backward_compatible_with(&guarded),
+        Err(vec![Incompatibility {
+            path: vec!["card".to_string()],
+            message: "+WhenPresent `number` removed, so the object is always checked".to_string()
+        }])
+    );
+}
+
+#[test]
+fn openapi_properties_may_be_named_like_keywords() {
+    let doc: serde_yaml::Value = serde_yaml::from_str(
+        r##"
+        components:
+            schemas:
+                Column:
+                    type: object
+                    required: [nullable, example]
+                    properties:
+                        nullable:
+                            type: boolean
+                        example:
+                            type: string
+                            example: "42"
+        "##,
+    )
+    .unwrap();
+
+    let validator = AS3Validator::from_openapi_component(&doc, "Column").unwrap();
+    assert_eq!(
+        validator.validate_json_str(r#"{ "nullable": true, "example": "42" }"#),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_json_str(r#"{ "example": "42" }"#),
+        Err(AS3ValidationError::MissingKey {
+            key: "nullable".to_string()
+        })
+    );
+    assert_eq!(
+        validator
+            .get("example")
+            .and_then(AS3Validator::annotations)
+            .map(|annotations| annotations.examples.clone()),
+        Some(vec![AS3Data::String("42".to_string())])
+    );
+}
+
+#[test]
+fn json_schema_bounds_never_loosen() {
+    let validator = AS3Validator::from_json_schema(&json!({
+        "type": "integer",
+        "minimum": 10,
+        "exclusiveMinimum": 0,
+        "maximum": 50,
+        "exclusiveMaximum": 20,
+        "multipleOf": 2.0,
+    }))
+    .unwrap();
+    assert_eq!(
+        validator,
+        AS3Validator::Integer {
+            minimum: Some(10),
+            maximum: Some(19),
+            step: Some(2),
+            step_origin: None,
+        }
+    );
+
+    let error = |schema: serde_json::Value| {
+        AS3Validator::from_json_schema(&schema)
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(
+        error(json!({ "type": "integer", "multipleOf": 2.5 })),
+        "`multipleOf` must be a positive integer on integers"
+    );
+    assert_eq!(
+        error(json!({ "type": "integer", "multipleOf": -2 })),
+        "`multipleOf` must be a positive integer on integers"
+    );
+    assert_eq!(
+        error(
+            json!({ "type": "object", "properties": { "name": { "type": "string", "maxLength": 1.5 } } })
+        ),
+        "`maxLength` must be a non-negative integer, not 1.5 at `properties.name`"
+    );
+    assert_eq!(
+        error(json!({ "type": "array", "items": true, "maxItems": -1 })),
+        "`maxItems` must be a non-negative integer, not -1"
+    );
+}
+
+#[test]
+fn code_built_validators_report_unusable_regexes() {
+    let validator = AS3Validator::String {
+        regex: Some("(".to_string()),
+        min_length: None,
+        max_length: None,
+        format: None,
+        case: None,
+        no_surrounding_whitespace: false,
+        non_empty: false,
+        min_bytes: None,
+        max_bytes: None,
+        normalize: None,
+        grammar: None,
+    };
+
+    let error = validator
+        .validate(&AS3Data::String("anything".to_string()))
+        .unwrap_err();
+    assert_eq!(
+        error,
+        AS3ValidationError::UnusableRegex {
+            regex: "(".to_string()
+        }
+    );
+    assert_eq!(error.code(), "invalid_regex");
+    assert!(matches!(
+        validator.schema_errors().as_slice(),
+        [AS3SchemaError::InvalidRegex { .. }]
+    ));
+}
+
+#[test]
+fn validate_pointer_follows_unions_and_options() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            contact:
+                +OneOf:
+                    - +Type: Object
+                      email:
+                          +Type: String
+                          +Format: email
+                    - +Type: Object
+                      phone:
+                          +Type: Integer
+            pet:
+                +Discriminator: kind
+                +Mapping:
+                    dog:
+                        +Type: Object
+                        age:
+                            +Type: Integer
+                    "#,
+    )
+    .unwrap();
+    let check = |pointer: &str, data: serde_json::Value| {
+        validator.validate_pointer(pointer, &AS3Data::from(&data))
+    };
+
+    // No alternative accepts the contact, but only one has an `email`.
+    assert_eq!(
+        check("/contact/email", json!({ "contact": { "email": "nope" } })),
+        Err(AS3ValidationError::FormatError {
+            value: "nope".to_string(),
+            format: "email"
+        })
+    );
+    assert_eq!(
+        check("/contact/fax", json!({ "contact": { "fax": 1 } })),
+        Err(AS3ValidationError::AmbiguousPointer {
+            segment: "fax".to_string()
+        })
+    );
+    assert_eq!(
+        check(
+            "/pet/age",
+            json!({ "pet": { "kind": "dog", "age": "three" } })
+        )
+        .map_err(|error| error.code()),
+        Err("type_error")
+    );
+    assert_eq!(
+        check("/pet/age", json!({ "pet": { "age": 3 } })),
+        Err(AS3ValidationError::MissingKey {
+            key: "kind".to_string()
+        })
+    );
+
+    let options = ValidationOptions {
+        integer_strings: true,
+        ..ValidationOptions::default()
+    };
+    let data = AS3Data::from(&json!({ "contact": { "phone": "5550100" } }));
+    assert!(validator
+        .validate_pointer_with_options("/contact/phone", &data, &options)
+        .is_valid());
+    let report = validator.validate_pointer_with_options(
+        "/contact/phone",
+        &AS3Data::from(&json!({ "contact": { "phone": "call me" } })),
+        &options,
+    );
+    assert_eq!(report.errors[0].path(), ["contact", "phone"]);
+}
+
+#[test]
+fn malformed_examples_are_schema_errors() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            year:
+                +Type: Integer
+                +Examples: 2024
+            tags:
+                +Type: String
+                +Examples:
+                    - news
+                    - { [1, 2]: pair }
+        "#,
+    )
+    .unwrap();
+
+    let mut errors: Vec<_> = AS3Validator::try_from(&yaml)
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    errors.sort();
+    assert_eq!(
+        errors,
+        vec![
+            "+Examples must be a list at `year.+Examples`".to_string(),
+            "unsupported literal value: key must be a string at `tags.+Examples.1`".to_string(),
+        ]
+    );
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn fetched_refs_resolve_hash_against_their_own_root() {
+    let url = serve_schema(
+        "Root:\n  +Type: Object\n  name:\n    +Type: String\n  children:\n    +Type: List\n    +Items:\n      +Ref: \"#\"\n",
+        2,
+    );
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&format!(
+        r#"
+        Root:
+            +Type: Object
+            owner:
+                +Ref: {url}
+        "#
+    ))
+    .unwrap();
+    let validator = AS3Validator::from(&yaml).unwrap();
+
+    let data = AS3Data::from(&json!({
+        "owner": { "name": "Ada", "children": [{ "name": "Byron", "children": [] }] }
+    }));
+    assert_eq!(validator.validate_async(&data).await, Ok(()));
+
+    let data = AS3Data::from(&json!({
+        "owner": { "name": "Ada", "children": [{ "name": 7, "children": [] }] }
+    }));
+    assert!(matches!(
+        validator.validate_async(&data).await,
+        Err(AS3ValidationError::TypeError { .. })
+    ));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn unparsable_date_bounds_are_schema_errors() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            check_in:
+                +Type: Date
+                +After: "2020-13-01"
+                +Before: "someday"
+        "#,
+    )
+    .unwrap();
+
+    let errors: Vec<_> = AS3Validator::try_from(&yaml)
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            "`+After` must be a YYYY-MM-DD date, not `2020-13-01` at `check_in`".to_string(),
+            "`+Before` must be a YYYY-MM-DD date, not `someday` at `check_in`".to_string(),
+        ]
+    );
+}
+
+#[cfg(feature = "bigdecimal")]
+#[test]
+fn unparsable_big_number_bounds_are_schema_errors() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            balance:
+                +Type: BigNumber
+                minimum: "-1e40"
+                maximum: "lots"
+        "#,
+    )
+    .unwrap();
+
+    let errors: Vec<_> = AS3Validator::try_from(&yaml)
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        errors,
+        vec!["`maximum` must be a number, not `lots` at `balance`".to_string()]
+    );
+}
+
+#[test]
+fn property_names_are_checked_under_the_run_options() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Object
+            +PropertyNames:
+                +Type: String
+                +Grammar: balanced
+        "#,
+    )
+    .unwrap();
+    let options = ValidationOptions::default().grammar("balanced", balanced);
+    let check = |data: serde_json::Value| {
+        validator
+            .validate_with_options(&AS3Data::from(&data), &options)
+            .errors
+    };
+
+    assert_eq!(check(json!({ "(())": 1, "()": 2 })), vec![]);
+    assert_eq!(
+        check(json!({ "(()": 1 })),
+        vec![AS3ValidationError::PropertyName {
+            key: "(()".to_string(),
+            error: Box::new(AS3ValidationError::GrammarError {
+                word: "(()".to_string(),
+                grammar: "balanced".to_string(),
+                position: 3,
+            }),
+        }]
+    );
+
+    let soft = ValidationOptions::default()
+        .grammar("balanced", balanced)
+        .soft("grammar");
+    let report = validator.validate_with_options(&AS3Data::from(&json!({ "(()": 1 })), &soft);
+    assert!(report.is_valid());
+    assert_eq!(report.warnings.len(), 1);
+}
+
+#[test]
+fn map_keys_are_checked_under_the_run_options() {
+    let validator = AS3Validator::from_yaml_str(
+        r#"
+        Root:
+            +Type: Map
+            +KeyType:
+                +Type: String
+                +Grammar: balanced
+            +ValueType:
+                +Type: Integer
+        "#,
+    )
+    .unwrap();
+    let options = ValidationOptions::default().grammar("balanced", balanced);
+    let check = |data: serde_json::Value| {
+        validator
+            .validate_with_options(&AS3Data::from(&data), &options)
+            .errors
+    };
+
+    assert_eq!(check(json!({ "()": 1 })), vec![]);
+    assert_eq!(
+        check(json!({ ")(": 1 })),
+        vec![AS3ValidationError::PropertyName {
+            key: ")(".to_string(),
+            error: Box::new(AS3ValidationError::GrammarError {
+                word: ")(".to_string(),
+                grammar: "balanced".to_string(),
+                position: 0,
+            }),
+        }]
+    );
+}
+
+#[test]
+fn mistyped_numeric_bounds_are_schema_errors() {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+        Root:
+            +Type: Object
+            age:
+                +Type: Integer
+                minimum: "abc"
+                step: 1.5
+            price:
+                +Type: Decimal
+                maximum: [10]
+                maxDecimalPlaces: -1
+        "#,
+    )
+    .unwrap();
+
+    let mut errors: Vec<_> = AS3Validator::try_from(&yaml)
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    errors.sort();
+    assert_eq!(
+        errors,
+        vec![
+            "`maxDecimalPlaces` must be a non-negative integer at `price.maxDecimalPlaces`"
+                .to_string(),
+            "`maximum` must be a number at `price.maximum`".to_string(),
+            "`minimum` must be an integer at `age.minimum`".to_string(),
+            "`step` must be an integer at `age.step`".to_string(),
+        ]
+    );
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_integer_bounds_stay_exact() {
+    const CEILING: i64 = i64::MAX - 1;
+
+    #[derive(AS3Schema)]
+    #[allow(dead_code)]
+    struct Ledger {
+        #[as3(min = 9007199254740993, max = CEILING)]
+        sequence: i64,
+        #[as3(min = -5)]
+        offset: i64,
+        #[as3(min = 0.5, max = 10)]
+        rate: f64,
+    }
+
+    let validator = Ledger::as3_validator();
+    let property = |key: &str| match &validator {
+        AS3Validator::Object { properties, .. } => properties[key].clone(),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        property("sequence"),
+        as3_validator!(Integer(min = (1_i64 << 53) + 1, max = CEILING))
+    );
+    assert_eq!(property("offset"), as3_validator!(Integer(min = -5)));
+    assert_eq!(
+        property("rate"),
+        as3_validator!(Decimal(min = 0.5, max = 10.0))
+    );
+    assert!(validator
+        .validate(&AS3Data::from(&json!({
+            "sequence": 9007199254740992_i64,
+            "offset": 0,
+            "rate": 1.0,
+        })))
+        .is_err());
+}
+
+#[test]
+fn json_schema_booleans_round_trip() {
+    let schema = json!({
+        "type": "object",
+        "properties": { "active": { "type": "boolean" } },
+        "required": ["active"],
+    });
+    let validator = AS3Validator::from_json_schema(&schema).unwrap();
+    assert_eq!(
+        validator,
+        AS3Validator::object([("active", AS3Validator::any_boolean())])
+    );
+    assert_eq!(
+        validator.to_json_schema()["properties"]["active"],
+        json!({ "type": "boolean" })
+    );
+    assert!(validator.is_valid(&AS3Data::from(&json!({ "active": false }))));
+    assert!(!validator.is_valid(&AS3Data::from(&json!({ "active": "yes" }))));
+}
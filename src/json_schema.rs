@@ -0,0 +1,709 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::{json, Map, Value};
+
+use crate::{AS3Data, AS3SchemaError, AS3Validator, Annotations, StringFormat};
+
+/// JSON Schema keywords [`AS3Validator::from_json_schema`] understands.
+const SUPPORTED: &[&str] = &[
+    "type",
+    "properties",
+    "required",
+    "additionalProperties",
+    "propertyNames",
+    "dependentRequired",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "format",
+    "contentEncoding",
+    "minimum",
+    "maximum",
+    "multipleOf",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "items",
+    "prefixItems",
+    "maxItems",
+    "enum",
+    "const",
+    "oneOf",
+    "anyOf",
+    "allOf",
+    "title",
+    "description",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "examples",
+];
+
+/// Keywords that only document a schema, and are dropped.
+const IGNORED: &[&str] = &["$schema", "$id", "$comment", "default"];
+
+impl AS3Validator {
+    /// Converts a JSON Schema (draft 2020-12) document into a validator.
+    ///
+    /// Types, the common constraints, `enum`, `const`, `oneOf`, `anyOf` and `allOf` carry
+    /// over, and properties left out of `required` become optional fields. Keywords with no
+    /// AS3 counterpart, such as `$ref`, `not` or `minItems`, are rejected instead of being
+    /// dropped, so the validator never accepts more than the original schema.
+    pub fn from_json_schema(schema: &Value) -> Result<AS3Validator, AS3SchemaError> {
+        let validator = convert(schema, &mut vec![])?;
+        match validator.schema_errors().into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(validator),
+        }
+    }
+
+    /// The JSON Schema (draft 2020-12) equivalent of the validator, the reverse of
+    /// [`AS3Validator::from_json_schema`].
+    ///
+    /// Constraints JSON Schema has no keyword for are left out, so the result can accept
+    /// more than the validator: `+WhenPresent`, sorted keys, cross-field rules, string
+    /// case and surrounding whitespace, byte sizes, Unicode normalization, grammars,
+    /// maximum decimal places, integer steps away from a nonzero origin, date and big
+    /// number bounds, the field names of a `Positional` validator, and flattened
+    /// validators other than objects.
+    pub fn to_json_schema(&self) -> Value {
+        let mut schema = describe(self);
+        if let Value::Object(schema) = &mut schema {
+            schema.insert(
+                "$schema".to_string(),
+                json!("https://json-schema.org/draft/2020-12/schema"),
+            );
+        }
+        schema
+    }
+}
+
+fn convert(schema: &Value, path: &mut Vec<String>) -> Result<AS3Validator, AS3SchemaError> {
+    let fail = |message: String, path: &[String]| AS3SchemaError::from(message).at(path);
+    let schema = match schema {
+        Value::Bool(true) => return Ok(AS3Validator::Any),
+        Value::Object(schema) => schema,
+        _ => {
+            return Err(fail(
+                "a schema must be an object or `true`".to_string(),
+                path,
+            ))
+        }
+    };
+    if let Some(keyword) = schema.keys().find(|keyword| {
+        !SUPPORTED.contains(&keyword.as_str()) && !IGNORED.contains(&keyword.as_str())
+    }) {
+        return Err(fail(
+            format!("unsupported JSON Schema keyword `{keyword}`"),
+            path,
+        ));
+    }
+    let combinator = ["oneOf", "anyOf", "allOf"]
+        .into_iter()
+        .find(|keyword| schema.contains_key(*keyword));
+    if let (Some(combinator), true) = (combinator, schema.contains_key("type")) {
+        return Err(fail(
+            format!("`{combinator}` alongside `type` is not supported"),
+            path,
+        ));
+    }
+
+    let validator = if let Some(value) = schema.get("const") {
+        AS3Validator::Const(AS3Data::from(value))
+    } else if let Some(values) = schema.get("enum") {
+        let Value::Array(values) = values else {
+            return Err(fail("`enum` must be a list".to_string(), path));
+        };
+        AS3Validator::Enum(
+            values
+                .iter()
+                .map(|value| AS3Validator::Const(AS3Data::from(value)))
+                .collect(),
+        )
+    } else if let Some(combinator) = combinator {
+        let Some(Value::Array(branches)) = schema.get(combinator) else {
+            return Err(fail(format!("`{combinator}` must be a list"), path));
+        };
+        let mut converted = vec![];
+        for (index, branch) in branches.iter().enumerate() {
+            path.extend([combinator.to_string(), index.to_string()]);
+            converted.push(convert(branch, path)?);
+            path.truncate(path.len() - 2);
+        }
+        match combinator {
+            "oneOf" => AS3Validator::OneOf(converted),
+            "allOf" => AS3Validator::AllOf(converted),
+            _ => AS3Validator::AnyOf(converted),
+        }
+    } else {
+        match schema.get("type") {
+            None => AS3Validator::Any,
+            Some(Value::String(name)) => typed(name, schema, path)?,
+            Some(Value::Array(names)) => {
+                let mut types = vec![];
+                for name in names {
+                    let Value::String(name) = name else {
+                        return Err(fail("`type` must list strings".to_string(), path));
+                    };
+                    types.push(typed(name, schema, path)?);
+                }
+                AS3Validator::Enum(types)
+            }
+            Some(_) => {
+                return Err(fail(
+                    "`type` must be a string or a list of strings".to_string(),
+                    path,
+                ))
+            }
+        }
+    };
+
+    let flag = |keyword: &str| {
+        schema
+            .get(keyword)
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    };
+    let text = |keyword: &str| {
+        schema
+            .get(keyword)
+            .and_then(Value::as_str)
+            .map(String::from)
+    };
+    let examples = match schema.get("examples") {
+        Some(Value::Array(examples)) => examples.iter().map(AS3Data::from).collect(),
+        Some(_) => return Err(fail("`examples` must be a list".to_string(), path)),
+        None => vec![],
+    };
+    Ok(annotate(
+        validator,
+        Annotations {
+            deprecated: flag("deprecated"),
+            read_only: flag("readOnly"),
+            write_only: flag("writeOnly"),
+            description: text("description"),
+            title: text("title"),
+            examples,
+            ..Annotations::default()
+        },
+    ))
+}
+
+/// Converts `schema` as an instance of the single JSON Schema type `name`.
+fn typed(
+    name: &str,
+    schema: &Map<String, Value>,
+    path: &mut Vec<String>,
+) -> Result<AS3Validator, AS3SchemaError> {
+    let fail = |message: String, path: &[String]| AS3SchemaError::from(message).at(path);
+    // Dropping a size the validator cannot use would accept more than the schema does.
+    let size = |keyword: &str, path: &[String]| match schema.get(keyword) {
+        None => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .or_else(|| {
+                value
+                    .as_f64()
+                    .filter(|size| *size >= 0.0 && size.fract() == 0.0)
+                    .map(|size| size as u64)
+            })
+            .map(|size| Some(size as usize))
+            .ok_or_else(|| {
+                fail(
+                    format!("`{keyword}` must be a non-negative integer, not {value}"),
+                    path,
+                )
+            }),
+    };
+    let number = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+    let child = |keyword: &str, path: &mut Vec<String>| match schema.get(keyword) {
+        Some(child) => {
+            path.push(keyword.to_string());
+            let converted = convert(child, path);
+            path.pop();
+            converted.map(Some)
+        }
+        None => Ok(None),
+    };
+
+    let validator = match name {
+        "object" => {
+            let mut properties = HashMap::new();
+            if let Some(declared) = schema.get("properties") {
+                let Value::Object(declared) = declared else {
+                    return Err(fail("`properties` must be an object".to_string(), path));
+                };
+                for (key, property) in declared {
+                    path.extend(["properties".to_string(), key.clone()]);
+                    properties.insert(key.clone(), convert(property, path)?);
+                    path.truncate(path.len() - 2);
+                }
+            }
+            let required: Vec<String> = match schema.get("required") {
+                Some(required) => serde_json::from_value(required.clone())
+                    .map_err(|_| fail("`required` must list keys".to_string(), path))?,
+                None => vec![],
+            };
+            for (key, property) in &mut properties {
+                if !required.contains(key) {
+                    property.make_optional();
+                }
+            }
+            // A required key without a schema of its own may hold anything.
+            for key in required {
+                properties.entry(key).or_insert(AS3Validator::Any);
+            }
+            let property_names = child("propertyNames", path)?;
+            match schema.get("additionalProperties") {
+                Some(Value::Object(_)) if properties.is_empty() => AS3Validator::Map {
+                    key_type: Arc::new(property_names.unwrap_or_else(AS3Validator::any_string)),
+                    value_type: Arc::new(child("additionalProperties", path)?.unwrap()),
+                },
+                additional_properties => AS3Validator::Object {
+                    properties,
+                    property_names: property_names.map(Arc::new),
+                    when_present: None,
+                    dependent_required: match schema.get("dependentRequired") {
+                        Some(dependent_required) => {
+                            serde_json::from_value(dependent_required.clone()).map_err(|_| {
+                                fail(
+                                    "`dependentRequired` must map keys to key lists".to_string(),
+                                    path,
+                                )
+                            })?
+                        }
+                        None => HashMap::new(),
+                    },
+                    additional_properties: additional_properties != Some(&Value::Bool(false)),
+                    additional_properties_schema: match additional_properties {
+                        Some(Value::Object(_)) => {
+                            child("additionalProperties", path)?.map(Arc::new)
+                        }
+                        _ => None,
+                    },
+                    require_sorted_keys: false,
+                    non_empty: false,
+                    rules: vec![],
+                },
+            }
+        }
+        "string" => {
+            // Base64 is an encoding in JSON Schema, but a format here.
+            let format = schema
+                .get("format")
+                .or_else(|| schema.get("contentEncoding"))
+                .and_then(Value::as_str);
+            #[cfg(feature = "chrono")]
+            if format == Some("date") {
+                return Ok(AS3Validator::Date {
+                    after: None,
+                    before: None,
+                });
+            }
+            // RFC 3339 datetimes always carry an offset.
+            #[cfg(feature = "chrono")]
+            if format == Some("date-time") {
+                return Ok(AS3Validator::DateTime {
+                    require_timezone: Some(true),
+                    utc_only: false,
+                });
+            }
+            AS3Validator::String {
+                regex: schema
+                    .get("pattern")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+                min_length: size("minLength", path)?,
+                max_length: size("maxLength", path)?,
+                format: match format {
+                    Some(name) => Some(StringFormat::from_name(name).ok_or_else(|| {
+                        fail(format!("unsupported string format `{name}`"), path)
+                    })?),
+                    None => None,
+                },
+                case: None,
+                no_surrounding_whitespace: false,
+                non_empty: false,
+                min_bytes: None,
+                max_bytes: None,
+                normalize: None,
+                grammar: None,
+            }
+        }
+        "integer" => {
+            // Integer bounds are inclusive, so exclusive ones move one step inwards. When
+            // both kinds are given, the tighter one wins.
+            let inclusive = number("minimum").map(|minimum| minimum.ceil() as i64);
+            let exclusive =
+                number("exclusiveMinimum").map(|exclusive| exclusive.floor() as i64 + 1);
+            let minimum = inclusive.max(exclusive);
+            let inclusive = number("maximum").map(|maximum| maximum.floor() as i64);
+            let exclusive = number("exclusiveMaximum").map(|exclusive| exclusive.ceil() as i64 - 1);
+            let maximum = match (inclusive, exclusive) {
+                (Some(inclusive), Some(exclusive)) => Some(inclusive.min(exclusive)),
+                (inclusive, exclusive) => inclusive.or(exclusive),
+            };
+            let step = match size("multipleOf", path) {
+                Ok(Some(step)) if step > 0 => Some(step as i64),
+                Ok(None) => None,
+                _ => {
+                    return Err(fail(
+                        "`multipleOf` must be a positive integer on integers".to_string(),
+                        path,
+                    ))
+                }
+            };
+            AS3Validator::Integer {
+                minimum,
+                maximum,
+                step,
+                step_origin: None,
+            }
+        }
+        "number" => {
+            if let Some(keyword) = ["exclusiveMinimum", "exclusiveMaximum", "multipleOf"]
+                .into_iter()
+                .find(|keyword| schema.contains_key(*keyword))
+            {
+                return Err(fail(
+                    format!("`{keyword}` is only supported on integers"),
+                    path,
+                ));
+            }
+            AS3Validator::Decimal {
+                minimum: number("minimum"),
+                maximum: number("maximum"),
+                max_decimal_places: None,
+            }
+        }
+        "boolean" => AS3Validator::any_boolean(),
+        "null" => AS3Validator::Const(AS3Data::Null),
+        "array" => match schema.get("prefixItems") {
+            Some(Value::Array(prefix)) => {
+                if schema.contains_key("maxItems") {
+                    return Err(fail(
+                        "`maxItems` alongside `prefixItems` is not supported".to_string(),
+                        path,
+                    ));
+                }
+                let mut items = vec![];
+                for (index, item) in prefix.iter().enumerate() {
+                    path.extend(["prefixItems".to_string(), index.to_string()]);
+                    items.push(convert(item, path)?);
+                    path.truncate(path.len() - 2);
+                }
+                let rest = match schema.get("items") {
+                    Some(Value::Bool(false)) => None,
+                    Some(_) => child("items", path)?,
+                    None => Some(AS3Validator::Any),
+                };
+                AS3Validator::Tuple {
+                    items,
+                    rest: rest.map(Arc::new),
+                }
+            }
+            Some(_) => return Err(fail("`prefixItems` must be a list".to_string(), path)),
+            None => AS3Validator::List {
+                items: Arc::new(child("items", path)?.unwrap_or(AS3Validator::Any)),
+                max_validated: size("maxItems", path)?,
+                non_empty: false,
+            },
+        },
+        name => {
+            return Err(AS3SchemaError::UnknownType {
+                name: name.to_string(),
+            }
+            .at(path))
+        }
+    };
+    Ok(validator)
+}
+
+fn annotate(validator: AS3Validator, annotations: Annotations) -> AS3Validator {
+    if annotations == Annotations::default() {
+        return validator;
+    }
+    AS3Validator::Annotated {
+        validator: Arc::new(validator),
+        annotations,
+    }
+}
+
+fn describe(validator: &AS3Validator) -> Value {
+    let mut schema = Map::new();
+    let mut set = |keyword: &str, value: Value| {
+        if !value.is_null() {
+            schema.insert(keyword.to_string(), value);
+        }
+    };
+    match validator {
+        AS3Validator::Annotated {
+            validator,
+            annotations,
+        } => {
+            let mut schema = describe(validator);
+            if let Value::Object(schema) = &mut schema {
+                for (keyword, set) in [
+                    ("deprecated", annotations.deprecated),
+                    ("readOnly", annotations.read_only),
+                    ("writeOnly", annotations.write_only),
+                ] {
+                    if set {
+                        schema.insert(keyword.to_string(), json!(true));
+                    }
+                }
+                for (keyword, text) in [
+                    ("title", &annotations.title),
+                    ("description", &annotations.description),
+                ] {
+                    if let Some(text) = text {
+                        schema.insert(keyword.to_string(), json!(text));
+                    }
+                }
+                if !annotations.examples.is_empty() {
+                    let examples = annotations.examples.iter().map(Value::from).collect();
+                    schema.insert("examples".to_string(), Value::Array(examples));
+                }
+            }
+            return schema;
+        }
+        AS3Validator::Object {
+            property_names,
+            dependent_required,
+            additional_properties,
+            additional_properties_schema,
+            non_empty,
+            ..
+        } => {
+            let mut properties = Map::new();
+            let mut required = vec![];
+            collect_properties(validator, &mut properties, &mut required);
+            required.sort();
+            set("type", json!("object"));
+            set("properties", Value::Object(properties));
+            if !required.is_empty() {
+                set("required", json!(required));
+            }
+            if let Some(property_names) = property_names {
+                set("propertyNames", describe(property_names));
+            }
+            if !dependent_required.is_empty() {
+                set("dependentRequired", json!(dependent_required));
+            }
+            if !additional_properties {
+                set("additionalProperties", json!(false));
+            } else if let Some(schema) = additional_properties_schema {
+                set("additionalProperties", describe(schema));
+            }
+            if *non_empty {
+                set("minProperties", json!(1));
+            }
+        }
+        AS3Validator::String {
+            regex,
+            min_length,
+            max_length,
+            format,
+            non_empty,
+            ..
+        } => {
+            let min_length = match (min_length, non_empty) {
+                (min_length, true) => Some(min_length.unwrap_or(0).max(1)),
+                (min_length, false) => *min_length,
+            };
+            set("type", json!("string"));
+            set("pattern", json!(regex));
+            set("minLength", json!(min_length));
+            set("maxLength", json!(max_length));
+            match format.map(|format| format.name()) {
+                Some("base64") => set("contentEncoding", json!("base64")),
+                name => set("format", json!(name)),
+            }
+        }
+        AS3Validator::Integer {
+            minimum,
+            maximum,
+            step,
+            step_origin,
+        } => {
+            set("type", json!("integer"));
+            set("minimum", json!(minimum));
+            set("maximum", json!(maximum));
+            if step_origin.unwrap_or(0) == 0 {
+                set("multipleOf", json!(step));
+            }
+        }
+        AS3Validator::Decimal {
+            minimum, maximum, ..
+        } => {
+            set("type", json!("number"));
+            set("minimum", json!(minimum));
+            set("maximum", json!(maximum));
+        }
+        AS3Validator::NumericString { format_mask } => {
+            set("type", json!("string"));
+            let pattern = match format_mask {
+                Some(mask) => mask
+                    .chars()
+                    .map(|slot| match slot {
+                        '0' => "[0-9]".to_string(),
+                        literal => regex::escape(&literal.to_string()),
+                    })
+                    .collect(),
+                None => "[0-9]+".to_string(),
+            };
+            set("pattern", json!(format!("^{pattern}$")));
+        }
+        AS3Validator::List {
+            items,
+            max_validated,
+            non_empty,
+        } => {
+            set("type", json!("array"));
+            set("items", describe(items));
+            set("maxItems", json!(max_validated));
+            if *non_empty {
+                set("minItems", json!(1));
+            }
+        }
+        AS3Validator::Tuple { items, rest } => {
+            set("type", json!("array"));
+            set(
+                "prefixItems",
+                Value::Array(items.iter().map(describe).collect()),
+            );
+            set(
+                "items",
+                match rest {
+                    Some(rest) => describe(rest),
+                    None => json!(false),
+                },
+            );
+        }
+        AS3Validator::Positional { fields } => {
+            set("type", json!("array"));
+            set(
+                "prefixItems",
+                fields
+                    .iter()
+                    .map(|(_, validator)| describe(validator))
+                    .collect(),
+            );
+            set("items", json!(false));
+        }
+        AS3Validator::Map {
+            key_type,
+            value_type,
+        } => {
+            set("type", json!("object"));
+            set("propertyNames", describe(key_type));
+            set("additionalProperties", describe(value_type));
+        }
+        AS3Validator::Flatten(validator) => return describe(validator),
+        #[cfg(feature = "chrono")]
+        AS3Validator::Date { .. } => {
+            set("type", json!("string"));
+            set("format", json!("date"));
+        }
+        #[cfg(feature = "chrono")]
+        AS3Validator::DateTime {
+            require_timezone,
+            utc_only,
+        } => {
+            set("type", json!("string"));
+            if *require_timezone == Some(true) || *utc_only {
+                set("format", json!("date-time"));
+            }
+            if *utc_only {
+                set("pattern", json!("[Zz]$"));
+            }
+        }
+        #[cfg(feature = "bigdecimal")]
+        AS3Validator::BigNumber { .. } => set("type", json!(["string", "number"])),
+        AS3Validator::Const(value) => set("const", Value::from(value)),
+        AS3Validator::Enum(_) if *validator == AS3Validator::any_boolean() => {
+            set("type", json!("boolean"))
+        }
+        AS3Validator::Enum(entries) => {
+            let values: Option<Vec<Value>> = entries
+                .iter()
+                .map(|entry| match entry {
+                    AS3Validator::Const(value) => Some(Value::from(value)),
+                    _ => None,
+                })
+                .collect();
+            match values {
+                Some(values) => set("enum", Value::Array(values)),
+                None => set("anyOf", entries.iter().map(describe).collect()),
+            }
+        }
+        AS3Validator::OneOf(branches) => set("oneOf", branches.iter().map(describe).collect()),
+        AS3Validator::AnyOf(branches) => set("anyOf", branches.iter().map(describe).collect()),
+        AS3Validator::AllOf(validators) => set("allOf", validators.iter().map(describe).collect()),
+        AS3Validator::Ref(url) => set("$ref", json!(url)),
+        AS3Validator::Discriminated { key, mapping } => {
+            let mut tags: Vec<_> = mapping.keys().collect();
+            tags.sort();
+            let variants = tags
+                .into_iter()
+                .map(|tag| tagged(describe(&mapping[tag]), key, tag))
+                .collect();
+            set("oneOf", variants);
+        }
+        AS3Validator::Any => {}
+    }
+    Value::Object(schema)
+}
+
+/// The properties of an object, including those of the objects it flattens.
+fn collect_properties(
+    validator: &AS3Validator,
+    properties: &mut Map<String, Value>,
+    required: &mut Vec<String>,
+) {
+    let AS3Validator::Object {
+        properties: declared,
+        ..
+    } = validator.unannotated()
+    else {
+        return;
+    };
+    for (key, property) in declared {
+        if let AS3Validator::Flatten(flattened) = property {
+            collect_properties(flattened, properties, required);
+            continue;
+        }
+        if !property
+            .annotations()
+            .is_some_and(|annotations| annotations.optional)
+        {
+            required.push(key.clone());
+        }
+        properties.insert(key.clone(), describe(property));
+    }
+}
+
+/// Requires the discriminator `key` of a variant's schema to hold `tag`.
+fn tagged(mut variant: Value, key: &str, tag: &str) -> Value {
+    let tag_schema = json!({ "const": tag });
+    if let Some(Value::Object(properties)) = variant.get_mut("properties") {
+        properties.insert(key.to_string(), tag_schema);
+        let required = variant
+            .as_object_mut()
+            .unwrap()
+            .entry("required")
+            .or_insert_with(|| json!([]));
+        if let Value::Array(required) = required {
+            required.push(json!(key));
+            required.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+            required.dedup();
+        }
+        return variant;
+    }
+    json!({
+        "allOf": [
+            variant,
+            { "type": "object", "properties": { key: tag_schema }, "required": [key] },
+        ]
+    })
+}
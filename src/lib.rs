@@ -0,0 +1,3190 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::io::BufRead;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+// Lets the code generated by `#[derive(AS3Schema)]` name this crate from inside it too.
+#[cfg(feature = "derive")]
+extern crate self as appcovecompiler;
+
+mod cache;
+mod compat;
+#[cfg(feature = "chrono")]
+mod dates;
+mod form;
+mod include;
+mod json_schema;
+mod lint;
+mod macros;
+mod mask;
+mod messages;
+#[cfg(feature = "rmp-serde")]
+mod msgpack;
+#[cfg(feature = "bigdecimal")]
+mod numbers;
+mod openapi;
+mod ops;
+mod options;
+mod pointer;
+mod problem;
+#[cfg(feature = "prost")]
+mod protobuf;
+#[cfg(feature = "async")]
+mod refs;
+#[cfg(feature = "ureq")]
+mod remote;
+#[cfg(feature = "sample")]
+mod sample;
+mod schema;
+mod strings;
+mod tree;
+
+#[cfg(feature = "derive")]
+pub use appcovecompiler_derive::AS3Schema;
+pub use cache::CachedValidator;
+pub use compat::Incompatibility;
+pub use lint::LintWarning;
+pub use mask::Mask;
+#[cfg(feature = "rmp-serde")]
+pub use msgpack::BinaryPolicy;
+use options::Validation;
+pub use options::{
+    ConversionOptions, Direction, Grammar, ValidationOptions, ValidationReport, ValidationStats,
+};
+pub use problem::to_problem_json;
+#[cfg(feature = "ureq")]
+pub use remote::{MemorySchemaCache, SchemaCache, SchemaFetchError};
+pub use schema::{__derive, AS3Schema};
+pub use strings::{NormalizationForm, StringCase, StringFormat};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum AS3Data {
+    Object(IndexMap<String, Box<AS3Data>>),
+    String(String),
+    Map {
+        #[serde(rename = "KeyType")]
+        key_type: Box<AS3Data>,
+        #[serde(rename = "ValueType")]
+        value_type: Box<AS3Data>,
+    },
+    Boolean(bool),
+    Integer(i64),
+    Decimal(f64),
+    List(Vec<AS3Data>),
+    Null,
+}
+
+/// Nested validators are held in [`Arc`]s, so cloning a validator shares its subtrees
+/// instead of copying them, and a validator can be handed to other threads cheaply.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum AS3Validator {
+    #[serde(rename = "+Object")]
+    Object {
+        properties: HashMap<String, AS3Validator>,
+        /// Validator every key of the object must satisfy, regardless of its value.
+        property_names: Option<Arc<AS3Validator>>,
+        /// When set, the object is only checked if the data contains this key.
+        when_present: Option<String>,
+        /// Keys that become required once the key they are listed under is present.
+        #[serde(default)]
+        dependent_required: HashMap<String, Vec<String>>,
+        /// Whether keys the object does not declare are allowed, as they are by default.
+        /// When `false` they are rejected with [`AS3ValidationError::UnknownKey`]. Only the
+        /// object the data is checked against decides: the setting of an object flattened
+        /// into it is ignored, and a discriminated variant must declare its tag key.
+        #[serde(default = "allowed")]
+        additional_properties: bool,
+        /// Validator the values under undeclared keys must satisfy while they are allowed,
+        /// such as a `Ref` to `#` for a config tree whose every node has the same shape.
+        /// Only the object the data is checked against applies it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        additional_properties_schema: Option<Arc<AS3Validator>>,
+        /// Reject data whose keys are not in lexicographic order, as a house style for
+        /// config files. Like `additional_properties`, only the outer object decides.
+        #[serde(default)]
+        require_sorted_keys: bool,
+        /// Reject an object without any keys with [`AS3ValidationError::EmptyCollection`].
+        /// Patches may still be empty, as they leave the object unchanged.
+        #[serde(default)]
+        non_empty: bool,
+        /// Value-based constraints between keys, checked after the properties.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        rules: Vec<CrossFieldRule>,
+    },
+    #[serde(rename = "+String")]
+    String {
+        regex: Option<String>,
+        /// Bounds on the number of characters (not bytes).
+        min_length: Option<usize>,
+        max_length: Option<usize>,
+        format: Option<StringFormat>,
+        case: Option<StringCase>,
+        /// Reject values with leading or trailing whitespace, as for identifiers.
+        #[serde(default)]
+        no_surrounding_whitespace: bool,
+        /// Reject the empty string; a shortcut for a `min_length` of 1.
+        #[serde(default)]
+        non_empty: bool,
+        /// Bounds on the size of the value in bytes, e.g. for a database column: the
+        /// UTF-8 length, or the decoded size of a [`StringFormat::Base64`] value.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_bytes: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_bytes: Option<usize>,
+        /// Normalize the value to this form before any other check.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        normalize: Option<NormalizationForm>,
+        /// Name of a parser registered with [`ValidationOptions::grammar`] that must accept
+        /// the whole value, for structured strings a regex cannot describe.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        grammar: Option<String>,
+    },
+    /// An integer within the inclusive `minimum` and `maximum` bounds.
+    #[serde(rename = "+Integer")]
+    Integer {
+        minimum: Option<i64>,
+        maximum: Option<i64>,
+        /// Only every `step`-th integer is accepted, counting from `step_origin` (0 when
+        /// unset): a slider of 0, 25, ..., 100 has a step of 25, and 5, 15, 25 a step of
+        /// 10 from 5.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        step: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        step_origin: Option<i64>,
+    },
+    #[serde(rename = "+Decimal")]
+    Decimal {
+        minimum: Option<f64>,
+        maximum: Option<f64>,
+        /// Most digits allowed after the decimal point, e.g. `2` for currency amounts.
+        max_decimal_places: Option<u32>,
+    },
+    /// A number kept as a string of ASCII digits, such as an account number. A
+    /// `format_mask` like `"000000"` fixes its width, with leading zeros: each `0` in the
+    /// mask stands for one digit, and any other character must appear as is.
+    #[serde(rename = "+NumericString")]
+    NumericString {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        format_mask: Option<String>,
+    },
+    #[serde(rename = "+List")]
+    List {
+        items: Arc<AS3Validator>,
+        /// Caps both the length of the list and how many elements are validated, so
+        /// oversized inputs are rejected without walking every element.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_validated: Option<usize>,
+        /// Reject a list without elements with [`AS3ValidationError::EmptyCollection`].
+        #[serde(default)]
+        non_empty: bool,
+    },
+    /// A list whose first elements match `items` position by position. Elements past
+    /// those are checked against `rest`, or rejected when there is no `rest`.
+    #[serde(rename = "+Tuple")]
+    Tuple {
+        items: Vec<AS3Validator>,
+        rest: Option<Arc<AS3Validator>>,
+    },
+    /// A list read as an object with a fixed field order, as CSV-like legacy feeds send
+    /// records: element `i` is the value of the `i`-th of `fields`, and errors are located
+    /// by field name. The list must have exactly one element per field.
+    #[serde(rename = "+Positional")]
+    Positional { fields: Vec<(String, AS3Validator)> },
+    /// An object with arbitrary keys, where every key matches `key_type` and every
+    /// value matches `value_type`. Any `AS3Data::Object` is accepted as long as all of its
+    /// entries are; an Object validator with `additional_properties` off is the way to
+    /// reject keys instead.
+    #[serde(rename = "+Map")]
+    Map {
+        key_type: Arc<AS3Validator>,
+        value_type: Arc<AS3Validator>,
+    },
+    /// Placed among an Object's properties, validates the enclosing object itself
+    /// rather than the value under its key, like `#[serde(flatten)]`.
+    #[serde(rename = "+Flatten")]
+    Flatten(Arc<AS3Validator>),
+    /// A validator carrying extra, non-structural information about its field.
+    #[serde(rename = "+Annotated")]
+    Annotated {
+        validator: Arc<AS3Validator>,
+        annotations: Annotations,
+    },
+    /// An ISO-8601 calendar date (`YYYY-MM-DD`), optionally bounded by the inclusive
+    /// `after` and `before` dates.
+    #[cfg(feature = "chrono")]
+    #[serde(rename = "+Date")]
+    Date {
+        after: Option<String>,
+        before: Option<String>,
+    },
+    /// An ISO-8601 date and time (`YYYY-MM-DDTHH:MM:SS`, with optional fractional seconds),
+    /// followed by a `Z` or `±HH:MM` offset unless it is a local time.
+    #[cfg(feature = "chrono")]
+    #[serde(rename = "+DateTime")]
+    DateTime {
+        /// `Some(true)` requires an offset and `Some(false)` forbids one; by default
+        /// both local and offset times are accepted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        require_timezone: Option<bool>,
+        /// Only accept times in UTC, written with the `Z` suffix.
+        #[serde(default)]
+        utc_only: bool,
+    },
+    /// A number compared with arbitrary precision against the inclusive `minimum` and
+    /// `maximum` bounds. Values beyond `f64` precision should be sent as decimal strings,
+    /// which this validator accepts alongside Integer and Decimal data.
+    #[cfg(feature = "bigdecimal")]
+    #[serde(rename = "+BigNumber")]
+    BigNumber {
+        minimum: Option<String>,
+        maximum: Option<String>,
+    },
+    /// Exactly this value, compared by equality.
+    #[serde(rename = "+Const")]
+    Const(AS3Data),
+    /// Passes when any of the entries accepts the data. Entries are usually `Const`
+    /// values, mixed with validators for ranges such as "any 5xx status code".
+    #[serde(rename = "+Enum")]
+    Enum(Vec<AS3Validator>),
+    /// Passes when exactly one of the branches accepts the data.
+    #[serde(rename = "+OneOf")]
+    OneOf(Vec<AS3Validator>),
+    /// Passes when at least one of the branches accepts the data. Unlike an `Enum`, a
+    /// rejection reports the errors of the closest branch, as `OneOf` does.
+    #[serde(rename = "+AnyOf")]
+    AnyOf(Vec<AS3Validator>),
+    /// Passes when every validator accepts the data, reporting the errors of each. As in
+    /// JSON Schema, an Object with `additional_properties` off still rejects the keys that
+    /// only the other validators declare.
+    #[serde(rename = "+AllOf")]
+    AllOf(Vec<AS3Validator>),
+    /// A tagged union: the String at `key` in the data object picks the validator in
+    /// `mapping` that the whole object must then satisfy.
+    #[serde(rename = "+Discriminated")]
+    Discriminated {
+        key: String,
+        mapping: HashMap<String, AS3Validator>,
+    },
+    /// The schema at a URL, fetched when data is validated. Only
+    /// [`AS3Validator::validate_async`] fetches it; the other entry points report
+    /// [`AS3ValidationError::RefUnavailable`] for any data that reaches it.
+    ///
+    /// The URL `#` instead refers to the validator the data is being checked against,
+    /// for trees of any depth such as a `Map` whose values are `#` again. Following it
+    /// counts towards `max_depth`.
+    #[serde(rename = "+Ref")]
+    Ref(String),
+    /// Accepts any data, including `Null`.
+    #[serde(rename = "+Any")]
+    Any,
+}
+
+/// Metadata attached to a validator through [`AS3Validator::Annotated`].
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Annotations {
+    /// The field still validates, but its presence is reported as a warning.
+    pub deprecated: bool,
+    /// The key may be left out of its object, or hold `null`.
+    pub optional: bool,
+    /// Set by the server: must be absent when validating a [`Direction::Request`].
+    pub read_only: bool,
+    /// Accepted from clients only: must be absent when validating a [`Direction::Response`].
+    pub write_only: bool,
+    /// Human-readable documentation for the field, e.g. for generated API docs.
+    pub description: Option<String>,
+    /// Short human-readable label for the field, e.g. for generated forms.
+    pub title: Option<String>,
+    /// Sample values for documentation, which [`AS3Validator::check_examples`] checks
+    /// against the validator they annotate.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<AS3Data>,
+    /// How [`AS3Validator::mask`] redacts the field's value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mask: Option<Mask>,
+}
+
+/// A constraint between two keys of an [`AS3Validator::Object`]: when the value under the
+/// `when` key equals the given value, the `then` key must be present and its value must
+/// satisfy the given validator, as in "if `country` is `US`, `zip` matches `^\d{5}$`".
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CrossFieldRule {
+    pub when: (String, AS3Data),
+    pub then: (String, AS3Validator),
+}
+
+impl CrossFieldRule {
+    /// Checks the rule against an object's keys. This lives outside
+    /// [`AS3Validator::collect_errors`] so the nested [`Validation`] does not grow the
+    /// stack frame of every level of recursion.
+    fn collect_errors(&self, data: &IndexMap<String, Box<AS3Data>>, validation: &mut Validation) {
+        let CrossFieldRule {
+            when: (when_key, when_value),
+            then: (then_key, then),
+        } = self;
+        if data.get(when_key).map(Box::as_ref) != Some(when_value) {
+            return;
+        }
+        let errors = match data.get(then_key) {
+            Some(value) => {
+                let mut checked = validation.nested();
+                then.collect_errors(value, &mut checked);
+                for warning in checked.report.warnings {
+                    validation.warn(warning);
+                }
+                checked.report.errors
+            }
+            None if validation.options.patch => vec![],
+            None => vec![AS3ValidationError::MissingKey {
+                key: then_key.clone(),
+            }],
+        };
+        validation.enter(then_key);
+        for error in errors {
+            validation.push(AS3ValidationError::RuleViolation {
+                when_key: when_key.clone(),
+                when_value: when_value
+                    .preview()
+                    .unwrap_or_else(|| when_value.describe()),
+                then_key: then_key.clone(),
+                error: Box::new(error),
+            });
+        }
+        validation.leave();
+    }
+}
+
+impl Annotations {
+    /// Reads the annotation directives of a validator. Malformed `+Examples` are recorded
+    /// in `build` at their own path, as they do not stop the validator from being built.
+    fn from_yaml(
+        yaml_config: &serde_yaml::Value,
+        build: &mut SchemaBuild,
+    ) -> Result<Annotations, String> {
+        let flag = |directive: &str| {
+            yaml_config
+                .get(directive)
+                .and_then(|flag| flag.as_bool())
+                .unwrap_or(false)
+        };
+        let mask = match yaml_config.get("+Mask").and_then(|mask| mask.as_str()) {
+            Some(name) => {
+                Some(Mask::from_name(name).ok_or_else(|| format!("unsupported mask `{name}`"))?)
+            }
+            None => None,
+        };
+        Ok(Annotations {
+            deprecated: flag("+Deprecated"),
+            optional: flag("+Optional"),
+            read_only: flag("+ReadOnly"),
+            write_only: flag("+WriteOnly"),
+            description: yaml_config
+                .get("+Description")
+                .and_then(|description| description.as_str())
+                .map(|description| description.to_string()),
+            title: yaml_config
+                .get("+Title")
+                .and_then(|title| title.as_str())
+                .map(|title| title.to_string()),
+            examples: build.examples(yaml_config.get("+Examples")),
+            mask,
+        })
+    }
+
+    /// Whether the field must not appear in data flowing in `direction`.
+    fn excluded_in(&self, direction: Option<Direction>) -> bool {
+        match direction {
+            Some(Direction::Request) => self.read_only,
+            Some(Direction::Response) => self.write_only,
+            None => false,
+        }
+    }
+}
+
+/// Data that has been checked against an [`AS3Validator`].
+///
+/// The only way to obtain one is through [`AS3Validator::parse`], so holding a
+/// `ValidatedData` is proof that the wrapped data satisfied the validator.
+///
+/// ```compile_fail
+/// use appcovecompiler::{AS3Data, ValidatedData};
+///
+/// let forged = ValidatedData(AS3Data::Integer(1));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValidatedData(AS3Data);
+
+impl ValidatedData {
+    pub fn as_data(&self) -> &AS3Data {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> AS3Data {
+        self.0
+    }
+}
+
+impl AsRef<AS3Data> for ValidatedData {
+    fn as_ref(&self) -> &AS3Data {
+        &self.0
+    }
+}
+
+/// Accepts anything, like [`AS3Validator::Any`].
+impl Default for AS3Validator {
+    fn default() -> Self {
+        AS3Validator::Any
+    }
+}
+
+impl AS3Validator {
+    /// A String validator without constraints.
+    pub fn any_string() -> AS3Validator {
+        AS3Validator::String {
+            regex: None,
+            min_length: None,
+            max_length: None,
+            format: None,
+            case: None,
+            no_surrounding_whitespace: false,
+            non_empty: false,
+            min_bytes: None,
+            max_bytes: None,
+            normalize: None,
+            grammar: None,
+        }
+    }
+
+    /// An Integer validator without bounds.
+    pub fn any_integer() -> AS3Validator {
+        AS3Validator::Integer {
+            minimum: None,
+            maximum: None,
+            step: None,
+            step_origin: None,
+        }
+    }
+
+    /// A Decimal validator without bounds.
+    pub fn any_decimal() -> AS3Validator {
+        AS3Validator::Decimal {
+            minimum: None,
+            maximum: None,
+            max_decimal_places: None,
+        }
+    }
+
+    /// Either boolean, as an `Enum` of the two `Const` values, since booleans have no
+    /// validator of their own.
+    pub fn any_boolean() -> AS3Validator {
+        AS3Validator::Enum(vec![
+            AS3Validator::Const(AS3Data::Boolean(true)),
+            AS3Validator::Const(AS3Data::Boolean(false)),
+        ])
+    }
+
+    /// Lets the key of this validator's field be absent, as `+Optional` does.
+    fn make_optional(&mut self) {
+        match self {
+            AS3Validator::Annotated { annotations, .. } => annotations.optional = true,
+            validator => {
+                *validator = AS3Validator::Annotated {
+                    validator: Arc::new(validator.clone()),
+                    annotations: Annotations {
+                        optional: true,
+                        ..Annotations::default()
+                    },
+                }
+            }
+        }
+    }
+
+    /// A List whose elements must all match `items`, of any length.
+    pub fn list_of(items: AS3Validator) -> AS3Validator {
+        AS3Validator::List {
+            items: Arc::new(items),
+            max_validated: None,
+            non_empty: false,
+        }
+    }
+
+    /// An Object requiring each of `properties` and allowing other keys.
+    pub fn object<K: Into<String>>(
+        properties: impl IntoIterator<Item = (K, AS3Validator)>,
+    ) -> AS3Validator {
+        AS3Validator::Object {
+            properties: properties
+                .into_iter()
+                .map(|(key, validator)| (key.into(), validator))
+                .collect(),
+            property_names: None,
+            when_present: None,
+            dependent_required: HashMap::new(),
+            additional_properties: true,
+            additional_properties_schema: None,
+            require_sorted_keys: false,
+            non_empty: false,
+            rules: vec![],
+        }
+    }
+
+    pub fn validate(&self, data: &AS3Data) -> Result<(), AS3ValidationError> {
+        match self.validate_all(data) {
+            Ok(()) => Ok(()),
+            Err(errors) => Err(errors.into_iter().next().unwrap().without_path()),
+        }
+    }
+
+    /// Whether [`AS3Validator::validate`] would succeed, for checks that ignore the details.
+    pub fn is_valid(&self, data: &AS3Data) -> bool {
+        self.validate(data).is_ok()
+    }
+
+    /// Validates a partial update: fields absent from `data` are not reported as missing,
+    /// but every field that is present is fully checked.
+    pub fn validate_patch(&self, data: &AS3Data) -> Result<(), AS3ValidationError> {
+        let options = ValidationOptions {
+            patch: true,
+            ..ValidationOptions::default()
+        };
+        match self
+            .validate_with_options(data, &options)
+            .errors
+            .into_iter()
+            .next()
+        {
+            None => Ok(()),
+            Some(error) => Err(error.without_path()),
+        }
+    }
+
+    /// Validates `data` and reports every violation instead of stopping at the first one.
+    ///
+    /// Errors below the root are wrapped in [`AS3ValidationError::AtPath`].
+    pub fn validate_all(&self, data: &AS3Data) -> Result<(), Vec<AS3ValidationError>> {
+        let report = self.validate_with_options(data, &ValidationOptions::default());
+        if report.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(report.errors)
+        }
+    }
+
+    /// Validates `data` and splits the outcome by top-level key, e.g. to show which
+    /// sections of a config passed.
+    ///
+    /// Every key the Object validator declares (flattened ones included) gets an entry,
+    /// holding the errors inside it or about it, such as it being missing. Errors about
+    /// the document as a whole, like a wrong type at the root, go under the empty key.
+    pub fn validate_sections(
+        &self,
+        data: &AS3Data,
+    ) -> HashMap<String, Result<(), Vec<AS3ValidationError>>> {
+        fn declared(validator: &AS3Validator, keys: &mut Vec<String>) {
+            if let AS3Validator::Object { properties, .. } = validator.unannotated() {
+                for (key, property) in properties {
+                    match property {
+                        AS3Validator::Flatten(flattened) => declared(flattened, keys),
+                        _ => keys.push(key.clone()),
+                    }
+                }
+            }
+        }
+        let mut keys = vec![];
+        declared(self, &mut keys);
+        let mut sections: HashMap<_, Result<(), Vec<AS3ValidationError>>> =
+            keys.into_iter().map(|key| (key, Ok(()))).collect();
+        for error in self.validate_all(data).err().unwrap_or_default() {
+            let section = match &error {
+                AS3ValidationError::AtPath { path, .. } => path[0].clone(),
+                AS3ValidationError::MissingKey { key }
+                | AS3ValidationError::UnknownKey { key }
+                | AS3ValidationError::UnsortedKey { key, .. }
+                | AS3ValidationError::PropertyName { key, .. } => key.clone(),
+                AS3ValidationError::DependencyMissing { required, .. } => required.clone(),
+                _ => String::new(),
+            };
+            let result = sections.entry(section).or_insert(Ok(()));
+            match result {
+                Ok(()) => *result = Err(vec![error]),
+                Err(errors) => errors.push(error),
+            }
+        }
+        sections
+    }
+
+    /// Validates `data` under `options`, separating hard errors from warnings.
+    pub fn validate_with_options(
+        &self,
+        data: &AS3Data,
+        options: &ValidationOptions,
+    ) -> ValidationReport {
+        let mut validation = Validation::new(options);
+        validation.root = Some(self);
+        self.collect_errors(data, &mut validation);
+        validation.finish()
+    }
+
+    /// Like [`AS3Validator::validate`], also counting the data nodes visited and the regexes
+    /// evaluated along the way.
+    pub fn validate_with_stats(
+        &self,
+        data: &AS3Data,
+    ) -> (Result<(), AS3ValidationError>, ValidationStats) {
+        let options = ValidationOptions::default();
+        let mut validation = Validation::new(&options);
+        validation.root = Some(self);
+        self.collect_errors(data, &mut validation);
+        let result = match validation.report.errors.into_iter().next() {
+            Some(error) => Err(error.without_path()),
+            None => Ok(()),
+        };
+        (result, validation.stats)
+    }
+
+    /// Like [`AS3Validator::validate_all`], but a root `List` validator checks its elements
+    /// across threads. Errors are returned in element order.
+    #[cfg(feature = "rayon")]
+    pub fn validate_parallel(&self, data: &AS3Data) -> Result<(), Vec<AS3ValidationError>> {
+        use rayon::prelude::*;
+
+        let (
+            AS3Validator::List {
+                items: items_type,
+                max_validated,
+                ..
+            },
+            AS3Data::List(items),
+        ) = (self, data)
+        else {
+            return self.validate_all(data);
+        };
+        if items.is_empty() || max_validated.is_some_and(|max| items.len() > max) {
+            return self.validate_all(data);
+        }
+        let errors: Vec<AS3ValidationError> = items
+            .par_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let options = ValidationOptions::default();
+                let mut validation = Validation::new(&options);
+                validation.root = Some(self);
+                validation.enter(&index.to_string());
+                items_type.collect_errors(item, &mut validation);
+                validation.report.errors
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flatten()
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validates `data` and, on success, hands it back wrapped in a [`ValidatedData`].
+    pub fn parse(&self, data: AS3Data) -> Result<ValidatedData, Vec<AS3ValidationError>> {
+        self.validate_all(&data)?;
+        Ok(ValidatedData(data))
+    }
+
+    fn collect_errors(&self, data: &AS3Data, validation: &mut Validation) {
+        if validation.too_deep() {
+            validation.push(AS3ValidationError::MaxDepthExceeded {
+                depth: validation.options.max_depth,
+            });
+            return;
+        }
+        if validation.out_of_time() {
+            return;
+        }
+        if let AS3Data::String(string) = data {
+            match self {
+                AS3Validator::Integer { .. }
+                    if validation.options.integer_strings
+                        && strings::is_integer_literal(string) =>
+                {
+                    return self.collect_integer_string(string, validation);
+                }
+                AS3Validator::Decimal { .. }
+                    if validation.options.decimal_strings
+                        && strings::is_decimal_literal(string) =>
+                {
+                    return self.collect_decimal_string(string, validation);
+                }
+                _ => {}
+            }
+        }
+        // These hand the same data on to the validator they wrap, which counts it.
+        if !matches!(
+            self,
+            AS3Validator::Flatten(_) | AS3Validator::Annotated { .. }
+        ) {
+            validation.stats.visit(data);
+            validation.audit(self, data);
+        }
+        match (self, data) {
+            (
+                AS3Validator::Object {
+                    properties,
+                    property_names,
+                    when_present,
+                    dependent_required,
+                    additional_properties,
+                    additional_properties_schema,
+                    require_sorted_keys,
+                    non_empty,
+                    rules,
+                },
+                AS3Data::Object(data_inner),
+            ) => {
+                let flattened = std::mem::take(&mut validation.flattening);
+                if let Some(when_present) = when_present {
+                    if !data_inner.contains_key(when_present) {
+                        return;
+                    }
+                }
+                if !validation.options.patch {
+                    for (trigger, dependents) in dependent_required {
+                        if !data_inner.contains_key(trigger) {
+                            continue;
+                        }
+                        for dependent in dependents {
+                            if !data_inner.contains_key(dependent) {
+                                validation.push(AS3ValidationError::DependencyMissing {
+                                    trigger: trigger.clone(),
+                                    required: dependent.clone(),
+                                });
+                            }
+                        }
+                    }
+                }
+                if !additional_properties && !flattened {
+                    let mut unknown: Vec<_> = data_inner
+                        .keys()
+                        .filter(|key| self.property(key).is_none())
+                        .collect();
+                    unknown.sort();
+                    for key in unknown {
+                        validation.push(AS3ValidationError::UnknownKey { key: key.clone() });
+                    }
+                }
+                if let Some(schema) = additional_properties_schema
+                    .as_ref()
+                    .filter(|_| *additional_properties && !flattened)
+                {
+                    for (key, value) in data_inner {
+                        if self.property(key).is_none() {
+                            validation.enter(key);
+                            schema.collect_errors(value, validation);
+                            validation.leave();
+                        }
+                    }
+                }
+                if *non_empty && data_inner.is_empty() && !validation.options.patch {
+                    validation.push(AS3ValidationError::EmptyCollection {
+                        collection: "object",
+                    });
+                }
+                if *require_sorted_keys && !flattened {
+                    let keys = data_inner.keys();
+                    if let Some((previous, key)) = keys
+                        .clone()
+                        .zip(keys.skip(1))
+                        .find(|(previous, key)| previous > key)
+                    {
+                        validation.push(AS3ValidationError::UnsortedKey {
+                            key: key.clone(),
+                            previous: previous.clone(),
+                        });
+                    }
+                }
+                if let Some(property_names) = property_names {
+                    for key in data_inner.keys() {
+                        property_names.collect_key_errors(key, validation);
+                    }
+                }
+                for (validator_key, validator_value) in properties {
+                    if let AS3Validator::Flatten(flattened) = validator_value {
+                        validation.flattening = true;
+                        flattened.collect_errors(data, validation);
+                        validation.flattening = false;
+                        continue;
+                    }
+                    let optional = validator_value
+                        .annotations()
+                        .is_some_and(|annotations| annotations.optional);
+                    match data_inner.get(validator_key) {
+                        Some(value) if optional && validation.treats_as_missing(value) => {}
+                        Some(value_from_key) => {
+                            validation.enter(validator_key);
+                            validator_value.collect_errors(value_from_key, validation);
+                            validation.leave();
+                        }
+                        None if validation.options.patch || optional => {}
+                        None if validator_value.annotations().is_some_and(|annotations| {
+                            annotations.excluded_in(validation.options.direction)
+                        }) => {}
+                        None => validation.push(AS3ValidationError::MissingKey {
+                            key: validator_key.clone(),
+                        }),
+                    }
+                }
+                for rule in rules {
+                    rule.collect_errors(data_inner, validation);
+                }
+            }
+            (AS3Validator::Integer { .. }, AS3Data::Integer(number)) => {
+                self.collect_integer_errors(*number, validation)
+            }
+            (
+                AS3Validator::Decimal {
+                    minimum,
+                    maximum,
+                    max_decimal_places,
+                },
+                AS3Data::Decimal(number),
+            ) => {
+                if let Some(minimum) = minimum {
+                    if minimum > number {
+                        validation.push(AS3ValidationError::Minimum {
+                            number: *number,
+                            minimum: *minimum,
+                        })
+                    }
+                }
+                if let Some(maximum) = maximum {
+                    if maximum < number {
+                        validation.push(AS3ValidationError::Maximum {
+                            number: *number,
+                            maximum: *maximum,
+                        })
+                    }
+                }
+                if let Some(max) = max_decimal_places {
+                    if decimal_places(*number) > *max {
+                        validation.push(AS3ValidationError::TooManyDecimals {
+                            value: *number,
+                            max: *max,
+                        })
+                    }
+                }
+            }
+            (AS3Validator::String { .. }, AS3Data::String(string)) => {
+                self.collect_string_errors(string, validation)
+            }
+            (AS3Validator::NumericString { format_mask }, AS3Data::String(string)) => {
+                match format_mask {
+                    Some(mask) if !strings::matches_mask(string, mask) => {
+                        validation.push(AS3ValidationError::MaskMismatch {
+                            value: string.clone(),
+                            mask: mask.clone(),
+                        })
+                    }
+                    None if string.is_empty()
+                        || !string.bytes().all(|byte| byte.is_ascii_digit()) =>
+                    {
+                        validation.push(AS3ValidationError::FormatError {
+                            value: string.clone(),
+                            format: "digits",
+                        })
+                    }
+                    _ => {}
+                }
+            }
+            (
+                AS3Validator::List {
+                    items: items_type,
+                    max_validated,
+                    non_empty,
+                },
+                AS3Data::List(items),
+            ) => {
+                if *non_empty && items.is_empty() {
+                    validation.push(AS3ValidationError::EmptyCollection { collection: "list" });
+                }
+                let limit = max_validated.unwrap_or(usize::MAX);
+                if items.len() > limit {
+                    validation.push(AS3ValidationError::ListTooLong {
+                        length: items.len(),
+                        max: limit,
+                    });
+                }
+                for (index, item) in items.iter().take(limit).enumerate() {
+                    validation.enter(&index.to_string());
+                    items_type.collect_errors(item, validation);
+                    validation.leave();
+                }
+            }
+            (AS3Validator::Tuple { items, rest }, AS3Data::List(elements)) => {
+                let length_matches = match rest {
+                    Some(_) => elements.len() >= items.len(),
+                    None => elements.len() == items.len(),
+                };
+                if !length_matches {
+                    validation.push(AS3ValidationError::TupleLength {
+                        expected: items.len(),
+                        got: elements.len(),
+                    });
+                }
+                for (index, element) in elements.iter().enumerate() {
+                    let Some(validator) = items.get(index).or(rest.as_deref()) else {
+                        break;
+                    };
+                    validation.enter(&index.to_string());
+                    validator.collect_errors(element, validation);
+                    validation.leave();
+                }
+            }
+            (AS3Validator::Positional { fields }, AS3Data::List(elements)) => {
+                if elements.len() != fields.len() {
+                    validation.push(AS3ValidationError::TupleLength {
+                        expected: fields.len(),
+                        got: elements.len(),
+                    });
+                }
+                for ((name, validator), element) in fields.iter().zip(elements) {
+                    validation.enter(name);
+                    validator.collect_errors(element, validation);
+                    validation.leave();
+                }
+            }
+            (
+                AS3Validator::Map {
+                    key_type,
+                    value_type,
+                },
+                AS3Data::Object(data_inner),
+            ) => {
+                for (key, value) in data_inner {
+                    key_type.collect_key_errors(key, validation);
+                    validation.enter(key);
+                    value_type.collect_errors(value, validation);
+                    validation.leave();
+                }
+            }
+            (AS3Validator::Flatten(flattened), _) => flattened.collect_errors(data, validation),
+            (
+                AS3Validator::Annotated {
+                    validator,
+                    annotations,
+                },
+                _,
+            ) => {
+                if annotations.excluded_in(validation.options.direction) {
+                    validation.push(if annotations.read_only {
+                        AS3ValidationError::ReadOnlyViolation
+                    } else {
+                        AS3ValidationError::WriteOnlyViolation
+                    });
+                    return;
+                }
+                if annotations.deprecated {
+                    validation.warn(AS3ValidationError::Deprecated);
+                }
+                validator.collect_errors(data, validation);
+            }
+            #[cfg(feature = "chrono")]
+            (AS3Validator::Date { after, before }, AS3Data::String(string)) => {
+                dates::check_date(string, after.as_deref(), before.as_deref(), validation)
+            }
+            #[cfg(feature = "chrono")]
+            (
+                AS3Validator::DateTime {
+                    require_timezone,
+                    utc_only,
+                },
+                AS3Data::String(string),
+            ) => dates::check_date_time(string, *require_timezone, *utc_only, validation),
+            #[cfg(feature = "bigdecimal")]
+            (AS3Validator::BigNumber { minimum, maximum }, _)
+                if matches!(
+                    data,
+                    AS3Data::String(_) | AS3Data::Integer(_) | AS3Data::Decimal(_)
+                ) =>
+            {
+                let value = match data {
+                    AS3Data::String(string) => string.clone(),
+                    AS3Data::Integer(number) => number.to_string(),
+                    AS3Data::Decimal(number) => number.to_string(),
+                    _ => unreachable!(),
+                };
+                numbers::check_number(&value, minimum.as_deref(), maximum.as_deref(), validation)
+            }
+            (AS3Validator::Const(value), _) => {
+                if value != data {
+                    validation.push(AS3ValidationError::NotConst {
+                        expected: value.preview().unwrap_or_else(|| value.describe()),
+                        preview: data.preview(),
+                    })
+                }
+            }
+            (AS3Validator::Enum(entries), _) => {
+                // Entries are checked under the run's options, as OneOf branches are.
+                let accepted = entries.iter().any(|entry| {
+                    let mut checked = validation.nested();
+                    entry.collect_errors(data, &mut checked);
+                    checked.report.is_valid()
+                });
+                if !accepted {
+                    validation.push(AS3ValidationError::NotInEnum {
+                        preview: data.preview(),
+                    })
+                }
+            }
+            (AS3Validator::AllOf(validators), _) => {
+                for validator in validators {
+                    validator.collect_errors(data, validation);
+                }
+            }
+            (AS3Validator::OneOf(_) | AS3Validator::AnyOf(_), _) => {
+                self.collect_branch_errors(data, validation)
+            }
+            (AS3Validator::Discriminated { key, mapping }, AS3Data::Object(data_inner)) => {
+                let Some(tag) = data_inner.get(key) else {
+                    validation.push(AS3ValidationError::MissingKey { key: key.clone() });
+                    return;
+                };
+                let validator = match tag.as_ref() {
+                    AS3Data::String(tag) => mapping.get(tag),
+                    _ => None,
+                };
+                match validator {
+                    Some(validator) => validator.collect_errors(data, validation),
+                    None => validation.push(AS3ValidationError::UnknownDiscriminator {
+                        key: key.clone(),
+                        preview: tag.preview(),
+                    }),
+                }
+            }
+            (AS3Validator::Ref(url), _) => self.collect_ref_errors(url, data, validation),
+            (AS3Validator::Any, _) => {}
+
+            _ if self.is_container() || data.is_container() => {
+                validation.push(AS3ValidationError::ShapeMismatch {
+                    expected: self.expected_shape(),
+                    found: data.describe(),
+                })
+            }
+            _ => validation.push(AS3ValidationError::TypeError {
+                expected: self.type_name(),
+                got: data.type_name(),
+                preview: data.preview(),
+            }),
+        }
+    }
+
+    /// Checks a string against its constraints. Kept out of
+    /// [`AS3Validator::collect_errors`] so the normalized copy does not grow its frame.
+    fn collect_string_errors(&self, string: &str, validation: &mut Validation) {
+        let AS3Validator::String {
+            regex,
+            min_length,
+            max_length,
+            format,
+            case,
+            no_surrounding_whitespace,
+            non_empty,
+            min_bytes,
+            max_bytes,
+            grammar,
+            ..
+        } = self
+        else {
+            return;
+        };
+        #[cfg(feature = "unicode-normalization")]
+        let normalized = match self {
+            AS3Validator::String {
+                normalize: Some(form),
+                ..
+            } => form.apply(string),
+            _ => string.into(),
+        };
+        #[cfg(feature = "unicode-normalization")]
+        let string: &str = &normalized;
+        if *non_empty && string.is_empty() {
+            validation.push(AS3ValidationError::EmptyString);
+        }
+        if *no_surrounding_whitespace && string.trim() != string {
+            validation.push(AS3ValidationError::SurroundingWhitespace {
+                word: string.to_owned(),
+            });
+        }
+        if let Some(regex) = regex {
+            validation.stats.regex_evaluations += 1;
+            match strings::compiled(regex) {
+                Some(re) if re.is_match(string) => {}
+                Some(_) => validation.push(AS3ValidationError::RegexError {
+                    word: string.to_owned(),
+                    regex: regex.to_owned(),
+                    position: strings::regex_failure(regex, string),
+                }),
+                None => validation.push(AS3ValidationError::UnusableRegex {
+                    regex: regex.to_owned(),
+                }),
+            }
+        }
+        let length = string.chars().count();
+        if min_length.is_some_and(|min| length < min) || max_length.is_some_and(|max| length > max)
+        {
+            validation.push(AS3ValidationError::StringLength {
+                length,
+                min: *min_length,
+                max: *max_length,
+            });
+        }
+        if let Some(format) = format {
+            if !format.matches(string) {
+                validation.push(AS3ValidationError::FormatError {
+                    value: string.to_owned(),
+                    format: format.name(),
+                });
+            }
+        }
+        if let Some(case) = case {
+            if !case.matches(string) {
+                validation.push(AS3ValidationError::CaseError {
+                    word: string.to_owned(),
+                    case: case.name(),
+                });
+            }
+        }
+        if let Some(grammar) = grammar {
+            match validation.options.grammars.get(grammar) {
+                Some(parse) => {
+                    if let Err(position) = parse(string) {
+                        validation.push(AS3ValidationError::GrammarError {
+                            word: string.to_owned(),
+                            grammar: grammar.clone(),
+                            position,
+                        });
+                    }
+                }
+                None => validation.push(AS3ValidationError::UnknownGrammar {
+                    grammar: grammar.clone(),
+                }),
+            }
+        }
+        if min_bytes.is_some() || max_bytes.is_some() {
+            // Invalid base64 was reported above as a format error.
+            let length = match format {
+                #[cfg(feature = "base64")]
+                Some(StringFormat::Base64) => strings::decoded_len(string),
+                _ => Some(string.len()),
+            };
+            if let Some(length) = length.filter(|length| {
+                min_bytes.is_some_and(|min| *length < min)
+                    || max_bytes.is_some_and(|max| *length > max)
+            }) {
+                validation.push(AS3ValidationError::ByteLength {
+                    length,
+                    min: *min_bytes,
+                    max: *max_bytes,
+                });
+            }
+        }
+    }
+
+    /// Checks `data` against the branches of a `OneOf`, which exactly one must accept, or
+    /// of an `AnyOf`, which stops at the first branch that does.
+    fn collect_branch_errors(&self, data: &AS3Data, validation: &mut Validation) {
+        let (AS3Validator::OneOf(branches) | AS3Validator::AnyOf(branches)) = self else {
+            return;
+        };
+        let exclusive = matches!(self, AS3Validator::OneOf(_));
+        let mut reports = vec![];
+        for branch in branches {
+            let mut checked = validation.nested();
+            branch.collect_errors(data, &mut checked);
+            if !exclusive && checked.report.is_valid() {
+                return;
+            }
+            reports.push(checked.report);
+        }
+        match reports.iter().filter(|report| report.is_valid()).count() {
+            0 => {
+                // A branch rejecting the data's type outright is a worse guess than one
+                // that got into its fields; after that, fewer errors is closer.
+                let closest = reports.into_iter().enumerate().min_by_key(|(_, report)| {
+                    let wrong_type = report
+                        .errors
+                        .iter()
+                        .any(|error| error.path().is_empty() && error.code() == "type_error");
+                    (wrong_type, report.errors.len())
+                });
+                validation.push(match closest {
+                    Some((closest, report)) => AS3ValidationError::NoBranchMatched {
+                        closest: Some(closest),
+                        errors: report.errors,
+                    },
+                    None => AS3ValidationError::NoBranchMatched {
+                        closest: None,
+                        errors: vec![],
+                    },
+                })
+            }
+            1 => {}
+            count => validation.push(AS3ValidationError::MultipleBranchesMatched { count }),
+        }
+    }
+
+    /// Checks an object key under the run's options, reporting its first error as an
+    /// [`AS3ValidationError::PropertyName`] at the object.
+    fn collect_key_errors(&self, key: &str, validation: &mut Validation) {
+        let mut checked = validation.nested();
+        self.collect_errors(&AS3Data::String(key.to_string()), &mut checked);
+        let report = checked.finish();
+        let wrap = |error: AS3ValidationError| AS3ValidationError::PropertyName {
+            key: key.to_string(),
+            error: Box::new(error.without_path()),
+        };
+        if let Some(error) = report.errors.into_iter().next() {
+            validation.push(wrap(error));
+        } else if let Some(warning) = report.warnings.into_iter().next() {
+            validation.warn(wrap(warning));
+        }
+    }
+
+    /// Checks `data` against the schema fetched for `url`, if the run has one, or against
+    /// the run's root validator for `#`. Kept out of [`AS3Validator::collect_errors`] like
+    /// the helpers below.
+    fn collect_ref_errors(&self, url: &str, data: &AS3Data, validation: &mut Validation) {
+        if url == "#" {
+            match validation.root {
+                Some(root) => {
+                    validation.root_follows += 1;
+                    root.collect_errors(data, validation);
+                    validation.root_follows -= 1;
+                }
+                None => validation.push(AS3ValidationError::RefUnavailable {
+                    url: url.to_string(),
+                    reason: "there is no root validator to refer to".to_string(),
+                }),
+            }
+            return;
+        }
+        match validation.refs.and_then(|refs| refs.get(url)) {
+            // Inside the fetched schema, `#` is that document's own root.
+            Some(Ok(validator)) => {
+                let outer = validation.root.replace(validator);
+                validator.collect_errors(data, validation);
+                validation.root = outer;
+            }
+            Some(Err(reason)) => validation.push(AS3ValidationError::RefUnavailable {
+                url: url.to_string(),
+                reason: reason.clone(),
+            }),
+            None => validation.push(AS3ValidationError::RefUnavailable {
+                url: url.to_string(),
+                reason: "it is only fetched by validate_async".to_string(),
+            }),
+        }
+    }
+
+    /// Checks an Integer against its bounds and step, comparing in `i64` so that values
+    /// past 2^53 are not rounded together. Kept out of [`AS3Validator::collect_errors`]
+    /// like the helpers below.
+    fn collect_integer_errors(&self, number: i64, validation: &mut Validation) {
+        let AS3Validator::Integer {
+            minimum,
+            maximum,
+            step,
+            step_origin,
+        } = self
+        else {
+            return;
+        };
+        // Bounds an f64 holds exactly keep the errors shared with Decimal.
+        let exact = |value: i64| value.unsigned_abs() <= 1 << f64::MANTISSA_DIGITS;
+        if let Some(minimum) = minimum.filter(|minimum| *minimum > number) {
+            validation.push(match exact(number) && exact(minimum) {
+                true => AS3ValidationError::Minimum {
+                    number: number as f64,
+                    minimum: minimum as f64,
+                },
+                false => AS3ValidationError::IntegerMinimum { number, minimum },
+            });
+        }
+        if let Some(maximum) = maximum.filter(|maximum| *maximum < number) {
+            validation.push(match exact(number) && exact(maximum) {
+                true => AS3ValidationError::Maximum {
+                    number: number as f64,
+                    maximum: maximum as f64,
+                },
+                false => AS3ValidationError::IntegerMaximum { number, maximum },
+            });
+        }
+        if let Some(step) = step.filter(|step| *step > 0) {
+            let origin = step_origin.unwrap_or(0);
+            // Widened, as the offset from the origin can overflow an i64.
+            if (number as i128 - origin as i128) % step as i128 != 0 {
+                validation.push(AS3ValidationError::NotOnStep {
+                    number,
+                    step,
+                    origin,
+                });
+            }
+        }
+    }
+
+    /// Checks digits sent as a string as the integer they spell out. Kept out of
+    /// [`AS3Validator::collect_errors`] so the converted value does not grow its frame.
+    fn collect_integer_string(&self, string: &str, validation: &mut Validation) {
+        match string.parse() {
+            Ok(number) => self.collect_errors(&AS3Data::Integer(number), validation),
+            // The string is all digits, so parsing can only fail on overflow.
+            Err(_) => validation.push(AS3ValidationError::NumberOutOfRange {
+                value: string.to_string(),
+            }),
+        }
+    }
+
+    /// Checks a number sent as a string, in plain or scientific notation, as the Decimal
+    /// it spells out. Kept out of [`AS3Validator::collect_errors`] like the one above.
+    fn collect_decimal_string(&self, string: &str, validation: &mut Validation) {
+        match string.parse::<f64>() {
+            Ok(number) if number.is_finite() => {
+                self.collect_errors(&AS3Data::Decimal(number), validation)
+            }
+            // An exponent such as `1e400` overflows to infinity rather than failing.
+            _ => validation.push(AS3ValidationError::NumberOutOfRange {
+                value: string.to_string(),
+            }),
+        }
+    }
+
+    /// Calls `f` on every node of the validator tree, parents before children, along with
+    /// the node's path.
+    ///
+    /// Path segments are object keys, tuple indices and `*` for list items and map values.
+    /// Directives get their YAML name (`+PropertyNames`, `+KeyType`), while flattened and
+    /// annotated validators and `OneOf`/`AllOf`/`Discriminated` branches share the path of the
+    /// node they wrap, and the validator of a cross-field rule gets the path of the key
+    /// it checks.
+    pub fn walk<F: FnMut(&AS3Validator, &[String])>(&self, mut f: F) {
+        self.walk_at(&mut vec![], &mut f);
+    }
+
+    fn walk_at<F: FnMut(&AS3Validator, &[String])>(&self, path: &mut Vec<String>, f: &mut F) {
+        f(self, path);
+        let mut child = |segment: Option<&str>, validator: &AS3Validator| {
+            if let Some(segment) = segment {
+                path.push(segment.to_string());
+            }
+            validator.walk_at(path, f);
+            if segment.is_some() {
+                path.pop();
+            }
+        };
+        match self {
+            AS3Validator::Object {
+                properties,
+                property_names,
+                additional_properties_schema,
+                rules,
+                ..
+            } => {
+                if let Some(property_names) = property_names {
+                    child(Some("+PropertyNames"), property_names);
+                }
+                if let Some(schema) = additional_properties_schema {
+                    child(Some("+AdditionalProperties"), schema);
+                }
+                for (key, validator) in properties {
+                    let segment = (!matches!(validator, AS3Validator::Flatten(_))).then_some(key);
+                    child(segment.map(String::as_str), validator);
+                }
+                for CrossFieldRule {
+                    then: (key, validator),
+                    ..
+                } in rules
+                {
+                    child(Some(key), validator);
+                }
+            }
+            AS3Validator::List { items, .. } => child(Some("*"), items),
+            AS3Validator::Tuple { items, rest } => {
+                for (index, validator) in items.iter().enumerate() {
+                    child(Some(&index.to_string()), validator);
+                }
+                if let Some(rest) = rest {
+                    child(Some("*"), rest);
+                }
+            }
+            AS3Validator::Positional { fields } => {
+                for (name, validator) in fields {
+                    child(Some(name), validator);
+                }
+            }
+            AS3Validator::Map {
+                key_type,
+                value_type,
+            } => {
+                child(Some("+KeyType"), key_type);
+                child(Some("*"), value_type);
+            }
+            AS3Validator::Flatten(validator) | AS3Validator::Annotated { validator, .. } => {
+                child(None, validator)
+            }
+            AS3Validator::OneOf(branches)
+            | AS3Validator::AnyOf(branches)
+            | AS3Validator::AllOf(branches)
+            | AS3Validator::Enum(branches) => {
+                for branch in branches {
+                    child(None, branch);
+                }
+            }
+            AS3Validator::Discriminated { mapping, .. } => {
+                for validator in mapping.values() {
+                    child(None, validator);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Dotted paths and type names of every leaf field that must be present, sorted by path.
+    ///
+    /// List elements are described with a `[]` suffix (`vehicles[].maker`), and the fields
+    /// of an object guarded by `+WhenPresent` are left out, as they are conditional, and so
+    /// are `+Optional` fields.
+    pub fn required_fields(&self) -> Vec<(String, &'static str)> {
+        let mut fields = vec![];
+        self.collect_required_fields(String::new(), &mut fields);
+        fields.sort();
+        fields
+    }
+
+    fn collect_required_fields(&self, path: String, fields: &mut Vec<(String, &'static str)>) {
+        match self {
+            AS3Validator::Object {
+                properties,
+                when_present,
+                ..
+            } => {
+                if when_present.is_some() {
+                    return;
+                }
+                for (key, validator) in properties {
+                    if validator
+                        .annotations()
+                        .is_some_and(|annotations| annotations.optional)
+                    {
+                        continue;
+                    }
+                    let path = match (validator, path.is_empty()) {
+                        (AS3Validator::Flatten(_), _) => path.clone(),
+                        (_, true) => key.clone(),
+                        (_, false) => format!("{path}.{key}"),
+                    };
+                    validator.collect_required_fields(path, fields);
+                }
+            }
+            AS3Validator::List { items, .. } => {
+                items.collect_required_fields(format!("{path}[]"), fields)
+            }
+            AS3Validator::Flatten(validator) | AS3Validator::Annotated { validator, .. } => {
+                validator.collect_required_fields(path, fields)
+            }
+            leaf => fields.push((path, leaf.type_name())),
+        }
+    }
+
+    /// Dotted paths of every key in `data` that the matching Object validator does not
+    /// declare, sorted. List elements are addressed by index (`vehicles.0.color`).
+    ///
+    /// This is a report and never fails: data the schema rejects is still searched as
+    /// far as its shape allows, and a `OneOf` is followed into its first accepting branch.
+    pub fn unknown_keys(&self, data: &AS3Data) -> Vec<String> {
+        let mut keys = vec![];
+        self.collect_unknown_keys(data, &mut vec![], &mut keys);
+        keys.sort();
+        keys
+    }
+
+    fn collect_unknown_keys(&self, data: &AS3Data, path: &mut Vec<String>, keys: &mut Vec<String>) {
+        fn child(
+            segment: &str,
+            validator: &AS3Validator,
+            data: &AS3Data,
+            path: &mut Vec<String>,
+            keys: &mut Vec<String>,
+        ) {
+            path.push(segment.to_string());
+            validator.collect_unknown_keys(data, path, keys);
+            path.pop();
+        }
+        match (self, data) {
+            (
+                AS3Validator::Object {
+                    additional_properties_schema,
+                    ..
+                },
+                AS3Data::Object(fields),
+            ) => {
+                let mut names: Vec<_> = fields.keys().collect();
+                names.sort();
+                for name in names {
+                    match self
+                        .property(name)
+                        .or(additional_properties_schema.as_deref())
+                    {
+                        Some(validator) => child(name, validator, &fields[name], path, keys),
+                        None => {
+                            path.push(name.clone());
+                            keys.push(path.join("."));
+                            path.pop();
+                        }
+                    }
+                }
+            }
+            (AS3Validator::List { items, .. }, AS3Data::List(elements)) => {
+                for (index, element) in elements.iter().enumerate() {
+                    child(&index.to_string(), items, element, path, keys);
+                }
+            }
+            (AS3Validator::Tuple { items, rest }, AS3Data::List(elements)) => {
+                for (index, element) in elements.iter().enumerate() {
+                    if let Some(validator) = items.get(index).or(rest.as_deref()) {
+                        child(&index.to_string(), validator, element, path, keys);
+                    }
+                }
+            }
+            (AS3Validator::Positional { fields }, AS3Data::List(elements)) => {
+                for ((name, validator), element) in fields.iter().zip(elements) {
+                    child(name, validator, element, path, keys);
+                }
+            }
+            (AS3Validator::Map { value_type, .. }, AS3Data::Object(fields)) => {
+                for (name, value) in fields {
+                    child(name, value_type, value, path, keys);
+                }
+            }
+            (AS3Validator::Flatten(validator) | AS3Validator::Annotated { validator, .. }, _) => {
+                validator.collect_unknown_keys(data, path, keys)
+            }
+            (AS3Validator::OneOf(branches) | AS3Validator::AnyOf(branches), _) => {
+                if let Some(branch) = branches.iter().find(|branch| branch.is_valid(data)) {
+                    branch.collect_unknown_keys(data, path, keys);
+                }
+            }
+            (AS3Validator::AllOf(validators), _) => {
+                // A key is known as soon as one of the validators declares it.
+                let mut unknown: Option<Vec<String>> = None;
+                for validator in validators {
+                    let mut found = vec![];
+                    validator.collect_unknown_keys(data, path, &mut found);
+                    unknown = Some(match unknown {
+                        Some(unknown) => unknown
+                            .into_iter()
+                            .filter(|key| found.contains(key))
+                            .collect(),
+                        None => found,
+                    });
+                }
+                keys.extend(unknown.unwrap_or_default());
+            }
+            (AS3Validator::Discriminated { key, mapping }, AS3Data::Object(fields)) => {
+                if let Some(AS3Data::String(tag)) = fields.get(key).map(Box::as_ref) {
+                    if let Some(validator) = mapping.get(tag) {
+                        validator.collect_unknown_keys(data, path, keys);
+                        // The tag itself is known, even when the variants leave it out.
+                        path.push(key.clone());
+                        let tag_path = path.join(".");
+                        path.pop();
+                        keys.retain(|unknown| unknown != &tag_path);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Looks up a nested validator by a dotted path in the format of
+    /// [`AS3Validator::required_fields`]: `vehicles.list` is the List validator and
+    /// `vehicles.list[].maker` a property of its elements. An empty path is `self`.
+    pub fn get(&self, path: &str) -> Option<&AS3Validator> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        path.split('.').try_fold(self, |validator, segment| {
+            let key = segment.trim_end_matches("[]");
+            let lists = (segment.len() - key.len()) / 2;
+            (0..lists).try_fold(validator.property(key)?, |validator, _| {
+                match validator.unannotated() {
+                    AS3Validator::List { items, .. } => Some(items.as_ref()),
+                    _ => None,
+                }
+            })
+        })
+    }
+
+    /// The validator for `key` of an object, looking through flattened objects.
+    fn property(&self, key: &str) -> Option<&AS3Validator> {
+        let AS3Validator::Object { properties, .. } = self.unannotated() else {
+            return None;
+        };
+        properties.get(key).or_else(|| {
+            properties.values().find_map(|validator| match validator {
+                AS3Validator::Flatten(flattened) => flattened.property(key),
+                _ => None,
+            })
+        })
+    }
+
+    fn unannotated(&self) -> &AS3Validator {
+        match self {
+            AS3Validator::Annotated { validator, .. } => validator.unannotated(),
+            validator => validator,
+        }
+    }
+
+    /// The `+Description` documenting this validator's field, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.annotations()?.description.as_deref()
+    }
+
+    /// The `+Title` labelling this validator's field, if any.
+    pub fn title(&self) -> Option<&str> {
+        self.annotations()?.title.as_deref()
+    }
+
+    /// The annotations attached to this validator, if any.
+    pub fn annotations(&self) -> Option<&Annotations> {
+        match self {
+            AS3Validator::Annotated { annotations, .. } => Some(annotations),
+            _ => None,
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            AS3Validator::Object { .. } => "Object",
+            AS3Validator::String { .. } => "String",
+            AS3Validator::Integer { .. } => "Integer",
+            AS3Validator::Decimal { .. } => "Decimal",
+            AS3Validator::NumericString { .. } => "NumericString",
+            AS3Validator::Const(_) => "Const",
+            AS3Validator::Enum(_) => "Enum",
+            AS3Validator::OneOf(_) => "OneOf",
+            AS3Validator::AnyOf(_) => "AnyOf",
+            AS3Validator::AllOf(_) => "AllOf",
+            AS3Validator::Ref(_) => "Ref",
+            AS3Validator::Discriminated { .. } => "Discriminated",
+            AS3Validator::List { .. } => "List",
+            AS3Validator::Tuple { .. } => "Tuple",
+            AS3Validator::Positional { .. } => "Positional",
+            AS3Validator::Map { .. } => "Map",
+            AS3Validator::Flatten(validator) => validator.type_name(),
+            AS3Validator::Annotated { validator, .. } => validator.type_name(),
+            #[cfg(feature = "chrono")]
+            AS3Validator::Date { .. } => "Date",
+            #[cfg(feature = "chrono")]
+            AS3Validator::DateTime { .. } => "DateTime",
+            #[cfg(feature = "bigdecimal")]
+            AS3Validator::BigNumber { .. } => "BigNumber",
+            AS3Validator::Any => "Any",
+        }
+    }
+
+    fn is_container(&self) -> bool {
+        match self {
+            AS3Validator::Object { .. }
+            | AS3Validator::List { .. }
+            | AS3Validator::Tuple { .. }
+            | AS3Validator::Positional { .. }
+            | AS3Validator::Map { .. }
+            | AS3Validator::Discriminated { .. } => true,
+            AS3Validator::Flatten(validator) => validator.is_container(),
+            AS3Validator::Annotated { validator, .. } => validator.is_container(),
+            _ => false,
+        }
+    }
+
+    /// What the validator expects, phrased for [`AS3ValidationError::ShapeMismatch`].
+    fn expected_shape(&self) -> &'static str {
+        match self {
+            AS3Validator::Object { .. } | AS3Validator::Map { .. } => "an object",
+            AS3Validator::List { .. }
+            | AS3Validator::Tuple { .. }
+            | AS3Validator::Positional { .. } => "a list",
+            AS3Validator::String { .. } => "a string",
+            AS3Validator::Integer { .. } => "an integer",
+            AS3Validator::Decimal { .. } => "a decimal",
+            AS3Validator::NumericString { .. } => "a string of digits",
+            AS3Validator::Const(_) => "the expected value",
+            AS3Validator::Enum(_) => "one of the allowed values",
+            AS3Validator::OneOf(_) => "exactly one of the alternatives",
+            AS3Validator::AnyOf(_) => "at least one of the alternatives",
+            AS3Validator::AllOf(_) => "a value meeting every constraint",
+            AS3Validator::Ref(_) => "a value matching the referenced schema",
+            AS3Validator::Discriminated { .. } => "an object",
+            AS3Validator::Flatten(validator) => validator.expected_shape(),
+            AS3Validator::Annotated { validator, .. } => validator.expected_shape(),
+            #[cfg(feature = "chrono")]
+            AS3Validator::Date { .. } => "a date",
+            #[cfg(feature = "chrono")]
+            AS3Validator::DateTime { .. } => "a date and time",
+            #[cfg(feature = "bigdecimal")]
+            AS3Validator::BigNumber { .. } => "a number",
+            AS3Validator::Any => "anything",
+        }
+    }
+
+    pub fn to_yaml_string(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(&serde_yaml::Value::from(self))
+    }
+
+    /// Reads back the output of [`AS3Validator::to_yaml_string`].
+    ///
+    /// `serde_yaml` only deserializes enums written as YAML tags, while `to_yaml_string`
+    /// writes each variant as a `+Variant:` key, so the document goes through JSON just
+    /// like on the way out.
+    pub fn from_yaml_string(yaml: &str) -> Result<AS3Validator, serde_yaml::Error> {
+        let serialized_yaml: serde_yaml::Value = serde_yaml::from_str(yaml)?;
+        let serialized_json = serde_json::to_value(&serialized_yaml)
+            .map_err(<serde_yaml::Error as serde::de::Error>::custom)?;
+        serde_json::from_value(serialized_json)
+            .map_err(<serde_yaml::Error as serde::de::Error>::custom)
+    }
+}
+
+impl From<&AS3Validator> for serde_json::Value {
+    fn from(validator: &AS3Validator) -> Self {
+        // Every key is a String and every number an i64/f64, so this cannot fail.
+        serde_json::to_value(validator).expect("validators always serialize to JSON")
+    }
+}
+
+/// The document [`AS3Validator::to_yaml_string`] writes out, for splicing a validator into
+/// a larger YAML document.
+impl From<&AS3Validator> for serde_yaml::Value {
+    fn from(validator: &AS3Validator) -> Self {
+        serde_yaml::to_value(serde_json::Value::from(validator))
+            .expect("JSON values always serialize to YAML")
+    }
+}
+
+/// Consistent with `PartialEq`: object keys hash alike in any order, as they compare
+/// equal in any order, and so do `0.0` and `-0.0`.
+impl Hash for AS3Data {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            AS3Data::Object(properties) => {
+                let entries = properties
+                    .iter()
+                    .map(|entry| {
+                        let mut hasher = DefaultHasher::new();
+                        entry.hash(&mut hasher);
+                        hasher.finish()
+                    })
+                    .fold(0, u64::wrapping_add);
+                properties.len().hash(state);
+                entries.hash(state);
+            }
+            AS3Data::String(string) => string.hash(state),
+            AS3Data::Map {
+                key_type,
+                value_type,
+            } => {
+                key_type.hash(state);
+                value_type.hash(state);
+            }
+            AS3Data::Boolean(boolean) => boolean.hash(state),
+            AS3Data::Integer(number) => number.hash(state),
+            AS3Data::Decimal(number) => {
+                let number = if *number == 0.0 { 0.0 } else { *number };
+                number.to_bits().hash(state)
+            }
+            AS3Data::List(items) => items.hash(state),
+            AS3Data::Null => {}
+        }
+    }
+}
+
+impl From<&serde_json::Value> for AS3Data {
+    fn from(json: &serde_json::Value) -> AS3Data {
+        AS3Data::from_value_with(json, &ConversionOptions::default())
+    }
+}
+
+/// The reverse of `From<&serde_json::Value>`. A `Map` becomes an object with `KeyType`
+/// and `ValueType` keys, and non-finite decimals, which JSON cannot represent, become `null`.
+impl From<&AS3Data> for serde_json::Value {
+    fn from(data: &AS3Data) -> Self {
+        match data {
+            AS3Data::Object(inner) => serde_json::Value::Object(
+                inner
+                    .iter()
+                    .map(|(key, value)| (key.clone(), serde_json::Value::from(value.as_ref())))
+                    .collect(),
+            ),
+            AS3Data::String(inner) => serde_json::Value::String(inner.clone()),
+            AS3Data::Map {
+                key_type,
+                value_type,
+            } => serde_json::json!({
+                "KeyType": serde_json::Value::from(key_type.as_ref()),
+                "ValueType": serde_json::Value::from(value_type.as_ref()),
+            }),
+            AS3Data::Boolean(inner) => serde_json::Value::Bool(*inner),
+            AS3Data::Integer(inner) => serde_json::Value::from(*inner),
+            AS3Data::Decimal(inner) => serde_json::Number::from_f64(*inner)
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            AS3Data::List(inner) => {
+                serde_json::Value::Array(inner.iter().map(serde_json::Value::from).collect())
+            }
+            AS3Data::Null => serde_json::Value::Null,
+        }
+    }
+}
+
+impl AS3Data {
+    /// Renders the data as indented JSON, e.g. to log a document that failed validation.
+    pub fn to_json_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&serde_json::Value::from(self))
+            .expect("JSON values always serialize")
+    }
+
+    /// Converts JSON to [`AS3Data`], classifying numbers as `options` dictates.
+    pub fn from_value_with(json: &serde_json::Value, options: &ConversionOptions) -> AS3Data {
+        match json {
+            serde_json::Value::Object(inner) => AS3Data::Object(
+                inner
+                    .iter()
+                    .map(|(key, value)| {
+                        (
+                            key.clone(),
+                            Box::new(AS3Data::from_value_with(value, options)),
+                        )
+                    })
+                    .collect(),
+            ),
+            serde_json::Value::Array(inner) => AS3Data::List(
+                inner
+                    .iter()
+                    .map(|e| AS3Data::from_value_with(e, options))
+                    .collect(),
+            ),
+            serde_json::Value::String(inner) => AS3Data::String(inner.clone()),
+            serde_json::Value::Number(inner) => {
+                if let Some(number) = inner.as_i64() {
+                    return AS3Data::Integer(number);
+                }
+                let number = inner.as_f64().unwrap();
+                let whole =
+                    number.fract() == 0.0 && number >= i64::MIN as f64 && number < i64::MAX as f64;
+                if options.whole_floats_as_integers && whole {
+                    AS3Data::Integer(number as i64)
+                } else {
+                    AS3Data::Decimal(number)
+                }
+            }
+            serde_json::Value::Bool(inner) => AS3Data::Boolean(*inner),
+            serde_json::Value::Null => AS3Data::Null,
+        }
+    }
+}
+
+/// Longest preview, in characters, included in a [`AS3ValidationError::TypeError`].
+const PREVIEW_LENGTH: usize = 32;
+
+impl AS3Data {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            AS3Data::Object(_) => "Object",
+            AS3Data::String(_) => "String",
+            AS3Data::Map { .. } => "Map",
+            AS3Data::Boolean(_) => "Boolean",
+            AS3Data::Integer(_) => "Integer",
+            AS3Data::Decimal(_) => "Decimal",
+            AS3Data::List(_) => "List",
+            AS3Data::Null => "Null",
+        }
+    }
+
+    fn is_container(&self) -> bool {
+        matches!(
+            self,
+            AS3Data::Object(_) | AS3Data::Map { .. } | AS3Data::List(_)
+        )
+    }
+
+    /// What the data is, phrased for [`AS3ValidationError::ShapeMismatch`].
+    fn describe(&self) -> String {
+        let preview = self.preview().unwrap_or_default();
+        match self {
+            AS3Data::Object(properties) => match properties.len() {
+                1 => "an object with 1 key".to_string(),
+                keys => format!("an object with {keys} keys"),
+            },
+            AS3Data::List(items) => match items.len() {
+                1 => "a list with 1 item".to_string(),
+                len => format!("a list with {len} items"),
+            },
+            AS3Data::Map { .. } => "a map".to_string(),
+            AS3Data::String(_) => format!("the string {preview}"),
+            AS3Data::Boolean(_) => format!("the boolean {preview}"),
+            AS3Data::Integer(_) => format!("the integer {preview}"),
+            AS3Data::Decimal(_) => format!("the decimal {preview}"),
+            AS3Data::Null => "null".to_string(),
+        }
+    }
+
+    fn preview(&self) -> Option<String> {
+        let preview = match self {
+            AS3Data::String(string) => format!("{string:?}"),
+            AS3Data::Boolean(boolean) => boolean.to_string(),
+            AS3Data::Integer(number) => number.to_string(),
+            AS3Data::Decimal(number) => number.to_string(),
+            AS3Data::Null => "null".to_string(),
+            AS3Data::Object(_) | AS3Data::Map { .. } | AS3Data::List(_) => return None,
+        };
+        if preview.chars().count() <= PREVIEW_LENGTH {
+            return Some(preview);
+        }
+        Some(format!(
+            "{}…",
+            preview.chars().take(PREVIEW_LENGTH).collect::<String>()
+        ))
+    }
+}
+
+/// Fails on YAML that has no JSON equivalent, such as tagged values or keys that are
+/// collections.
+impl TryFrom<&serde_yaml::Value> for AS3Data {
+    type Error = serde_json::Error;
+
+    fn try_from(yaml: &serde_yaml::Value) -> Result<Self, Self::Error> {
+        Ok(AS3Data::from(&serde_json::to_value(yaml)?))
+    }
+}
+
+impl TryFrom<&str> for AS3Data {
+    type Error = serde_json::Error;
+
+    fn try_from(json: &str) -> Result<AS3Data, Self::Error> {
+        let json: serde_json::Value = serde_json::from_str(json)?;
+        Ok(AS3Data::from(&json))
+    }
+}
+
+/// Counts the fractional digits of `number` using its shortest round-trip representation,
+/// so `0.1 + 0.2` counts as 17 digits while `3.14` counts as 2.
+fn decimal_places(number: f64) -> u32 {
+    let formatted = number.to_string();
+    match formatted.split_once('.') {
+        Some((_, fraction)) => fraction.len() as u32,
+        None => 0,
+    }
+}
+
+/// Drops a leading UTF-8 byte order mark, as left behind by some Windows editors.
+fn strip_bom(input: &str) -> &str {
+    input.strip_prefix('\u{feff}').unwrap_or(input)
+}
+
+/// Reads one `+Rules` entry, `{ +When: { key: value }, +Then: { key: validator } }`.
+fn rule_from_yaml(
+    rule: &serde_yaml::Value,
+    build: &mut SchemaBuild,
+) -> Result<CrossFieldRule, String> {
+    let single = |directive: &str| match rule.get(directive) {
+        Some(serde_yaml::Value::Mapping(entry)) if entry.len() == 1 => {
+            let (key, value) = entry.iter().next().unwrap();
+            key.as_str()
+                .map(|key| (key.to_string(), value))
+                .ok_or_else(|| format!("the {directive} key of a rule must be a string"))
+        }
+        _ => Err(format!("a rule needs {directive} with exactly one key")),
+    };
+    let (when_key, when_value) = single("+When")?;
+    let (then_key, then) = single("+Then")?;
+    Ok(CrossFieldRule {
+        when: (when_key, const_value(when_value)?),
+        then: (then_key.clone(), build.child(Some(&then_key), then)),
+    })
+}
+
+/// Converts a literal written in a schema, such as an `Enum` entry, to data.
+fn const_value(value: &serde_yaml::Value) -> Result<AS3Data, String> {
+    serde_json::to_value(value)
+        .map(|value| AS3Data::from(&value))
+        .map_err(|e| format!("unsupported literal value: {e}"))
+}
+
+impl AS3Validator {
+    /// Parses a YAML schema document and builds the validator under its `Root` key.
+    pub fn from_yaml_str(yaml: &str) -> Result<AS3Validator, AS3SchemaError> {
+        let yaml_config: serde_yaml::Value =
+            serde_yaml::from_str(strip_bom(yaml)).map_err(|e| e.to_string())?;
+        AS3Validator::from(&yaml_config)
+    }
+
+    /// Converts an already-parsed JSON value, such as GraphQL variables, and validates it.
+    /// A `null` under an optional field counts as the field being absent.
+    pub fn validate_value(&self, value: &serde_json::Value) -> Result<(), AS3ValidationError> {
+        self.validate(&AS3Data::from(value))
+    }
+
+    /// Parses `json` and validates the resulting data.
+    pub fn validate_json_str(&self, json: &str) -> Result<(), AS3ValidationError> {
+        let data =
+            AS3Data::try_from(strip_bom(json)).map_err(|e| AS3ValidationError::ParseError {
+                message: e.to_string(),
+            })?;
+        self.validate(&data)
+    }
+
+    /// Parses a YAML document, resolving aliases and `<<` merge keys, and validates it.
+    pub fn validate_yaml_str(&self, yaml: &str) -> Result<(), AS3ValidationError> {
+        let parse_error = |message: String| AS3ValidationError::ParseError { message };
+        let mut yaml: serde_yaml::Value =
+            serde_yaml::from_str(strip_bom(yaml)).map_err(|e| parse_error(e.to_string()))?;
+        yaml.apply_merge().map_err(|e| parse_error(e.to_string()))?;
+        let data = AS3Data::try_from(&yaml).map_err(|e| parse_error(e.to_string()))?;
+        self.validate(&data)
+    }
+
+    /// Parses raw JSON bytes, such as a request body, and validates the resulting data
+    /// without first copying them into a `String`.
+    pub fn validate_bytes(&self, bytes: &[u8]) -> Result<(), AS3ValidationError> {
+        let bytes = bytes.strip_prefix("\u{feff}".as_bytes()).unwrap_or(bytes);
+        let json: serde_json::Value =
+            serde_json::from_slice(bytes).map_err(|e| AS3ValidationError::ParseError {
+                message: e.to_string(),
+            })?;
+        self.validate(&AS3Data::from(&json))
+    }
+
+    /// Validates newline-delimited JSON one line at a time, yielding each result with its
+    /// 1-based line number. Blank lines are skipped, and a line that cannot be read or
+    /// parsed yields a [`AS3ValidationError::ParseError`] without ending the stream.
+    pub fn validate_ndjson<'a, R: BufRead + 'a>(
+        &'a self,
+        reader: R,
+    ) -> impl Iterator<Item = (usize, Result<(), AS3ValidationError>)> + 'a {
+        reader
+            .lines()
+            .enumerate()
+            .filter_map(move |(index, line)| match line {
+                Ok(line) if line.trim().is_empty() => None,
+                Ok(line) => Some((index + 1, self.validate_json_str(&line))),
+                Err(e) => Some((
+                    index + 1,
+                    Err(AS3ValidationError::ParseError {
+                        message: e.to_string(),
+                    }),
+                )),
+            })
+    }
+
+    /// Loads a schema, failing on its first mistake. Use [`AS3Validator::try_from`] to
+    /// get every mistake at once.
+    pub fn from(yaml_config: &serde_yaml::Value) -> Result<AS3Validator, AS3SchemaError> {
+        AS3Validator::try_from(yaml_config).map_err(|mut errors| errors.remove(0).without_path())
+    }
+
+    fn build_from_yaml(
+        yaml_config: &serde_yaml::Value,
+        build: &mut SchemaBuild,
+    ) -> Result<AS3Validator, AS3SchemaError> {
+        let validator_type = match yaml_config.get("+Type") {
+            Some(serde_yaml::Value::String(validator_type)) => validator_type.as_str(),
+            None if yaml_config.get("+OneOf").is_some() => "OneOf",
+            None if yaml_config.get("+AnyOf").is_some() => "AnyOf",
+            None if yaml_config.get("+AllOf").is_some() => "AllOf",
+            None if yaml_config.get("+Ref").is_some() => "Ref",
+            None if yaml_config.get("+Discriminator").is_some() => "Discriminated",
+            _ => return Err("Non ce il +type".to_string().into()),
+        };
+
+        let validator = match (validator_type, yaml_config) {
+            ("Object", serde_yaml::Value::Mapping(inner)) => {
+                let mut x: HashMap<String, AS3Validator> = HashMap::new();
+                for (key, value) in inner {
+                    let Some(key) = key.as_str() else {
+                        return Err("Object keys must be strings".to_string().into());
+                    };
+                    if !key.starts_with('+') {
+                        x.insert(key.to_string(), build.child(Some(key), value));
+                    }
+                }
+                let property_names = yaml_config.get("+PropertyNames").map(|property_names| {
+                    Arc::new(build.child(Some("+PropertyNames"), property_names))
+                });
+                if let Some(flattened) = yaml_config.get("+Flatten") {
+                    x.insert(
+                        "+Flatten".to_string(),
+                        AS3Validator::Flatten(Arc::new(build.child(None, flattened))),
+                    );
+                }
+                AS3Validator::Object {
+                    properties: x,
+                    property_names,
+                    when_present: yaml_config
+                        .get("+WhenPresent")
+                        .and_then(|key| key.as_str())
+                        .map(|key| key.to_string()),
+                    dependent_required: match yaml_config.get("+DependentRequired") {
+                        Some(dependent_required) => {
+                            serde_yaml::from_value(dependent_required.clone()).map_err(|e| {
+                                format!("+DependentRequired must map keys to key lists: {e}")
+                            })?
+                        }
+                        None => HashMap::new(),
+                    },
+                    additional_properties: match yaml_config.get("+AdditionalProperties") {
+                        Some(serde_yaml::Value::Bool(allowed)) => *allowed,
+                        None | Some(serde_yaml::Value::Mapping(_)) => true,
+                        Some(_) => {
+                            return Err("+AdditionalProperties must be a boolean or a validator"
+                                .to_string()
+                                .into())
+                        }
+                    },
+                    additional_properties_schema: match yaml_config.get("+AdditionalProperties") {
+                        Some(schema @ serde_yaml::Value::Mapping(_)) => {
+                            Some(Arc::new(build.child(Some("+AdditionalProperties"), schema)))
+                        }
+                        _ => None,
+                    },
+                    non_empty: yaml_config
+                        .get("+NonEmpty")
+                        .and_then(|non_empty| non_empty.as_bool())
+                        .unwrap_or(false),
+                    require_sorted_keys: match yaml_config.get("+RequireSortedKeys") {
+                        Some(serde_yaml::Value::Bool(required)) => *required,
+                        None => false,
+                        Some(_) => {
+                            return Err("+RequireSortedKeys must be a boolean".to_string().into())
+                        }
+                    },
+                    rules: match yaml_config.get("+Rules") {
+                        Some(serde_yaml::Value::Sequence(rules)) => rules
+                            .iter()
+                            .map(|rule| rule_from_yaml(rule, build))
+                            .collect::<Result<_, _>>()?,
+                        Some(_) => return Err("+Rules must be a list".to_string().into()),
+                        None => vec![],
+                    },
+                }
+            }
+            ("String", serde_yaml::Value::Mapping(_)) => {
+                let length = |directive: &str| {
+                    yaml_config
+                        .get(directive)
+                        .and_then(|length| length.as_u64())
+                        .map(|length| length as usize)
+                };
+                let format = match yaml_config
+                    .get("+Format")
+                    .and_then(|format| format.as_str())
+                {
+                    Some(name) => Some(
+                        StringFormat::from_name(name)
+                            .ok_or_else(|| format!("unsupported string format `{name}`"))?,
+                    ),
+                    None => None,
+                };
+                let case = match yaml_config.get("+Case").and_then(|case| case.as_str()) {
+                    Some(name) => Some(
+                        StringCase::from_name(name)
+                            .ok_or_else(|| format!("unsupported string case `{name}`"))?,
+                    ),
+                    None => None,
+                };
+                let regex = match yaml_config.get("+Regex") {
+                    Some(serde_yaml::Value::String(regex)) => Some(regex.clone()),
+                    Some(serde_yaml::Value::Null) | None => None,
+                    Some(_) => return Err("+Regex must be a string".to_string().into()),
+                };
+                AS3Validator::String {
+                    regex,
+                    min_length: length("+MinLength"),
+                    max_length: length("+MaxLength"),
+                    format,
+                    case,
+                    no_surrounding_whitespace: yaml_config
+                        .get("+NoSurroundingWhitespace")
+                        .and_then(|flag| flag.as_bool())
+                        .unwrap_or(false),
+                    non_empty: yaml_config
+                        .get("+NonEmpty")
+                        .and_then(|flag| flag.as_bool())
+                        .unwrap_or(false),
+                    min_bytes: length("+MinBytes"),
+                    max_bytes: length("+MaxBytes"),
+                    normalize: match yaml_config.get("+Normalize").and_then(|form| form.as_str()) {
+                        Some(name) => {
+                            Some(NormalizationForm::from_name(name).ok_or_else(|| {
+                                format!("unsupported normalization form `{name}`")
+                            })?)
+                        }
+                        None => None,
+                    },
+                    grammar: match yaml_config.get("+Grammar") {
+                        Some(serde_yaml::Value::String(grammar)) => Some(grammar.clone()),
+                        None => None,
+                        Some(_) => return Err("+Grammar must be a string".to_string().into()),
+                    },
+                }
+            }
+
+            ("Integer", serde_yaml::Value::Mapping(_)) => {
+                let mut integer = |key: &str| {
+                    build.number(yaml_config, key, "an integer", |value| value.as_i64())
+                };
+                AS3Validator::Integer {
+                    minimum: integer("minimum"),
+                    maximum: integer("maximum"),
+                    step: integer("step"),
+                    step_origin: integer("stepOrigin"),
+                }
+            }
+            ("Map", serde_yaml::Value::Mapping(_)) => {
+                let (Some(key_type), Some(value_type)) =
+                    (yaml_config.get("+KeyType"), yaml_config.get("+ValueType"))
+                else {
+                    return Err("Map requires both +KeyType and +ValueType"
+                        .to_string()
+                        .into());
+                };
+                AS3Validator::Map {
+                    key_type: Arc::new(build.child(Some("+KeyType"), key_type)),
+                    value_type: Arc::new(build.child(Some("*"), value_type)),
+                }
+            }
+            ("NumericString", serde_yaml::Value::Mapping(_)) => AS3Validator::NumericString {
+                format_mask: match yaml_config.get("formatMask") {
+                    Some(serde_yaml::Value::String(mask)) => Some(mask.clone()),
+                    None => None,
+                    Some(_) => return Err("formatMask must be a string".to_string().into()),
+                },
+            },
+            ("Decimal", serde_yaml::Value::Mapping(_)) => AS3Validator::Decimal {
+                minimum: build.number(yaml_config, "minimum", "a number", |value| value.as_f64()),
+                maximum: build.number(yaml_config, "maximum", "a number", |value| value.as_f64()),
+                max_decimal_places: build.number(
+                    yaml_config,
+                    "maxDecimalPlaces",
+                    "a non-negative integer",
+                    |value| value.as_u64().and_then(|places| u32::try_from(places).ok()),
+                ),
+            },
+            // Every element is checked against `+Items`, which may be any validator, such
+            // as a Discriminated one for a list of differently shaped objects.
+            ("List", serde_yaml::Value::Mapping(_)) => {
+                let Some(items @ serde_yaml::Value::Mapping(_)) = yaml_config.get("+Items") else {
+                    return Err("List requires a +Items validator".to_string().into());
+                };
+                AS3Validator::List {
+                    items: Arc::new(build.child(Some("*"), items)),
+                    max_validated: yaml_config
+                        .get("+MaxItems")
+                        .and_then(|max| max.as_u64())
+                        .map(|max| max as usize),
+                    non_empty: yaml_config
+                        .get("+NonEmpty")
+                        .and_then(|flag| flag.as_bool())
+                        .unwrap_or(false),
+                }
+            }
+            ("Tuple", serde_yaml::Value::Mapping(_)) => {
+                let Some(serde_yaml::Value::Sequence(items)) = yaml_config.get("+Items") else {
+                    return Err("Tuple requires a +Items list".to_string().into());
+                };
+                AS3Validator::Tuple {
+                    items: items
+                        .iter()
+                        .enumerate()
+                        .map(|(index, item)| build.child(Some(&index.to_string()), item))
+                        .collect(),
+                    rest: yaml_config
+                        .get("+Rest")
+                        .map(|rest| Arc::new(build.child(Some("*"), rest))),
+                }
+            }
+            // Fields keep the order they are written in.
+            ("Positional", serde_yaml::Value::Mapping(inner)) => {
+                let mut fields = vec![];
+                for (key, value) in inner {
+                    let Some(key) = key.as_str() else {
+                        return Err("Positional field names must be strings".to_string().into());
+                    };
+                    if !key.starts_with('+') {
+                        fields.push((key.to_string(), build.child(Some(key), value)));
+                    }
+                }
+                AS3Validator::Positional { fields }
+            }
+            #[cfg(feature = "chrono")]
+            ("Date", serde_yaml::Value::Mapping(_)) => AS3Validator::Date {
+                after: yaml_config
+                    .get("+After")
+                    .and_then(|after| after.as_str())
+                    .map(|after| after.to_string()),
+                before: yaml_config
+                    .get("+Before")
+                    .and_then(|before| before.as_str())
+                    .map(|before| before.to_string()),
+            },
+            #[cfg(feature = "chrono")]
+            ("DateTime", serde_yaml::Value::Mapping(_)) => AS3Validator::DateTime {
+                require_timezone: yaml_config
+                    .get("+RequireTimezone")
+                    .and_then(|require| require.as_bool()),
+                utc_only: yaml_config
+                    .get("+UtcOnly")
+                    .and_then(|utc_only| utc_only.as_bool())
+                    .unwrap_or(false),
+            },
+            #[cfg(feature = "bigdecimal")]
+            ("BigNumber", serde_yaml::Value::Mapping(_)) => {
+                let bound = |name: &str| match yaml_config.get(name)? {
+                    serde_yaml::Value::String(bound) => Some(bound.clone()),
+                    serde_yaml::Value::Number(bound) => Some(bound.to_string()),
+                    _ => None,
+                };
+                AS3Validator::BigNumber {
+                    minimum: bound("minimum"),
+                    maximum: bound("maximum"),
+                }
+            }
+            ("Enum", serde_yaml::Value::Mapping(_)) => {
+                let Some(serde_yaml::Value::Sequence(values)) = yaml_config.get("+Values") else {
+                    return Err("Enum requires a +Values list".to_string().into());
+                };
+                let mut entries = vec![];
+                for value in values {
+                    // A mapping with a `+Type` is a nested validator, anything else a literal.
+                    let entry = match value.get("+Type") {
+                        Some(_) => build.child(None, value),
+                        None => AS3Validator::Const(const_value(value)?),
+                    };
+                    entries.push(entry);
+                }
+                AS3Validator::Enum(entries)
+            }
+            ("Const", serde_yaml::Value::Mapping(_)) => {
+                let Some(value) = yaml_config.get("+Value") else {
+                    return Err("Const requires a +Value".to_string().into());
+                };
+                AS3Validator::Const(const_value(value)?)
+            }
+            ("OneOf", serde_yaml::Value::Mapping(_)) => {
+                let Some(serde_yaml::Value::Sequence(branches)) = yaml_config.get("+OneOf") else {
+                    return Err("OneOf requires a +OneOf list".to_string().into());
+                };
+                AS3Validator::OneOf(
+                    branches
+                        .iter()
+                        .map(|branch| build.child(None, branch))
+                        .collect(),
+                )
+            }
+            ("AnyOf", serde_yaml::Value::Mapping(_)) => {
+                let Some(serde_yaml::Value::Sequence(branches)) = yaml_config.get("+AnyOf") else {
+                    return Err("AnyOf requires a +AnyOf list".to_string().into());
+                };
+                AS3Validator::AnyOf(
+                    branches
+                        .iter()
+                        .map(|branch| build.child(None, branch))
+                        .collect(),
+                )
+            }
+            ("AllOf", serde_yaml::Value::Mapping(_)) => {
+                let Some(serde_yaml::Value::Sequence(validators)) = yaml_config.get("+AllOf")
+                else {
+                    return Err("AllOf requires a +AllOf list".to_string().into());
+                };
+                AS3Validator::AllOf(
+                    validators
+                        .iter()
+                        .map(|validator| build.child(None, validator))
+                        .collect(),
+                )
+            }
+            ("Discriminated", serde_yaml::Value::Mapping(_)) => {
+                let (
+                    Some(serde_yaml::Value::String(key)),
+                    Some(serde_yaml::Value::Mapping(mapping)),
+                ) = (
+                    yaml_config.get("+Discriminator"),
+                    yaml_config.get("+Mapping"),
+                )
+                else {
+                    return Err("Discriminated requires a +Discriminator key and a +Mapping"
+                        .to_string()
+                        .into());
+                };
+                let mut variants = HashMap::new();
+                for (tag, validator) in mapping {
+                    let Some(tag) = tag.as_str() else {
+                        return Err("+Mapping keys must be strings".to_string().into());
+                    };
+                    variants.insert(tag.to_string(), build.child(None, validator));
+                }
+                AS3Validator::Discriminated {
+                    key: key.clone(),
+                    mapping: variants,
+                }
+            }
+            ("Ref", serde_yaml::Value::Mapping(_)) => match yaml_config.get("+Ref") {
+                Some(serde_yaml::Value::String(url)) => AS3Validator::Ref(url.clone()),
+                _ => return Err("Ref requires a +Ref URL".to_string().into()),
+            },
+            ("Any", serde_yaml::Value::Mapping(_)) => AS3Validator::Any,
+            _ => {
+                return Err(AS3SchemaError::UnknownType {
+                    name: validator_type.to_string(),
+                })
+            }
+        };
+
+        let annotations = Annotations::from_yaml(yaml_config, build)?;
+        if annotations == Annotations::default() {
+            return Ok(validator);
+        }
+        Ok(AS3Validator::Annotated {
+            validator: Arc::new(validator),
+            annotations,
+        })
+    }
+}
+/// Reports every mistake in a schema rather than only the first, each located by its
+/// path in the format of [`AS3Validator::walk`].
+impl TryFrom<&serde_yaml::Value> for AS3Validator {
+    type Error = Vec<AS3SchemaError>;
+
+    fn try_from(yaml_config: &serde_yaml::Value) -> Result<Self, Self::Error> {
+        AS3Validator::build(yaml_config, false)
+    }
+}
+
+impl AS3Validator {
+    /// Like [`AS3Validator::try_from`], but also reports every `+` directive the loader
+    /// does not know, and every other key a non-Object type does not read, so that a typo
+    /// such as `+Minimun` or `minimun` is not silently ignored.
+    pub fn try_from_strict(
+        yaml_config: &serde_yaml::Value,
+    ) -> Result<AS3Validator, Vec<AS3SchemaError>> {
+        AS3Validator::build(yaml_config, true)
+    }
+
+    fn build(
+        yaml_config: &serde_yaml::Value,
+        strict: bool,
+    ) -> Result<AS3Validator, Vec<AS3SchemaError>> {
+        let serde_yaml::Value::Mapping(inner) = yaml_config else {
+            return Err(vec!["Definition must start with a Yaml Mapping"
+                .to_string()
+                .into()]);
+        };
+        let Some(root) = inner.get("Root") else {
+            return Err(vec!["Missing root word from definition".to_string().into()]);
+        };
+
+        let mut build = SchemaBuild {
+            path: vec![],
+            errors: vec![],
+            strict,
+        };
+        let validator = build.child(None, root);
+        let mut errors = build.errors;
+        errors.sort_by(|a, b| a.path().cmp(b.path()));
+        // Placeholders for broken nodes accept anything, so the rest can still be checked.
+        errors.extend(validator.schema_errors());
+        if errors.is_empty() {
+            Ok(validator)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Mistakes found so far while building a schema, and where the builder is in it.
+struct SchemaBuild {
+    path: Vec<String>,
+    errors: Vec<AS3SchemaError>,
+    /// Whether unknown `+` directives are mistakes rather than ignored.
+    strict: bool,
+}
+
+impl SchemaBuild {
+    /// Builds a nested validator, recording its mistake and standing in `Any` for it so
+    /// that the rest of the schema is still built.
+    fn child(&mut self, segment: Option<&str>, yaml_config: &serde_yaml::Value) -> AS3Validator {
+        if let Some(segment) = segment {
+            self.path.push(segment.to_string());
+        }
+        if self.strict {
+            self.check_directives(yaml_config);
+        }
+        let validator = match AS3Validator::build_from_yaml(yaml_config, self) {
+            Ok(validator) => validator,
+            Err(error) => {
+                self.errors.push(error.at(&self.path));
+                AS3Validator::Any
+            }
+        };
+        if segment.is_some() {
+            self.path.pop();
+        }
+        validator
+    }
+
+    /// Converts a `+Examples` list, recording each entry that is not a literal value.
+    fn examples(&mut self, examples: Option<&serde_yaml::Value>) -> Vec<AS3Data> {
+        let examples = match examples {
+            None => return vec![],
+            Some(serde_yaml::Value::Sequence(examples)) => examples,
+            Some(_) => {
+                let path = [self.path.as_slice(), &["+Examples".to_string()]].concat();
+                self.errors
+                    .push(AS3SchemaError::from("+Examples must be a list".to_string()).at(&path));
+                return vec![];
+            }
+        };
+        let mut converted = vec![];
+        for (index, example) in examples.iter().enumerate() {
+            match const_value(example) {
+                Ok(example) => converted.push(example),
+                Err(error) => {
+                    let path = [
+                        self.path.as_slice(),
+                        &["+Examples".to_string(), index.to_string()],
+                    ]
+                    .concat();
+                    self.errors.push(AS3SchemaError::from(error).at(&path));
+                }
+            }
+        }
+        converted
+    }
+
+    /// Reads the bound under `key` with `read`, recording a value of the wrong type at
+    /// the key rather than leaving the validator unbounded.
+    fn number<T>(
+        &mut self,
+        yaml_config: &serde_yaml::Value,
+        key: &str,
+        expected: &str,
+        read: impl Fn(&serde_yaml::Value) -> Option<T>,
+    ) -> Option<T> {
+        let value = yaml_config.get(key).filter(|value| !value.is_null())?;
+        let number = read(value);
+        if number.is_none() {
+            let path = [self.path.as_slice(), &[key.to_string()]].concat();
+            self.errors
+                .push(AS3SchemaError::from(format!("`{key}` must be {expected}")).at(&path));
+        }
+        number
+    }
+
+    fn check_directives(&mut self, yaml_config: &serde_yaml::Value) {
+        let Some(inner) = yaml_config.as_mapping() else {
+            return;
+        };
+        let validator_type = yaml_config.get("+Type").and_then(|name| name.as_str());
+        let plain_keys = plain_keys(validator_type.unwrap_or_default());
+        for key in inner.keys().filter_map(|key| key.as_str()) {
+            let known = match (key.starts_with('+'), plain_keys) {
+                (true, _) => DIRECTIVES.contains(&key),
+                (false, Some(plain_keys)) => plain_keys.contains(&key),
+                // The other keys of an Object or Positional are its fields.
+                (false, None) => true,
+            };
+            if !known {
+                let path = [self.path.as_slice(), &[key.to_string()]].concat();
+                self.errors.push(
+                    AS3SchemaError::UnknownDirective {
+                        name: key.to_string(),
+                    }
+                    .at(&path),
+                );
+            }
+        }
+    }
+}
+
+/// The keys without a `+` that the YAML loader reads for `validator_type`, or `None` for
+/// the types whose other keys are their fields.
+fn plain_keys(validator_type: &str) -> Option<&'static [&'static str]> {
+    Some(match validator_type {
+        "Object" | "Positional" => return None,
+        "Integer" => &["minimum", "maximum", "step", "stepOrigin"],
+        "Decimal" => &["minimum", "maximum", "maxDecimalPlaces"],
+        "NumericString" => &["formatMask"],
+        "BigNumber" => &["minimum", "maximum"],
+        _ => &[],
+    })
+}
+
+/// Every `+` directive the YAML loader reads, on any type.
+const DIRECTIVES: &[&str] = &[
+    "+AdditionalProperties",
+    "+After",
+    "+AllOf",
+    "+AnyOf",
+    "+Before",
+    "+Case",
+    "+DependentRequired",
+    "+Deprecated",
+    "+Description",
+    "+Discriminator",
+    "+Examples",
+    "+Flatten",
+    "+Format",
+    "+Grammar",
+    "+Items",
+    "+KeyType",
+    "+Mapping",
+    "+MaxBytes",
+    "+MaxItems",
+    "+Mask",
+    "+MaxLength",
+    "+MinBytes",
+    "+MinLength",
+    "+NoSurroundingWhitespace",
+    "+NonEmpty",
+    "+Normalize",
+    "+OneOf",
+    "+Optional",
+    "+PropertyNames",
+    "+ReadOnly",
+    "+Ref",
+    "+Regex",
+    "+RequireSortedKeys",
+    "+RequireTimezone",
+    "+Rest",
+    "+Rules",
+    "+Title",
+    "+Type",
+    "+UtcOnly",
+    "+Value",
+    "+ValueType",
+    "+Values",
+    "+WhenPresent",
+    "+WriteOnly",
+];
+
+/// A mistake in a schema, reported when it is loaded rather than when data is validated.
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum AS3SchemaError {
+    #[error("Regex `{}` does not compile: {}" , .regex, .message)]
+    InvalidRegex {
+        path: Vec<String>,
+        regex: String,
+        message: String,
+    },
+    #[error("Unsupported type `{}`" , .name)]
+    UnknownType { name: String },
+    /// A `+` directive the loader does not know, or a key the type does not read,
+    /// reported by [`AS3Validator::try_from_strict`].
+    #[error("Unknown directive `{}`" , .name)]
+    UnknownDirective { name: String },
+    /// Constraints that exclude every possible value, such as an inverted range.
+    #[error("{}" , .message)]
+    Contradiction { path: Vec<String>, message: String },
+    /// Any other malformed schema: bad YAML, a missing directive, an unreadable include.
+    #[error("{}" , .0)]
+    Invalid(String),
+    #[error("{} at `{}`" , .error, .path.join("."))]
+    AtPath {
+        path: Vec<String>,
+        error: Box<AS3SchemaError>,
+    },
+}
+
+impl AS3SchemaError {
+    /// Where in the schema the mistake is, in the format of [`AS3Validator::walk`].
+    pub fn path(&self) -> &[String] {
+        match self {
+            AS3SchemaError::InvalidRegex { path, .. }
+            | AS3SchemaError::Contradiction { path, .. }
+            | AS3SchemaError::AtPath { path, .. } => path,
+            _ => &[],
+        }
+    }
+
+    /// Strips the location added by [`AS3Validator::try_from`], returning the bare error.
+    pub fn without_path(self) -> AS3SchemaError {
+        match self {
+            AS3SchemaError::AtPath { error, .. } => *error,
+            error => error,
+        }
+    }
+
+    /// Locates an error that does not carry its own path.
+    fn at(self, path: &[String]) -> AS3SchemaError {
+        if path.is_empty() || !self.path().is_empty() {
+            return self;
+        }
+        AS3SchemaError::AtPath {
+            path: path.to_vec(),
+            error: Box::new(self),
+        }
+    }
+}
+
+impl From<String> for AS3SchemaError {
+    fn from(message: String) -> Self {
+        AS3SchemaError::Invalid(message)
+    }
+}
+
+/// New variants are added as validators grow, so match on [`AS3ValidationError::kind`]
+/// or [`AS3ValidationError::code`] where a wildcard arm would hide them.
+#[derive(Error, Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum AS3ValidationError {
+    #[error("Mismatched types. Expected `{}` got `{}`{} . " , .expected , .got, fmt_preview(.preview))]
+    TypeError {
+        expected: &'static str,
+        got: &'static str,
+        /// Short rendering of the offending value, for scalars only.
+        preview: Option<String>,
+    },
+    /// An object where a list was expected, a scalar where a container was expected, etc.
+    #[error("Expected {} but found {} . " , .expected , .found)]
+    ShapeMismatch {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("Key {} is not in " , .key )]
+    MissingKey { key: String },
+    #[error("Key `{}` is not allowed here . " , .key)]
+    UnknownKey { key: String },
+
+    #[error("Key `{}` should come before `{}` . " , .key, .previous)]
+    UnsortedKey { key: String, previous: String },
+    #[error("Because `{}` is {}, `{}` is invalid: {}" , .when_key, .when_value, .then_key, .error)]
+    RuleViolation {
+        when_key: String,
+        when_value: String,
+        then_key: String,
+        error: Box<AS3ValidationError>,
+    },
+    #[error("Key `{}` is required when `{}` is present . " , .required, .trigger)]
+    DependencyMissing { trigger: String, required: String },
+    #[error("Word {} is not following the `{}` regex{} " , .word, .regex, fmt_position(.position))]
+    RegexError {
+        word: String,
+        regex: String,
+        /// For regexes anchored with `^`, the character offset where the word stopped
+        /// matching, or its length if it ended too early.
+        position: Option<usize>,
+    },
+    #[error("Word {} does not parse as `{}` from character {} . " , .word, .grammar, .position)]
+    GrammarError {
+        word: String,
+        grammar: String,
+        /// The character offset where the grammar's parser gave up.
+        position: usize,
+    },
+    /// A String validator names a grammar missing from [`ValidationOptions::grammars`].
+    #[error("Grammar `{}` is not registered . " , .grammar)]
+    UnknownGrammar { grammar: String },
+
+    /// A String validator built in code with a regex that does not compile, which loading
+    /// a schema would have rejected.
+    #[error("Regex `{}` does not compile, so no value can match . " , .regex)]
+    UnusableRegex { regex: String },
+
+    #[error(" `{}` is under the minumum of `{}` . " , fmt_number(*.number) , fmt_number(*.minimum))]
+    Minimum { number: f64, minimum: f64 },
+
+    #[error(" `{}` is over the maximum of `{}` . " , fmt_number(*.number) , fmt_number(*.maximum))]
+    Maximum { number: f64, maximum: f64 },
+
+    /// [`AS3ValidationError::Minimum`] for an Integer, used when a value is beyond 2^53
+    /// and so would be misreported as an `f64`.
+    #[error(" `{}` is under the minumum of `{}` . " , .number , .minimum)]
+    IntegerMinimum { number: i64, minimum: i64 },
+
+    /// [`AS3ValidationError::Maximum`] for an Integer beyond 2^53.
+    #[error(" `{}` is over the maximum of `{}` . " , .number , .maximum)]
+    IntegerMaximum { number: i64, maximum: i64 },
+
+    #[error("Value{} is not one of the allowed values . " , fmt_preview(.preview))]
+    NotInEnum { preview: Option<String> },
+
+    #[error(" `{}` is not a multiple of `{}` away from `{}` . " , .number, .step, .origin)]
+    NotOnStep { number: i64, step: i64, origin: i64 },
+
+    #[error("Value{} is not {} . " , fmt_preview(.preview), .expected)]
+    NotConst {
+        expected: String,
+        preview: Option<String>,
+    },
+
+    #[error("Key `{}` is not a valid property name: {}" , .key, .error)]
+    PropertyName {
+        key: String,
+        error: Box<AS3ValidationError>,
+    },
+
+    #[error("`{}` has more than {} decimal places . " , fmt_number(*.value), .max)]
+    TooManyDecimals { value: f64, max: u32 },
+
+    #[error("Unable to parse the data: {}" , .message)]
+    ParseError { message: String },
+
+    #[error("Expected a tuple of {} elements, got {} . " , .expected, .got)]
+    TupleLength { expected: usize, got: usize },
+
+    #[error("List of {} elements is longer than the maximum of {} . " , .length, .max)]
+    ListTooLong { length: usize, max: usize },
+
+    #[error("`{}` is not a valid {} . " , .value, .format)]
+    FormatError { value: String, format: &'static str },
+
+    #[error("Content of {} bytes is outside the allowed range {} to {} . " , .length, fmt_bound(.min), fmt_bound(.max))]
+    ByteLength {
+        length: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+
+    #[error("String of length {} is outside the allowed range {} to {} . " , .length, fmt_bound(.min), fmt_bound(.max))]
+    StringLength {
+        length: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+
+    #[error("Word {} is not {}case . " , .word, .case)]
+    CaseError { word: String, case: &'static str },
+
+    #[error("Word {:?} has leading or trailing whitespace . " , .word)]
+    SurroundingWhitespace { word: String },
+
+    #[error("String must not be empty . ")]
+    EmptyString,
+
+    /// A list or object marked `non_empty` without any elements or keys.
+    #[error("Expected a non-empty {} . " , .collection)]
+    EmptyCollection { collection: &'static str },
+
+    #[error("Date `{}` is outside the range {} to {} . " , .date, .after.as_deref().unwrap_or("-"), .before.as_deref().unwrap_or("-"))]
+    DateRange {
+        date: String,
+        after: Option<String>,
+        before: Option<String>,
+    },
+
+    /// A [`AS3Validator::NumericString`] that does not fill its `format_mask`, such as
+    /// `42` for `000000`.
+    #[error("Value `{}` does not match the mask `{}` . " , .value, .mask)]
+    MaskMismatch { value: String, mask: String },
+
+    /// The pointer passed to [`AS3Validator::validate_pointer`] leads to no value.
+    #[error("Pointer `{}` does not lead to a value in the data . " , .pointer)]
+    PointerNotFound { pointer: String },
+
+    /// [`AS3Validator::validate_pointer`] reached a `OneOf`, `AnyOf` or `Enum` where no
+    /// alternative accepts the data and more than one, or none, describes the next segment.
+    #[error("No single alternative of the schema describes `{}` . " , .segment)]
+    AmbiguousPointer { segment: String },
+
+    /// A local time where [`AS3Validator::DateTime`] requires an offset.
+    #[error("Datetime `{}` has no timezone offset . " , .value)]
+    MissingTimezone { value: String },
+
+    #[error("Datetime `{}` must not carry a timezone offset . " , .value)]
+    UnexpectedTimezone { value: String },
+
+    #[error("Datetime `{}` is not in UTC (`Z`) . " , .value)]
+    NotUtc { value: String },
+
+    #[error("`{}` is outside the range {} to {} . " , .value, .minimum.as_deref().unwrap_or("-"), .maximum.as_deref().unwrap_or("-"))]
+    NumberRange {
+        value: String,
+        minimum: Option<String>,
+        maximum: Option<String>,
+    },
+
+    #[error("`{}` does not fit in a 64-bit number . " , .value)]
+    NumberOutOfRange { value: String },
+
+    #[error("Discriminator `{}`{} does not name a known variant . " , .key, fmt_preview(.preview))]
+    UnknownDiscriminator {
+        key: String,
+        preview: Option<String>,
+    },
+
+    #[error("Value does not match any of the alternatives{} . " , fmt_closest(.closest, .errors))]
+    NoBranchMatched {
+        /// Index of the branch that came closest to accepting the value, if there were any.
+        closest: Option<usize>,
+        /// The errors reported by the closest branch.
+        errors: Vec<AS3ValidationError>,
+    },
+
+    #[error("Value matches {} alternatives instead of exactly one . " , .count)]
+    MultipleBranchesMatched { count: usize },
+
+    #[error("Field is read-only and cannot be sent in a request")]
+    ReadOnlyViolation,
+
+    #[error("Field is write-only and cannot be returned in a response")]
+    WriteOnlyViolation,
+
+    #[error("Field is deprecated")]
+    Deprecated,
+
+    #[error("Data is nested deeper than {} levels . " , .depth)]
+    MaxDepthExceeded { depth: usize },
+
+    #[error("Validation did not finish within {:?} . " , .timeout)]
+    Timeout { timeout: std::time::Duration },
+
+    #[error("Unable to use the schema at `{}`: {} . " , .url, .reason)]
+    RefUnavailable { url: String, reason: String },
+
+    #[error("{} at `{}`" , .error, .path.join("."))]
+    AtPath {
+        path: Vec<String>,
+        error: Box<AS3ValidationError>,
+    },
+}
+
+/// Serde default for [`AS3Validator::Object`]'s `additional_properties`.
+fn allowed() -> bool {
+    true
+}
+
+fn fmt_bound(bound: &Option<usize>) -> String {
+    match bound {
+        Some(bound) => bound.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// Renders a number without float noise, e.g. `0.1 + 0.2` as `0.3`, by rounding it to
+/// 15 significant digits: the most an `f64` holds exactly.
+pub(crate) fn fmt_number(number: f64) -> String {
+    match format!("{number:.14e}").parse::<f64>() {
+        Ok(rounded) if number.is_finite() => rounded.to_string(),
+        _ => number.to_string(),
+    }
+}
+
+fn fmt_closest(closest: &Option<usize>, errors: &[AS3ValidationError]) -> String {
+    match (closest, errors.first()) {
+        (Some(closest), Some(error)) => format!("; closest is #{closest}: {error}"),
+        _ => String::new(),
+    }
+}
+
+fn fmt_position(position: &Option<usize>) -> String {
+    match position {
+        Some(position) => format!(" from character {position}"),
+        None => String::new(),
+    }
+}
+
+fn fmt_preview(preview: &Option<String>) -> String {
+    match preview {
+        Some(preview) => format!(" ({preview})"),
+        None => String::new(),
+    }
+}
+
+/// Broad category of an [`AS3ValidationError`], from [`AS3ValidationError::kind`].
+///
+/// Unlike the errors themselves this list is not expected to grow: new errors join the
+/// category they belong to, so a `match` over it keeps compiling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The value is of the wrong type or shape.
+    Type,
+    /// Keys or elements are missing, unexpected or misnamed.
+    Structure,
+    /// A number, length or date is out of bounds.
+    Range,
+    /// A string does not have the required form.
+    Format,
+    /// The value is none of the allowed values or alternatives.
+    Choice,
+    /// The field may not travel in this direction, or should not be used.
+    Access,
+    /// The input could not be parsed at all.
+    Parse,
+    /// The data exceeds a limit that guards the validator itself, or a schema it needs
+    /// could not be fetched.
+    Limit,
+}
+
+impl AS3ValidationError {
+    /// The category of the error, looking through its location.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            AS3ValidationError::TypeError { .. } | AS3ValidationError::ShapeMismatch { .. } => {
+                ErrorKind::Type
+            }
+            AS3ValidationError::MissingKey { .. }
+            | AS3ValidationError::UnknownKey { .. }
+            | AS3ValidationError::UnsortedKey { .. }
+            | AS3ValidationError::DependencyMissing { .. }
+            | AS3ValidationError::PointerNotFound { .. }
+            | AS3ValidationError::AmbiguousPointer { .. }
+            | AS3ValidationError::PropertyName { .. }
+            | AS3ValidationError::TupleLength { .. } => ErrorKind::Structure,
+            AS3ValidationError::Minimum { .. }
+            | AS3ValidationError::Maximum { .. }
+            | AS3ValidationError::IntegerMinimum { .. }
+            | AS3ValidationError::IntegerMaximum { .. }
+            | AS3ValidationError::NotOnStep { .. }
+            | AS3ValidationError::TooManyDecimals { .. }
+            | AS3ValidationError::ListTooLong { .. }
+            | AS3ValidationError::EmptyCollection { .. }
+            | AS3ValidationError::StringLength { .. }
+            | AS3ValidationError::ByteLength { .. }
+            | AS3ValidationError::DateRange { .. }
+            | AS3ValidationError::NumberRange { .. }
+            | AS3ValidationError::NumberOutOfRange { .. } => ErrorKind::Range,
+            AS3ValidationError::RegexError { .. }
+            | AS3ValidationError::GrammarError { .. }
+            | AS3ValidationError::UnknownGrammar { .. }
+            | AS3ValidationError::UnusableRegex { .. }
+            | AS3ValidationError::FormatError { .. }
+            | AS3ValidationError::MaskMismatch { .. }
+            | AS3ValidationError::MissingTimezone { .. }
+            | AS3ValidationError::UnexpectedTimezone { .. }
+            | AS3ValidationError::NotUtc { .. }
+            | AS3ValidationError::CaseError { .. }
+            | AS3ValidationError::SurroundingWhitespace { .. }
+            | AS3ValidationError::EmptyString => ErrorKind::Format,
+            AS3ValidationError::NotInEnum { .. }
+            | AS3ValidationError::NotConst { .. }
+            | AS3ValidationError::UnknownDiscriminator { .. }
+            | AS3ValidationError::NoBranchMatched { .. }
+            | AS3ValidationError::MultipleBranchesMatched { .. } => ErrorKind::Choice,
+            AS3ValidationError::ReadOnlyViolation
+            | AS3ValidationError::WriteOnlyViolation
+            | AS3ValidationError::Deprecated => ErrorKind::Access,
+            AS3ValidationError::ParseError { .. } => ErrorKind::Parse,
+            AS3ValidationError::MaxDepthExceeded { .. }
+            | AS3ValidationError::Timeout { .. }
+            | AS3ValidationError::RefUnavailable { .. } => ErrorKind::Limit,
+            AS3ValidationError::RuleViolation { error, .. }
+            | AS3ValidationError::AtPath { error, .. } => error.kind(),
+        }
+    }
+
+    /// Stable identifier of the kind of error, as used by [`ValidationOptions::soft_errors`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            AS3ValidationError::TypeError { .. } | AS3ValidationError::ShapeMismatch { .. } => {
+                "type_error"
+            }
+            AS3ValidationError::MissingKey { .. } => "missing_key",
+            AS3ValidationError::UnknownKey { .. } => "unknown_key",
+            AS3ValidationError::UnsortedKey { .. } => "unsorted_key",
+            AS3ValidationError::DependencyMissing { .. } => "dependency_missing",
+            AS3ValidationError::RuleViolation { .. } => "rule",
+            AS3ValidationError::RegexError { .. } => "regex",
+            AS3ValidationError::GrammarError { .. } => "grammar",
+            AS3ValidationError::UnknownGrammar { .. } => "unknown_grammar",
+            AS3ValidationError::UnusableRegex { .. } => "invalid_regex",
+            AS3ValidationError::Minimum { .. } | AS3ValidationError::IntegerMinimum { .. } => {
+                "minimum"
+            }
+            AS3ValidationError::Maximum { .. } | AS3ValidationError::IntegerMaximum { .. } => {
+                "maximum"
+            }
+            AS3ValidationError::NotInEnum { .. } => "enum",
+            AS3ValidationError::NotOnStep { .. } => "step",
+            AS3ValidationError::NotConst { .. } => "const",
+            AS3ValidationError::UnknownDiscriminator { .. } => "unknown_discriminator",
+            AS3ValidationError::NoBranchMatched { .. } => "no_branch_matched",
+            AS3ValidationError::MultipleBranchesMatched { .. } => "multiple_branches_matched",
+            AS3ValidationError::PropertyName { .. } => "property_name",
+            AS3ValidationError::TooManyDecimals { .. } => "too_many_decimals",
+            AS3ValidationError::ParseError { .. } => "parse",
+            AS3ValidationError::TupleLength { .. } => "tuple_length",
+            AS3ValidationError::ListTooLong { .. } => "list_too_long",
+            AS3ValidationError::FormatError { .. } => "format",
+            AS3ValidationError::StringLength { .. } => "string_length",
+            AS3ValidationError::ByteLength { .. } => "byte_length",
+            AS3ValidationError::CaseError { .. } => "case",
+            AS3ValidationError::SurroundingWhitespace { .. } => "whitespace",
+            AS3ValidationError::EmptyString => "empty",
+            AS3ValidationError::EmptyCollection { .. } => "empty_collection",
+            AS3ValidationError::DateRange { .. } => "date_range",
+            AS3ValidationError::MaskMismatch { .. } => "mask_mismatch",
+            AS3ValidationError::PointerNotFound { .. } => "pointer_not_found",
+            AS3ValidationError::AmbiguousPointer { .. } => "ambiguous_pointer",
+            AS3ValidationError::MissingTimezone { .. } => "missing_timezone",
+            AS3ValidationError::UnexpectedTimezone { .. } => "unexpected_timezone",
+            AS3ValidationError::NotUtc { .. } => "not_utc",
+            AS3ValidationError::NumberRange { .. } => "number_range",
+            AS3ValidationError::NumberOutOfRange { .. } => "number_out_of_range",
+            AS3ValidationError::ReadOnlyViolation => "read_only",
+            AS3ValidationError::WriteOnlyViolation => "write_only",
+            AS3ValidationError::Deprecated => "deprecated",
+            AS3ValidationError::MaxDepthExceeded { .. } => "max_depth",
+            AS3ValidationError::Timeout { .. } => "timeout",
+            AS3ValidationError::RefUnavailable { .. } => "ref_unavailable",
+            AS3ValidationError::AtPath { error, .. } => error.code(),
+        }
+    }
+
+    /// Object keys and list indices leading from the root of the data to the error.
+    pub fn path(&self) -> &[String] {
+        match self {
+            AS3ValidationError::AtPath { path, .. } => path,
+            _ => &[],
+        }
+    }
+
+    /// Strips the location, returning the bare error.
+    pub fn without_path(self) -> AS3ValidationError {
+        match self {
+            AS3ValidationError::AtPath { error, .. } => *error,
+            error => error,
+        }
+    }
+
+    /// Location of the error as an RFC 6901 JSON Pointer, e.g. `/vehicles/list/1/maker`.
+    pub fn json_pointer(&self) -> Option<String> {
+        let AS3ValidationError::AtPath { path, .. } = self else {
+            return None;
+        };
+        Some(
+            path.iter()
+                .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+                .collect(),
+        )
+    }
+}
+
+/// Groups errors, such as those from [`AS3Validator::validate_all`], by the dotted path of
+/// the field they are about, so all problems with a field can be shown together.
+///
+/// Errors keep their order within a field and lose their location, which the key already
+/// gives. Errors about the document root are grouped under the empty path.
+pub fn group_by_path(errors: Vec<AS3ValidationError>) -> HashMap<String, Vec<AS3ValidationError>> {
+    let mut groups: HashMap<String, Vec<AS3ValidationError>> = HashMap::new();
+    for error in errors {
+        let path = error.path().join(".");
+        groups.entry(path).or_default().push(error.without_path());
+    }
+    groups
+}
+
+#[cfg(test)]
+#[path = "integration_test.rs"]
+mod test;
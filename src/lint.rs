@@ -0,0 +1,279 @@
+use std::fmt;
+
+use regex::Regex;
+
+use crate::{AS3Data, AS3SchemaError, AS3ValidationError, AS3Validator};
+
+/// A contradictory or redundant constraint found by [`AS3Validator::lint`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintWarning {
+    /// Where the constraint sits, in the format of [`AS3Validator::walk`].
+    pub path: Vec<String>,
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "Root: {}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path.join("/"), self.message)
+        }
+    }
+}
+
+impl AS3Validator {
+    /// Statically checks the schema for constraints no data can satisfy, or that repeat
+    /// themselves. Warnings are sorted by path.
+    ///
+    /// Schemas loaded from YAML have already been rejected for the problems reported as
+    /// [`AS3SchemaError`]s, so this mostly matters for validators built in code.
+    pub fn lint(&self) -> Vec<LintWarning> {
+        let mut warnings = vec![];
+        self.walk(|node, path| {
+            let errors = schema_errors(node, path)
+                .into_iter()
+                .map(|error| match error {
+                    AS3SchemaError::Contradiction { message, .. } => message,
+                    error => error.to_string(),
+                });
+            for message in errors.chain(lint_node(node)) {
+                warnings.push(LintWarning {
+                    path: path.to_vec(),
+                    message,
+                });
+            }
+        });
+        warnings.sort_by(|a, b| a.path.cmp(&b.path));
+        warnings
+    }
+
+    /// Validates the `+Examples` of every annotated validator against that validator, to
+    /// catch examples that went stale as the schema changed.
+    ///
+    /// Each error is located at the example, as the validator's path followed by
+    /// `+Examples` and the example's index, then by the path inside the example.
+    pub fn check_examples(&self) -> Result<(), Vec<AS3ValidationError>> {
+        let mut errors = vec![];
+        self.walk(|node, path| {
+            let AS3Validator::Annotated { annotations, .. } = node else {
+                return;
+            };
+            for (index, example) in annotations.examples.iter().enumerate() {
+                for error in node.validate_all(example).err().unwrap_or_default() {
+                    let mut located = path.to_vec();
+                    located.extend(["+Examples".to_string(), index.to_string()]);
+                    located.extend(error.path().iter().cloned());
+                    errors.push(AS3ValidationError::AtPath {
+                        path: located,
+                        error: Box::new(error.without_path()),
+                    });
+                }
+            }
+        });
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Constraints that cannot work at all, which loading a schema rejects: regexes that
+    /// do not compile and bounds that exclude every value. Sorted by path.
+    pub(crate) fn schema_errors(&self) -> Vec<AS3SchemaError> {
+        let mut errors = vec![];
+        self.walk(|node, path| errors.extend(schema_errors(node, path)));
+        errors.sort_by(|a, b| a.path().cmp(b.path()));
+        errors
+    }
+}
+
+fn schema_errors(node: &AS3Validator, path: &[String]) -> Vec<AS3SchemaError> {
+    let mut errors = vec![];
+    let mut inverted = |what: &str, minimum: &dyn fmt::Display, maximum: &dyn fmt::Display| {
+        errors.push(AS3SchemaError::Contradiction {
+            path: path.to_vec(),
+            message: format!("{what} {minimum} is greater than {maximum}, so no value can match"),
+        })
+    };
+    match node {
+        AS3Validator::String {
+            regex,
+            min_length,
+            max_length,
+            min_bytes,
+            max_bytes,
+            ..
+        } => {
+            if let Some((min, max)) = min_length.zip(*max_length).filter(|(min, max)| min > max) {
+                inverted("minimum length", &min, &max);
+            }
+            if let Some((min, max)) = min_bytes.zip(*max_bytes).filter(|(min, max)| min > max) {
+                inverted("minimum size in bytes", &min, &max);
+            }
+            if let Some(regex) = regex {
+                if let Err(error) = Regex::new(regex) {
+                    errors.push(AS3SchemaError::InvalidRegex {
+                        path: path.to_vec(),
+                        regex: regex.clone(),
+                        message: error.to_string(),
+                    });
+                }
+            }
+            #[cfg(not(feature = "unicode-normalization"))]
+            if let AS3Validator::String {
+                normalize: Some(form),
+                ..
+            } = node
+            {
+                errors.push(
+                    AS3SchemaError::from(format!(
+                        "normalizing to {} requires the `unicode-normalization` feature",
+                        form.name()
+                    ))
+                    .at(path),
+                );
+            }
+        }
+        AS3Validator::Integer {
+            minimum,
+            maximum,
+            step,
+            ..
+        } => {
+            if let Some((min, max)) = minimum.zip(*maximum).filter(|(min, max)| min > max) {
+                inverted("minimum", &min, &max);
+            }
+            if let Some(step) = step.filter(|step| *step <= 0) {
+                errors.push(
+                    AS3SchemaError::from(format!("step must be positive, not {step}")).at(path),
+                );
+            }
+        }
+        AS3Validator::Decimal {
+            minimum, maximum, ..
+        } => {
+            if let Some((min, max)) = minimum.zip(*maximum).filter(|(min, max)| min > max) {
+                inverted("minimum", &min, &max);
+            }
+        }
+        #[cfg(feature = "chrono")]
+        AS3Validator::Date { after, before } => {
+            let parse = |bound: &str| chrono::NaiveDate::parse_from_str(bound, "%Y-%m-%d").ok();
+            if let Some((after, before)) = after.as_deref().zip(before.as_deref()) {
+                if parse(after)
+                    .zip(parse(before))
+                    .is_some_and(|(after, before)| after > before)
+                {
+                    inverted("after date", &after, &before);
+                }
+            }
+            for (name, bound) in [("+After", after), ("+Before", before)] {
+                if let Some(bound) = bound.as_deref().filter(|bound| parse(bound).is_none()) {
+                    errors.push(
+                        AS3SchemaError::from(format!(
+                            "`{name}` must be a YYYY-MM-DD date, not `{bound}`"
+                        ))
+                        .at(path),
+                    );
+                }
+            }
+        }
+        #[cfg(feature = "bigdecimal")]
+        AS3Validator::BigNumber { minimum, maximum } => {
+            use std::str::FromStr;
+
+            let parse = |bound: &str| bigdecimal::BigDecimal::from_str(bound).ok();
+            if let Some((minimum, maximum)) = minimum.as_deref().zip(maximum.as_deref()) {
+                if parse(minimum)
+                    .zip(parse(maximum))
+                    .is_some_and(|(min, max)| min > max)
+                {
+                    inverted("minimum", &minimum, &maximum);
+                }
+            }
+            for (name, bound) in [("minimum", minimum), ("maximum", maximum)] {
+                if let Some(bound) = bound.as_deref().filter(|bound| parse(bound).is_none()) {
+                    errors.push(
+                        AS3SchemaError::from(format!("`{name}` must be a number, not `{bound}`"))
+                            .at(path),
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+    errors
+}
+
+fn lint_node(node: &AS3Validator) -> Vec<String> {
+    let mut messages = vec![];
+    match node {
+        AS3Validator::Object {
+            properties,
+            property_names: Some(property_names),
+            ..
+        } => {
+            let mut keys: Vec<_> = properties
+                .keys()
+                .filter(|key| !key.starts_with('+'))
+                .filter(|key| {
+                    property_names
+                        .validate(&AS3Data::String(key.to_string()))
+                        .is_err()
+                })
+                .collect();
+            keys.sort();
+            for key in keys {
+                messages.push(format!(
+                    "property `{key}` is declared but rejected by +PropertyNames"
+                ));
+            }
+        }
+        AS3Validator::Enum(values) => {
+            if values.is_empty() {
+                messages.push("enum has no values, so no value can match".to_string());
+            }
+            for (index, value) in values.iter().enumerate() {
+                if values[..index].contains(value) {
+                    let value: &dyn fmt::Debug = match value {
+                        AS3Validator::Const(value) => value,
+                        value => value,
+                    };
+                    messages.push(format!("enum lists {value:?} more than once"));
+                }
+            }
+        }
+        #[cfg(feature = "chrono")]
+        AS3Validator::DateTime {
+            require_timezone: Some(false),
+            utc_only: true,
+        } => {
+            messages.push(
+                "DateTime forbids a timezone but is +UtcOnly, so no value can match".to_string(),
+            );
+        }
+        AS3Validator::Discriminated { mapping, .. } if mapping.is_empty() => {
+            messages.push("Discriminated has no mapping, so no value can match".to_string());
+        }
+        AS3Validator::OneOf(branches) if branches.is_empty() => {
+            messages.push("OneOf has no branches, so no value can match".to_string());
+        }
+        AS3Validator::AnyOf(branches) if branches.is_empty() => {
+            messages.push("AnyOf has no branches, so no value can match".to_string());
+        }
+        AS3Validator::AllOf(validators) if validators.is_empty() => {
+            messages.push("AllOf has no validators, so it accepts anything".to_string());
+        }
+        AS3Validator::Annotated { annotations, .. }
+            if annotations.read_only && annotations.write_only =>
+        {
+            messages.push(
+                "field is both read-only and write-only, so it can never be sent or returned"
+                    .to_string(),
+            );
+        }
+        _ => {}
+    }
+    messages
+}
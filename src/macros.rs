@@ -0,0 +1,83 @@
+/// Builds an [`AS3Validator`](crate::AS3Validator) from a compact, `json!`-like syntax.
+///
+/// Objects are written as `{ key: validator, .. }`, lists as `[validator]`, and scalar
+/// validators take their constraints as named arguments matching the variant's fields
+/// (`min` and `max` are accepted as shorthands for `minimum` and `maximum`).
+///
+/// ```
+/// use appcovecompiler::as3_validator;
+///
+/// let validator = as3_validator!({
+///     age: Integer(min = 20),
+///     name: String(regex = "^[A-Z]"),
+///     vehicles: [{ maker: String, year: Integer }],
+///     "first name": String,
+/// });
+/// ```
+#[macro_export]
+macro_rules! as3_validator {
+    ({ $($key:tt : $head:tt $(($($args:tt)*))?),* $(,)? }) => {
+        $crate::AS3Validator::object::<::std::string::String>([
+            $((
+                $crate::as3_validator!(@key $key),
+                $crate::as3_validator!($head $(($($args)*))?),
+            )),*
+        ])
+    };
+    ([ $($item:tt)+ ]) => {
+        $crate::AS3Validator::list_of($crate::as3_validator!($($item)+))
+    };
+    (String $(($($arg:ident = $value:expr),* $(,)?))?) => {{
+        #[allow(unused_mut)]
+        let mut validator = $crate::AS3Validator::any_string();
+        $($($crate::as3_validator!(@set String validator $arg $value);)*)?
+        validator
+    }};
+    (Integer $(($($arg:ident = $value:expr),* $(,)?))?) => {{
+        #[allow(unused_mut)]
+        let mut validator = $crate::AS3Validator::any_integer();
+        $($($crate::as3_validator!(@set Integer validator $arg $value);)*)?
+        validator
+    }};
+    (Decimal $(($($arg:ident = $value:expr),* $(,)?))?) => {{
+        #[allow(unused_mut)]
+        let mut validator = $crate::AS3Validator::any_decimal();
+        $($($crate::as3_validator!(@set Decimal validator $arg $value);)*)?
+        validator
+    }};
+    (Any) => {
+        $crate::AS3Validator::Any
+    };
+    (@set String $validator:ident regex $value:expr) => {
+        if let $crate::AS3Validator::String { regex, .. } = &mut $validator {
+            *regex = Some(::std::string::String::from($value));
+        }
+    };
+    (@set String $validator:ident no_surrounding_whitespace $value:expr) => {
+        if let $crate::AS3Validator::String { no_surrounding_whitespace, .. } = &mut $validator {
+            *no_surrounding_whitespace = $value;
+        }
+    };
+    (@set String $validator:ident non_empty $value:expr) => {
+        if let $crate::AS3Validator::String { non_empty, .. } = &mut $validator {
+            *non_empty = $value;
+        }
+    };
+    (@set $variant:ident $validator:ident min $value:expr) => {
+        $crate::as3_validator!(@set $variant $validator minimum $value)
+    };
+    (@set $variant:ident $validator:ident max $value:expr) => {
+        $crate::as3_validator!(@set $variant $validator maximum $value)
+    };
+    (@set $variant:ident $validator:ident $field:ident $value:expr) => {
+        if let $crate::AS3Validator::$variant { $field, .. } = &mut $validator {
+            *$field = Some($value);
+        }
+    };
+    (@key $key:ident) => {
+        ::std::string::String::from(stringify!($key))
+    };
+    (@key $key:literal) => {
+        ::std::string::String::from($key)
+    };
+}
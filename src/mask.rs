@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+
+use crate::options::Validation;
+use crate::{AS3Data, AS3Validator, ValidationOptions};
+
+/// How [`AS3Validator::mask`] redacts a field, set with `+Mask`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mask {
+    /// Keep the last four characters behind `****`, as card numbers are shown.
+    Last4,
+    /// Replace the whole value with `****`.
+    Full,
+}
+
+impl Mask {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mask::Last4 => "last4",
+            Mask::Full => "full",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Mask> {
+        match name {
+            "last4" => Some(Mask::Last4),
+            "full" => Some(Mask::Full),
+            _ => None,
+        }
+    }
+
+    /// The redacted value. The number of `*` is fixed, so the length of the original
+    /// does not leak either, and `null` is left alone.
+    fn apply(&self, value: &AS3Data) -> AS3Data {
+        let text = match value {
+            AS3Data::Null => return AS3Data::Null,
+            AS3Data::String(text) => text.clone(),
+            AS3Data::Integer(number) => number.to_string(),
+            _ => String::new(),
+        };
+        let length = text.chars().count();
+        AS3Data::String(match self {
+            Mask::Last4 if length > 4 => {
+                let last: String = text.chars().skip(length - 4).collect();
+                format!("****{last}")
+            }
+            _ => "****".to_string(),
+        })
+    }
+}
+
+impl AS3Validator {
+    /// A copy of `data` with the fields annotated with `+Mask` redacted, e.g. before a
+    /// validated document is logged.
+    ///
+    /// Data is matched to validators as in [`AS3Validator::unknown_keys`], so data the
+    /// schema rejects is still masked as far as its shape allows. A `OneOf`, `AnyOf` or
+    /// `Enum` uses its first accepting alternative, or every alternative when none accepts
+    /// the data, and a `Ref` to `#` masks with `self` again.
+    pub fn mask(&self, data: &AS3Data) -> AS3Data {
+        let mut masked = data.clone();
+        self.mask_in_place(&mut masked, self, false);
+        masked
+    }
+
+    /// `followed` is set once a `#` ref has led back to `root` without descending into
+    /// the data, as following it again could only loop.
+    fn mask_in_place(&self, data: &mut AS3Data, root: &AS3Validator, followed: bool) {
+        match self {
+            AS3Validator::Annotated {
+                annotations,
+                validator,
+            } => match annotations.mask {
+                Some(mask) => *data = mask.apply(data),
+                None => validator.mask_in_place(data, root, followed),
+            },
+            AS3Validator::Flatten(validator) => validator.mask_in_place(data, root, followed),
+            AS3Validator::OneOf(alternatives)
+            | AS3Validator::AnyOf(alternatives)
+            | AS3Validator::Enum(alternatives) => {
+                let accepts = |entry: &&AS3Validator| {
+                    let options = ValidationOptions::default();
+                    let mut validation = Validation::new(&options);
+                    validation.root = Some(root);
+                    entry.collect_errors(data, &mut validation);
+                    validation.report.is_valid()
+                };
+                match alternatives.iter().find(accepts) {
+                    Some(alternative) => alternative.mask_in_place(data, root, followed),
+                    // Invalid documents are the ones most often logged; masking a field
+                    // twice is harmless, leaking it is not.
+                    None => {
+                        for alternative in alternatives {
+                            alternative.mask_in_place(data, root, followed);
+                        }
+                    }
+                }
+            }
+            AS3Validator::AllOf(validators) => {
+                for validator in validators {
+                    validator.mask_in_place(data, root, followed);
+                }
+            }
+            AS3Validator::Discriminated { key, mapping } => {
+                let variant = match data {
+                    AS3Data::Object(fields) => match fields.get(key).map(Box::as_ref) {
+                        Some(AS3Data::String(tag)) => mapping.get(tag),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let Some(variant) = variant {
+                    variant.mask_in_place(data, root, followed);
+                }
+            }
+            AS3Validator::Ref(url) if url == "#" && !followed => {
+                root.mask_in_place(data, root, true)
+            }
+            _ => self.mask_children(data, root),
+        }
+    }
+
+    fn mask_children(&self, data: &mut AS3Data, root: &AS3Validator) {
+        match (self, data) {
+            (
+                AS3Validator::Object {
+                    additional_properties_schema,
+                    ..
+                },
+                AS3Data::Object(fields),
+            ) => {
+                for (name, value) in fields.iter_mut() {
+                    let validator = self.property(name);
+                    if let Some(validator) = validator.or(additional_properties_schema.as_deref()) {
+                        validator.mask_in_place(value, root, false);
+                    }
+                }
+            }
+            (AS3Validator::List { items, .. }, AS3Data::List(elements)) => {
+                for element in elements {
+                    items.mask_in_place(element, root, false);
+                }
+            }
+            (AS3Validator::Tuple { items, rest }, AS3Data::List(elements)) => {
+                for (index, element) in elements.iter_mut().enumerate() {
+                    if let Some(validator) = items.get(index).or(rest.as_deref()) {
+                        validator.mask_in_place(element, root, false);
+                    }
+                }
+            }
+            (AS3Validator::Positional { fields }, AS3Data::List(elements)) => {
+                for ((_, validator), element) in fields.iter().zip(elements) {
+                    validator.mask_in_place(element, root, false);
+                }
+            }
+            (AS3Validator::Map { value_type, .. }, AS3Data::Object(fields)) => {
+                for value in fields.values_mut() {
+                    value_type.mask_in_place(value, root, false);
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{fmt_number, AS3ValidationError};
+
+/// Translated message templates, keyed on error code and language. Placeholders name the
+/// error's fields. English is the `Display` output, so it is not listed here.
+const TEMPLATES: &[(&str, &str, &str)] = &[
+    (
+        "type_error",
+        "it",
+        "Tipo errato: atteso `{expected}`, trovato `{got}`.",
+    ),
+    ("missing_key", "it", "Manca la chiave `{key}`."),
+    ("unknown_key", "it", "La chiave `{key}` non è ammessa."),
+    (
+        "unsorted_key",
+        "it",
+        "La chiave `{key}` deve venire prima di `{previous}`.",
+    ),
+    (
+        "dependency_missing",
+        "it",
+        "La chiave `{required}` è obbligatoria se è presente `{trigger}`.",
+    ),
+    ("regex", "it", "`{word}` non rispetta la regex `{regex}`."),
+    (
+        "minimum",
+        "it",
+        "`{number}` è sotto il minimo di `{minimum}`.",
+    ),
+    (
+        "maximum",
+        "it",
+        "`{number}` è sopra il massimo di `{maximum}`.",
+    ),
+    (
+        "too_many_decimals",
+        "it",
+        "`{value}` ha più di {max} cifre decimali.",
+    ),
+    ("parse", "it", "Impossibile leggere i dati: {message}"),
+    ("format", "it", "`{value}` non è un {format} valido."),
+    ("enum", "it", "Il valore non è tra quelli ammessi."),
+    (
+        "step",
+        "it",
+        "`{number}` non dista da `{origin}` un multiplo di `{step}`.",
+    ),
+    ("deprecated", "it", "Il campo è deprecato."),
+];
+
+/// How each language introduces the location of an [`AS3ValidationError::AtPath`] error.
+const AT_PATH: &[(&str, &str)] = &[("it", "in")];
+
+impl AS3ValidationError {
+    /// Renders the error in `lang` (an ISO 639-1 code such as `"it"`), falling back to the
+    /// English [`Display`](std::fmt::Display) output when there is no translation.
+    pub fn message(&self, lang: &str) -> String {
+        if let AS3ValidationError::AtPath { path, error } = self {
+            if let Some((_, at)) = AT_PATH.iter().find(|(language, _)| *language == lang) {
+                return format!("{} {at} `{}`", error.message(lang), path.join("."));
+            }
+            return self.to_string();
+        }
+        let template = TEMPLATES
+            .iter()
+            .find(|(code, language, _)| *code == self.code() && *language == lang);
+        let (Some((_, _, template)), Some(fields)) = (template, self.fields()) else {
+            return self.to_string();
+        };
+        fields
+            .into_iter()
+            .fold(template.to_string(), |message, (name, value)| {
+                message.replace(&format!("{{{name}}}"), &value)
+            })
+    }
+
+    /// The values the templates of this error's code refer to, or `None` for errors that
+    /// share a code with a differently shaped variant and so have no templates.
+    fn fields(&self) -> Option<Vec<(&'static str, String)>> {
+        let fields = match self {
+            AS3ValidationError::TypeError { expected, got, .. } => {
+                vec![("expected", expected.to_string()), ("got", got.to_string())]
+            }
+            AS3ValidationError::MissingKey { key } | AS3ValidationError::UnknownKey { key } => {
+                vec![("key", key.clone())]
+            }
+            AS3ValidationError::UnsortedKey { key, previous } => {
+                vec![("key", key.clone()), ("previous", previous.clone())]
+            }
+            AS3ValidationError::DependencyMissing { trigger, required } => {
+                vec![("trigger", trigger.clone()), ("required", required.clone())]
+            }
+            AS3ValidationError::RegexError { word, regex, .. } => {
+                vec![("word", word.clone()), ("regex", regex.clone())]
+            }
+            AS3ValidationError::Minimum { number, minimum } => {
+                vec![
+                    ("number", fmt_number(*number)),
+                    ("minimum", fmt_number(*minimum)),
+                ]
+            }
+            AS3ValidationError::Maximum { number, maximum } => {
+                vec![
+                    ("number", fmt_number(*number)),
+                    ("maximum", fmt_number(*maximum)),
+                ]
+            }
+            AS3ValidationError::IntegerMinimum { number, minimum } => {
+                vec![
+                    ("number", number.to_string()),
+                    ("minimum", minimum.to_string()),
+                ]
+            }
+            AS3ValidationError::IntegerMaximum { number, maximum } => {
+                vec![
+                    ("number", number.to_string()),
+                    ("maximum", maximum.to_string()),
+                ]
+            }
+            AS3ValidationError::TooManyDecimals { value, max } => {
+                vec![("value", fmt_number(*value)), ("max", max.to_string())]
+            }
+            AS3ValidationError::ParseError { message } => vec![("message", message.clone())],
+            AS3ValidationError::FormatError { value, format } => {
+                vec![("value", value.clone()), ("format", format.to_string())]
+            }
+            AS3ValidationError::NotOnStep {
+                number,
+                step,
+                origin,
+            } => vec![
+                ("number", number.to_string()),
+                ("step", step.to_string()),
+                ("origin", origin.to_string()),
+            ],
+            AS3ValidationError::NotInEnum { .. } | AS3ValidationError::Deprecated => vec![],
+            _ => return None,
+        };
+        Some(fields)
+    }
+}
@@ -0,0 +1,84 @@
+use base64::Engine;
+use indexmap::IndexMap;
+use rmpv::Value;
+
+use crate::{AS3Data, AS3ValidationError, AS3Validator};
+
+/// What to do with MessagePack binary and extension values, which have no JSON equivalent.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryPolicy {
+    /// Fail with a [`AS3ValidationError::ParseError`].
+    #[default]
+    Reject,
+    /// Validate the bytes as a standard, padded base64 String.
+    Base64,
+}
+
+impl AS3Validator {
+    /// Decodes a MessagePack document and validates it, rejecting binary values.
+    pub fn validate_msgpack(&self, bytes: &[u8]) -> Result<(), AS3ValidationError> {
+        self.validate_msgpack_with(bytes, BinaryPolicy::Reject)
+    }
+
+    /// Like [`AS3Validator::validate_msgpack`], handling binary values per `policy`.
+    pub fn validate_msgpack_with(
+        &self,
+        bytes: &[u8],
+        policy: BinaryPolicy,
+    ) -> Result<(), AS3ValidationError> {
+        let value: Value =
+            rmp_serde::from_slice(bytes).map_err(|e| AS3ValidationError::ParseError {
+                message: e.to_string(),
+            })?;
+        self.validate(&to_data(value, policy)?)
+    }
+}
+
+fn to_data(value: Value, policy: BinaryPolicy) -> Result<AS3Data, AS3ValidationError> {
+    let data = match value {
+        Value::Nil => AS3Data::Null,
+        Value::Boolean(boolean) => AS3Data::Boolean(boolean),
+        Value::Integer(number) => match number.as_i64() {
+            Some(number) => AS3Data::Integer(number),
+            None => AS3Data::Decimal(number.as_f64().unwrap_or(f64::NAN)),
+        },
+        Value::F32(number) => AS3Data::Decimal(number.into()),
+        Value::F64(number) => AS3Data::Decimal(number),
+        Value::String(string) => match string.into_str() {
+            Some(string) => AS3Data::String(string),
+            None => return Err(parse_error("string is not valid UTF-8")),
+        },
+        Value::Binary(bytes) | Value::Ext(_, bytes) => match policy {
+            BinaryPolicy::Reject => return Err(parse_error("binary value has no JSON equivalent")),
+            BinaryPolicy::Base64 => {
+                AS3Data::String(base64::engine::general_purpose::STANDARD.encode(bytes))
+            }
+        },
+        Value::Array(items) => AS3Data::List(
+            items
+                .into_iter()
+                .map(|item| to_data(item, policy))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Map(entries) => {
+            let mut object = IndexMap::new();
+            for (key, value) in entries {
+                let Value::String(key) = key else {
+                    return Err(parse_error("map key is not a string"));
+                };
+                let key = key
+                    .into_str()
+                    .ok_or_else(|| parse_error("map key is not valid UTF-8"))?;
+                object.insert(key, Box::new(to_data(value, policy)?));
+            }
+            AS3Data::Object(object)
+        }
+    };
+    Ok(data)
+}
+
+fn parse_error(message: &str) -> AS3ValidationError {
+    AS3ValidationError::ParseError {
+        message: format!("MessagePack {message}"),
+    }
+}
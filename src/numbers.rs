@@ -0,0 +1,46 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+
+use crate::options::Validation;
+use crate::AS3ValidationError;
+
+/// Checks that the decimal `value` lies within the inclusive `minimum`/`maximum` bounds,
+/// comparing with arbitrary precision.
+pub(crate) fn check_number(
+    value: &str,
+    minimum: Option<&str>,
+    maximum: Option<&str>,
+    validation: &mut Validation,
+) {
+    let Some(number) = parse_number(value, validation) else {
+        return;
+    };
+    let minimum_bound = minimum.and_then(|minimum| parse_number(minimum, validation));
+    let maximum_bound = maximum.and_then(|maximum| parse_number(maximum, validation));
+
+    let too_small = minimum_bound.is_some_and(|minimum| number < minimum);
+    let too_large = maximum_bound.is_some_and(|maximum| number > maximum);
+    if too_small || too_large {
+        validation.push(AS3ValidationError::NumberRange {
+            value: value.to_string(),
+            minimum: minimum.map(|minimum| minimum.to_string()),
+            maximum: maximum.map(|maximum| maximum.to_string()),
+        });
+    }
+}
+
+/// Parses `value` as written: surrounding whitespace is a format error, as it is for
+/// Integer and Decimal strings.
+fn parse_number(value: &str, validation: &mut Validation) -> Option<BigDecimal> {
+    match BigDecimal::from_str(value) {
+        Ok(number) => Some(number),
+        Err(_) => {
+            validation.push(AS3ValidationError::FormatError {
+                value: value.to_string(),
+                format: "number",
+            });
+            None
+        }
+    }
+}
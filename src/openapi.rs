@@ -0,0 +1,97 @@
+use serde_json::{json, Map, Value};
+
+use crate::{AS3SchemaError, AS3Validator};
+
+impl AS3Validator {
+    /// Builds a validator from `components.schemas.<name>` of an OpenAPI 3 document, so
+    /// request bodies can be checked against the same spec the docs are generated from.
+    ///
+    /// Local `$ref`s such as `#/components/schemas/Vehicle` are inlined, and the result is
+    /// converted like [`AS3Validator::from_json_schema`]. The OpenAPI 3.0 keywords
+    /// `nullable` and `example` are read as a `null` alternative and as `examples`. A
+    /// `$ref` to another document, or to a schema it is nested in, is an error, as the
+    /// validator cannot refer back to itself.
+    pub fn from_openapi_component(
+        doc: &serde_yaml::Value,
+        name: &str,
+    ) -> Result<AS3Validator, AS3SchemaError> {
+        let doc = serde_json::to_value(doc)
+            .map_err(|e| format!("Unable to read the OpenAPI document: {e}"))?;
+        let reference = format!(
+            "#/components/schemas/{}",
+            name.replace('~', "~0").replace('/', "~1")
+        );
+        if doc.pointer(&reference[1..]).is_none() {
+            return Err(format!("The OpenAPI document has no `components.schemas.{name}`").into());
+        }
+        let schema = inline(&doc, &json!({ "$ref": reference }), &mut vec![])?;
+        AS3Validator::from_json_schema(&schema)
+    }
+}
+
+/// Replaces every `$ref` in `schema` with the part of `doc` it points to. `refs` holds the
+/// references being inlined, to catch one that leads back to itself.
+fn inline(doc: &Value, schema: &Value, refs: &mut Vec<String>) -> Result<Value, String> {
+    let schema = match schema {
+        Value::Object(schema) => schema,
+        Value::Array(items) => {
+            return items
+                .iter()
+                .map(|item| inline(doc, item, refs))
+                .collect::<Result<_, _>>()
+                .map(Value::Array)
+        }
+        value => return Ok(value.clone()),
+    };
+    // As in OpenAPI 3.0, the keys next to a `$ref` are ignored.
+    if let Some(reference) = schema.get("$ref") {
+        let Some(reference) = reference.as_str() else {
+            return Err("`$ref` must be a string".to_string());
+        };
+        let Some(pointer) = reference.strip_prefix('#') else {
+            return Err(format!(
+                "Only `$ref`s within the document are supported, not `{reference}`"
+            ));
+        };
+        if refs.iter().any(|seen| seen == reference) {
+            return Err(format!(
+                "`{reference}` refers back to itself, which cannot be inlined"
+            ));
+        }
+        let Some(target) = doc.pointer(pointer) else {
+            return Err(format!("`{reference}` does not point into the document"));
+        };
+        refs.push(reference.to_string());
+        let inlined = inline(doc, target, refs);
+        refs.pop();
+        return inlined;
+    }
+
+    let mut inlined = Map::new();
+    for (key, value) in schema {
+        let value = match (key.as_str(), value) {
+            // Keyed by property names, which may well be `nullable` or `example`: only the
+            // schemas under them are rewritten.
+            ("properties" | "patternProperties" | "$defs", Value::Object(schemas)) => {
+                let mut inlined = Map::new();
+                for (name, schema) in schemas {
+                    inlined.insert(name.clone(), inline(doc, schema, refs)?);
+                }
+                Value::Object(inlined)
+            }
+            // Data rather than schemas.
+            ("example" | "examples" | "enum" | "const" | "default", value) => value.clone(),
+            (_, value) => inline(doc, value, refs)?,
+        };
+        inlined.insert(key.clone(), value);
+    }
+    if let Some(example) = inlined.remove("example") {
+        inlined
+            .entry("examples")
+            .or_insert_with(|| Value::Array(vec![example]));
+    }
+    match inlined.remove("nullable") {
+        Some(Value::Bool(true)) => Ok(json!({ "anyOf": [inlined, { "type": "null" }] })),
+        _ => Ok(Value::Object(inlined)),
+    }
+}
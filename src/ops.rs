@@ -0,0 +1,47 @@
+use std::ops::{BitAnd, BitOr};
+
+use crate::AS3Validator;
+
+/// `a & b` builds an [`AS3Validator::AllOf`], which the data must satisfy both sides of.
+/// Chains such as `a & b & c` extend the same `AllOf` rather than nesting.
+///
+/// ```
+/// use appcovecompiler::{as3_validator, AS3Data};
+///
+/// let even_teen = as3_validator!(Integer(min = 13, max = 19)) & as3_validator!(Integer(step = 2));
+/// assert!(even_teen.is_valid(&AS3Data::Integer(14)));
+/// assert!(!even_teen.is_valid(&AS3Data::Integer(15)));
+/// ```
+impl BitAnd for AS3Validator {
+    type Output = AS3Validator;
+
+    fn bitand(self, other: AS3Validator) -> AS3Validator {
+        let mut validators = match self {
+            AS3Validator::AllOf(validators) => validators,
+            validator => vec![validator],
+        };
+        match other {
+            AS3Validator::AllOf(others) => validators.extend(others),
+            other => validators.push(other),
+        }
+        AS3Validator::AllOf(validators)
+    }
+}
+
+/// `a | b` builds an [`AS3Validator::AnyOf`], which the data must satisfy at least one
+/// side of. Chains such as `a | b | c` extend the same `AnyOf` rather than nesting.
+impl BitOr for AS3Validator {
+    type Output = AS3Validator;
+
+    fn bitor(self, other: AS3Validator) -> AS3Validator {
+        let mut branches = match self {
+            AS3Validator::AnyOf(branches) => branches,
+            validator => vec![validator],
+        };
+        match other {
+            AS3Validator::AnyOf(others) => branches.extend(others),
+            other => branches.push(other),
+        }
+        AS3Validator::AnyOf(branches)
+    }
+}
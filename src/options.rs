@@ -0,0 +1,277 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::{AS3Data, AS3ValidationError, AS3Validator};
+
+/// A parser for the `grammar` of a String validator: `Ok` when it accepts the whole
+/// value, or the character offset where parsing failed.
+pub type Grammar = fn(&str) -> Result<(), usize>;
+
+/// Knobs that change how a validation run treats its findings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationOptions {
+    /// Error codes (see [`AS3ValidationError::code`]) reported as warnings instead of errors.
+    pub soft_errors: HashSet<String>,
+    /// Skip missing keys, as when validating the body of a PATCH request.
+    pub patch: bool,
+    /// Which way the data travels, for `read_only`/`write_only` fields.
+    pub direction: Option<Direction>,
+    /// Skip optional fields holding an empty string, as web forms send for blank inputs,
+    /// instead of validating them.
+    pub treat_empty_as_missing: bool,
+    /// Deepest nesting of objects and lists that is descended into, where following an
+    /// [`AS3Validator::Ref`] to `#` counts as one more level. Anything deeper is rejected
+    /// with [`AS3ValidationError::MaxDepthExceeded`] instead of recursing on, so
+    /// adversarial input cannot overflow the stack.
+    pub max_depth: usize,
+    /// Longest a validation run may take. Once it has passed, the value being checked is
+    /// rejected with [`AS3ValidationError::Timeout`] and the rest of the data is skipped.
+    ///
+    /// Regexes run on the linear-time `regex` crate, so a pattern such as `(a+)+$` cannot
+    /// backtrack catastrophically; this bounds the total work on huge documents instead.
+    pub timeout: Option<Duration>,
+    /// Accept Integer values sent as strings of digits, such as `"42"`, as query
+    /// parameters and form fields carry them. A string too large for an `i64` is rejected
+    /// with [`AS3ValidationError::NumberOutOfRange`] rather than rounded or wrapped.
+    pub integer_strings: bool,
+    /// Accept Decimal values sent as strings written like JSON numbers, in plain or
+    /// scientific notation, such as `"-0.5"` or `"1e10"`. A string beyond the range of an
+    /// `f64` is rejected with [`AS3ValidationError::NumberOutOfRange`].
+    pub decimal_strings: bool,
+    /// List the scalar values that passed in [`ValidationReport::audited`], for audit
+    /// trails that record which fields were checked without logging their contents.
+    pub audit: bool,
+    /// Parsers that String validators name in their `grammar`, added with
+    /// [`ValidationOptions::grammar`].
+    pub grammars: HashMap<String, Grammar>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            soft_errors: HashSet::new(),
+            patch: false,
+            direction: None,
+            treat_empty_as_missing: false,
+            max_depth: 256,
+            timeout: None,
+            integer_strings: false,
+            decimal_strings: false,
+            audit: false,
+            grammars: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Data sent by a client; read-only fields must be absent.
+    Request,
+    /// Data returned by a server; write-only fields must be absent.
+    Response,
+}
+
+impl ValidationOptions {
+    /// Downgrades every error with the given code to a warning.
+    pub fn soft(mut self, code: &str) -> Self {
+        self.soft_errors.insert(code.to_string());
+        self
+    }
+
+    /// Registers the parser that String validators with a `grammar` of `name` consult.
+    pub fn grammar(mut self, name: &str, parser: Grammar) -> Self {
+        self.grammars.insert(name.to_string(), parser);
+        self
+    }
+}
+
+/// How [`AS3Data::from_value_with`] turns JSON into [`AS3Data`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConversionOptions {
+    /// Turn whole-valued floats such as `2.0` into `Integer`s. By default they stay
+    /// `Decimal`s, while `2` is always an `Integer`.
+    pub whole_floats_as_integers: bool,
+}
+
+/// Outcome of [`crate::AS3Validator::validate_with_options`].
+#[derive(Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    pub errors: Vec<AS3ValidationError>,
+    pub warnings: Vec<AS3ValidationError>,
+    /// Dotted paths (`vehicles.0.maker`) and type names of the scalar values that were
+    /// validated without errors, sorted by path. Only filled in when
+    /// [`ValidationOptions::audit`] is set.
+    pub audited: Vec<(String, &'static str)>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// How much work a validation run did, from [`crate::AS3Validator::validate_with_stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationStats {
+    pub objects: usize,
+    pub lists: usize,
+    pub scalars: usize,
+    pub regex_evaluations: usize,
+}
+
+impl ValidationStats {
+    pub(crate) fn visit(&mut self, data: &AS3Data) {
+        match data {
+            AS3Data::Object(_) | AS3Data::Map { .. } => self.objects += 1,
+            AS3Data::List(_) => self.lists += 1,
+            _ => self.scalars += 1,
+        }
+    }
+}
+
+/// State threaded through a single validation run.
+pub(crate) struct Validation<'a> {
+    pub(crate) options: &'a ValidationOptions,
+    pub(crate) report: ValidationReport,
+    pub(crate) stats: ValidationStats,
+    /// Set while a flattened object is checked, so it leaves undeclared keys to the
+    /// object it is flattened into.
+    pub(crate) flattening: bool,
+    /// Schemas fetched for [`AS3Validator::Ref`] URLs, or why they could not be.
+    pub(crate) refs: Option<&'a HashMap<String, Result<AS3Validator, String>>>,
+    /// The validator the run started from, which a [`AS3Validator::Ref`] to `#` stands for.
+    pub(crate) root: Option<&'a AS3Validator>,
+    /// How many `#` refs are being followed. They count towards `max_depth` like path
+    /// segments, so a schema that refers back to itself without descending into the data
+    /// still ends, and each level of a recursive tree is charged for its extra frames.
+    pub(crate) root_follows: usize,
+    path: Vec<String>,
+    deadline: Option<Instant>,
+    timed_out: bool,
+    /// Values noted for [`ValidationOptions::audit`], whether or not they passed.
+    audited: Vec<(Vec<String>, &'static str)>,
+}
+
+impl<'a> Validation<'a> {
+    pub(crate) fn new(options: &'a ValidationOptions) -> Self {
+        Validation {
+            options,
+            report: ValidationReport::default(),
+            stats: ValidationStats::default(),
+            path: vec![],
+            flattening: false,
+            refs: None,
+            root: None,
+            root_follows: 0,
+            deadline: options.timeout.map(|timeout| Instant::now() + timeout),
+            timed_out: false,
+            audited: vec![],
+        }
+    }
+
+    /// A separate run over part of the data, which shares this run's deadline, fetched
+    /// schemas and root.
+    pub(crate) fn nested(&self) -> Validation<'a> {
+        Validation {
+            deadline: self.deadline,
+            refs: self.refs,
+            root: self.root,
+            root_follows: self.root_follows,
+            ..Validation::new(self.options)
+        }
+    }
+
+    /// Descends into the object key or list index `segment`.
+    pub(crate) fn enter(&mut self, segment: &str) {
+        self.path.push(segment.to_string());
+    }
+
+    pub(crate) fn leave(&mut self) {
+        self.path.pop();
+    }
+
+    /// Whether the current value sits deeper than `max_depth` allows.
+    pub(crate) fn too_deep(&self) -> bool {
+        self.path.len() + self.root_follows > self.options.max_depth
+    }
+
+    /// Whether the run is past its deadline. The first check after it reports
+    /// [`AS3ValidationError::Timeout`] at the current path; later ones stay silent.
+    pub(crate) fn out_of_time(&mut self) -> bool {
+        if !self.timed_out
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            self.timed_out = true;
+            let timeout = self.options.timeout.unwrap_or_default();
+            self.push(AS3ValidationError::Timeout { timeout });
+        }
+        self.timed_out
+    }
+
+    /// Notes the value at the current path for [`ValidationOptions::audit`].
+    pub(crate) fn audit(&mut self, validator: &AS3Validator, data: &AS3Data) {
+        if !self.options.audit || self.path.is_empty() || data.is_container() {
+            return;
+        }
+        // Validators that wrap others hand them the same value; the outermost names it.
+        if self
+            .audited
+            .last()
+            .is_some_and(|(path, _)| *path == self.path)
+        {
+            return;
+        }
+        self.audited
+            .push((self.path.clone(), validator.type_name()));
+    }
+
+    /// Ends the run, keeping only the audited values that no error was reported at.
+    pub(crate) fn finish(self) -> ValidationReport {
+        let mut report = self.report;
+        report.audited = self
+            .audited
+            .into_iter()
+            .filter(|(path, _)| !report.errors.iter().any(|error| error.path() == path))
+            .map(|(path, type_name)| (path.join("."), type_name))
+            .collect();
+        report.audited.sort();
+        report
+    }
+
+    /// Whether the value of an optional field counts as absent under these options.
+    pub(crate) fn treats_as_missing(&self, value: &AS3Data) -> bool {
+        match value {
+            AS3Data::Null => true,
+            AS3Data::String(s) => self.options.treat_empty_as_missing && s.is_empty(),
+            _ => false,
+        }
+    }
+
+    fn locate(&self, error: AS3ValidationError) -> AS3ValidationError {
+        if self.path.is_empty() {
+            error
+        } else {
+            AS3ValidationError::AtPath {
+                path: self.path.clone(),
+                error: Box::new(error),
+            }
+        }
+    }
+
+    /// Records a finding that never fails validation.
+    pub(crate) fn warn(&mut self, warning: AS3ValidationError) {
+        let warning = self.locate(warning);
+        self.report.warnings.push(warning);
+    }
+
+    pub(crate) fn push(&mut self, error: AS3ValidationError) {
+        let error = self.locate(error);
+        if self.options.soft_errors.contains(error.code()) {
+            self.report.warnings.push(error);
+        } else {
+            self.report.errors.push(error);
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use crate::options::Validation;
+use crate::{AS3Data, AS3ValidationError, AS3Validator, ValidationOptions, ValidationReport};
+
+/// Stands in for the parts of the data the schema does not describe.
+static ANY: AS3Validator = AS3Validator::Any;
+
+impl AS3Validator {
+    /// Validates only the part of `data` at the RFC 6901 JSON Pointer `pointer`, such as
+    /// `/vehicles/list/0`, against the validator at the same place in the schema. Like
+    /// [`AS3Validator::validate`], the first error is returned without its location.
+    pub fn validate_pointer(
+        &self,
+        pointer: &str,
+        data: &AS3Data,
+    ) -> Result<(), AS3ValidationError> {
+        let report =
+            self.validate_pointer_with_options(pointer, data, &ValidationOptions::default());
+        match report.errors.into_iter().next() {
+            Some(error) => Err(error.without_path()),
+            None => Ok(()),
+        }
+    }
+
+    /// [`AS3Validator::validate_pointer`] under `options`, reporting every error with its
+    /// location in the whole document.
+    ///
+    /// On the way down, discriminated unions pick their variant from the data, an `AllOf`
+    /// hands on every validator, and a `OneOf`, `AnyOf` or `Enum` the alternative that
+    /// accepts the data, or else the only one that describes the next segment. Keys an object does
+    /// not declare are checked against [`AS3Validator::Any`] unless it rejects them with
+    /// `additional_properties`. A pointer that does not lead to a value fails with
+    /// [`AS3ValidationError::PointerNotFound`], and one the schema cannot follow with the
+    /// error that stopped it, such as [`AS3ValidationError::AmbiguousPointer`].
+    pub fn validate_pointer_with_options(
+        &self,
+        pointer: &str,
+        data: &AS3Data,
+        options: &ValidationOptions,
+    ) -> ValidationReport {
+        let mut validation = Validation::new(options);
+        validation.root = Some(self);
+        let not_found = || AS3ValidationError::PointerNotFound {
+            pointer: pointer.to_string(),
+        };
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            validation.push(not_found());
+            return validation.finish();
+        }
+        let mut validators = vec![self];
+        let mut data = data;
+        for segment in pointer.split('/').skip(1) {
+            let segment = segment.replace("~1", "/").replace("~0", "~");
+            let child = match data {
+                AS3Data::Object(fields) => fields.get(&segment).map(Box::as_ref),
+                AS3Data::List(items) => index(&segment).and_then(|index| items.get(index)),
+                _ => None,
+            };
+            let Some(child) = child else {
+                validation.push(not_found());
+                return validation.finish();
+            };
+            let mut children = vec![];
+            for validator in validators {
+                if let Err(error) =
+                    self.child_validators(validator, data, &segment, options, 0, &mut children)
+                {
+                    validation.push(error);
+                    return validation.finish();
+                }
+            }
+            validation.enter(&segment);
+            validators = children;
+            data = child;
+        }
+        for validator in validators {
+            validator.collect_errors(data, &mut validation);
+        }
+        validation.finish()
+    }
+
+    /// Adds the validators the value under `segment` of `data` must satisfy, given that
+    /// `validator` checks `data`. `self` is the root a [`AS3Validator::Ref`] to `#` leads
+    /// back to, which has been followed `follows` times without descending.
+    fn child_validators<'a>(
+        &'a self,
+        validator: &'a AS3Validator,
+        data: &AS3Data,
+        segment: &str,
+        options: &ValidationOptions,
+        follows: usize,
+        children: &mut Vec<&'a AS3Validator>,
+    ) -> Result<(), AS3ValidationError> {
+        let descend = |validator: &'a AS3Validator, children: &mut Vec<&'a AS3Validator>| {
+            self.child_validators(validator, data, segment, options, follows, children)
+        };
+        let child = match validator {
+            AS3Validator::Annotated { validator, .. } | AS3Validator::Flatten(validator) => {
+                return descend(validator, children)
+            }
+            AS3Validator::AllOf(validators) => {
+                for validator in validators {
+                    descend(validator, children)?;
+                }
+                return Ok(());
+            }
+            AS3Validator::OneOf(alternatives)
+            | AS3Validator::AnyOf(alternatives)
+            | AS3Validator::Enum(alternatives) => {
+                let accepting = alternatives.iter().find(|alternative| {
+                    let mut validation = Validation::new(options);
+                    validation.root = Some(self);
+                    alternative.collect_errors(data, &mut validation);
+                    validation.report.is_valid()
+                });
+                if let Some(alternative) = accepting {
+                    return descend(alternative, children);
+                }
+                // Otherwise only an alternative that has something to say about the
+                // segment can be meant.
+                let mut describing = alternatives.iter().filter_map(|alternative| {
+                    let mut found = vec![];
+                    descend(alternative, &mut found).ok()?;
+                    let describes = found.iter().any(|found| !std::ptr::eq(*found, &ANY));
+                    describes.then_some(found)
+                });
+                match (describing.next(), describing.next()) {
+                    (Some(found), None) => {
+                        children.extend(found);
+                        return Ok(());
+                    }
+                    _ => {
+                        return Err(AS3ValidationError::AmbiguousPointer {
+                            segment: segment.to_string(),
+                        })
+                    }
+                }
+            }
+            AS3Validator::Discriminated { key, mapping } => {
+                let AS3Data::Object(fields) = data else {
+                    return Err(AS3ValidationError::ShapeMismatch {
+                        expected: validator.expected_shape(),
+                        found: data.describe(),
+                    });
+                };
+                let Some(tag) = fields.get(key) else {
+                    return Err(AS3ValidationError::MissingKey { key: key.clone() });
+                };
+                let variant = match tag.as_ref() {
+                    AS3Data::String(tag) => mapping.get(tag),
+                    _ => None,
+                };
+                return match variant {
+                    Some(variant) => descend(variant, children),
+                    None => Err(AS3ValidationError::UnknownDiscriminator {
+                        key: key.clone(),
+                        preview: tag.preview(),
+                    }),
+                };
+            }
+            AS3Validator::Ref(url) if url == "#" => {
+                // A root that is only a `#` ref would lead back to itself forever.
+                if follows >= options.max_depth {
+                    return Err(AS3ValidationError::MaxDepthExceeded {
+                        depth: options.max_depth,
+                    });
+                }
+                return self.child_validators(self, data, segment, options, follows + 1, children);
+            }
+            AS3Validator::Ref(url) => {
+                return Err(AS3ValidationError::RefUnavailable {
+                    url: url.to_string(),
+                    reason: "it is only fetched by validate_async".to_string(),
+                })
+            }
+            AS3Validator::Object {
+                additional_properties,
+                additional_properties_schema,
+                ..
+            } => match validator.property(segment) {
+                Some(property) => property,
+                None if !additional_properties => {
+                    return Err(AS3ValidationError::UnknownKey {
+                        key: segment.to_string(),
+                    })
+                }
+                None => additional_properties_schema.as_deref().unwrap_or(&ANY),
+            },
+            AS3Validator::Map { value_type, .. } => value_type,
+            AS3Validator::List { items, .. } => items,
+            AS3Validator::Tuple { items, rest } => {
+                match index(segment)
+                    .and_then(|index| items.get(index))
+                    .or(rest.as_deref())
+                {
+                    Some(item) => item,
+                    None => {
+                        return Err(AS3ValidationError::TupleLength {
+                            expected: items.len(),
+                            got: match data {
+                                AS3Data::List(elements) => elements.len(),
+                                _ => 0,
+                            },
+                        })
+                    }
+                }
+            }
+            AS3Validator::Positional { fields } => {
+                match index(segment).and_then(|index| fields.get(index)) {
+                    Some((_, field)) => field,
+                    None => {
+                        return Err(AS3ValidationError::TupleLength {
+                            expected: fields.len(),
+                            got: match data {
+                                AS3Data::List(elements) => elements.len(),
+                                _ => 0,
+                            },
+                        })
+                    }
+                }
+            }
+            AS3Validator::Any => &ANY,
+            // A scalar validator has no children, so the data is of the wrong shape.
+            _ => {
+                return Err(AS3ValidationError::ShapeMismatch {
+                    expected: validator.expected_shape(),
+                    found: data.describe(),
+                })
+            }
+        };
+        children.push(child);
+        Ok(())
+    }
+}
+
+/// The list index a pointer segment stands for: digits without a leading zero.
+fn index(segment: &str) -> Option<usize> {
+    let digits = !segment.is_empty() && segment.bytes().all(|byte| byte.is_ascii_digit());
+    if !digits || (segment.len() > 1 && segment.starts_with('0')) {
+        return None;
+    }
+    segment.parse().ok()
+}
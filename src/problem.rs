@@ -0,0 +1,27 @@
+use serde_json::json;
+
+use crate::AS3ValidationError;
+
+/// Renders validation errors as an RFC 7807 `application/problem+json` document.
+///
+/// Each entry carries the JSON Pointer of the failing value (empty for the document
+/// root), the error [`code`](AS3ValidationError::code), and a human readable detail.
+pub fn to_problem_json(errors: &[AS3ValidationError]) -> serde_json::Value {
+    let errors: Vec<serde_json::Value> = errors
+        .iter()
+        .map(|error| {
+            json!({
+                "pointer": error.json_pointer().unwrap_or_default(),
+                "code": error.code(),
+                "detail": error.clone().without_path().to_string(),
+            })
+        })
+        .collect();
+
+    json!({
+        "type": "about:blank",
+        "title": "Validation failed",
+        "status": 422,
+        "errors": errors,
+    })
+}
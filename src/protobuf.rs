@@ -0,0 +1,30 @@
+//! Validation of decoded protobuf messages, behind the `prost` feature.
+//!
+//! Messages are bridged through `serde_json`, so the generated types must also derive
+//! `serde::Serialize`, e.g. with `prost_build::Config::type_attribute(".",
+//! "#[derive(serde::Serialize)]")`. Fields then appear under their Rust names.
+
+use prost::Message;
+use serde::Serialize;
+
+use crate::{AS3Data, AS3ValidationError, AS3Validator};
+
+impl AS3Data {
+    /// Converts a decoded protobuf message into [`AS3Data`].
+    pub fn from_message<M: Message + Serialize>(message: &M) -> Result<AS3Data, serde_json::Error> {
+        Ok(AS3Data::from(&serde_json::to_value(message)?))
+    }
+}
+
+impl AS3Validator {
+    /// Checks a decoded protobuf message against constraints the `.proto` cannot express.
+    pub fn validate_message<M: Message + Serialize>(
+        &self,
+        message: &M,
+    ) -> Result<(), AS3ValidationError> {
+        let data = AS3Data::from_message(message).map_err(|e| AS3ValidationError::ParseError {
+            message: e.to_string(),
+        })?;
+        self.validate(&data)
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use crate::options::Validation;
+use crate::{AS3Data, AS3ValidationError, AS3Validator, ValidationOptions};
+
+impl AS3Validator {
+    /// Like [`AS3Validator::validate`], but first fetches the schemas behind every
+    /// [`AS3Validator::Ref`], including those the fetched schemas refer to in turn.
+    ///
+    /// Downloads run on tokio's blocking pool, so the runtime is free while they are in
+    /// flight, and each URL is fetched once. The data is then checked without awaiting
+    /// anything further. A schema that cannot be fetched or parsed fails the data that
+    /// reaches it with [`AS3ValidationError::RefUnavailable`].
+    pub async fn validate_async(&self, data: &AS3Data) -> Result<(), AS3ValidationError> {
+        let refs = self.fetch_refs().await;
+        let options = ValidationOptions::default();
+        let mut validation = Validation::new(&options);
+        validation.refs = Some(&refs);
+        validation.root = Some(self);
+        self.collect_errors(data, &mut validation);
+        match validation.report.errors.into_iter().next() {
+            Some(error) => Err(error.without_path()),
+            None => Ok(()),
+        }
+    }
+
+    async fn fetch_refs(&self) -> HashMap<String, Result<AS3Validator, String>> {
+        let mut refs = HashMap::new();
+        let mut pending = ref_urls(self);
+        while !pending.is_empty() {
+            pending.sort();
+            pending.dedup();
+            pending.retain(|url| !refs.contains_key(url));
+            let downloads: Vec<_> = pending
+                .drain(..)
+                .map(|url| {
+                    let download = url.clone();
+                    let task =
+                        tokio::task::spawn_blocking(move || AS3Validator::from_url(&download));
+                    (url, task)
+                })
+                .collect();
+            for (url, task) in downloads {
+                let fetched = match task.await {
+                    Ok(Ok(validator)) => {
+                        pending.extend(ref_urls(&validator));
+                        Ok(validator)
+                    }
+                    Ok(Err(e)) => Err(e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                refs.insert(url, fetched);
+            }
+        }
+        refs
+    }
+}
+
+fn ref_urls(validator: &AS3Validator) -> Vec<String> {
+    let mut urls = vec![];
+    validator.walk(|validator, _| {
+        match validator {
+            // `#` is the validator itself, not a document to download.
+            AS3Validator::Ref(url) if url != "#" => urls.push(url.clone()),
+            _ => {}
+        }
+    });
+    urls
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::{AS3SchemaError, AS3Validator};
+
+#[derive(Error, Debug)]
+pub enum SchemaFetchError {
+    #[error("Unable to fetch the schema: {}" , .0)]
+    Network(String),
+    #[error("Unable to parse the fetched schema: {}" , .0)]
+    Parse(AS3SchemaError),
+}
+
+/// Hook used by [`AS3Validator::from_url_cached`] to skip downloading unchanged schemas.
+///
+/// Entries are revalidated with `If-None-Match`, so a cache only needs to remember the
+/// last `ETag` and the validator built from that response.
+pub trait SchemaCache {
+    fn get(&self, url: &str) -> Option<(&str, &AS3Validator)>;
+    fn put(&mut self, url: &str, etag: String, validator: AS3Validator);
+}
+
+/// In-memory [`SchemaCache`] keyed by URL.
+#[derive(Debug, Default)]
+pub struct MemorySchemaCache {
+    entries: HashMap<String, (String, AS3Validator)>,
+}
+
+impl SchemaCache for MemorySchemaCache {
+    fn get(&self, url: &str) -> Option<(&str, &AS3Validator)> {
+        self.entries
+            .get(url)
+            .map(|(etag, validator)| (etag.as_str(), validator))
+    }
+
+    fn put(&mut self, url: &str, etag: String, validator: AS3Validator) {
+        self.entries.insert(url.to_string(), (etag, validator));
+    }
+}
+
+impl AS3Validator {
+    /// Downloads a YAML schema over HTTP and builds the validator under its `Root` key.
+    pub fn from_url(url: &str) -> Result<AS3Validator, SchemaFetchError> {
+        let response = ureq::get(url)
+            .call()
+            .map_err(|e| SchemaFetchError::Network(e.to_string()))?;
+        let body = response
+            .into_string()
+            .map_err(|e| SchemaFetchError::Network(e.to_string()))?;
+        AS3Validator::from_yaml_str(&body).map_err(SchemaFetchError::Parse)
+    }
+
+    /// Like [`AS3Validator::from_url`], but reuses the cached validator when the server
+    /// answers `304 Not Modified` for the cached `ETag`.
+    pub fn from_url_cached(
+        url: &str,
+        cache: &mut impl SchemaCache,
+    ) -> Result<AS3Validator, SchemaFetchError> {
+        let mut request = ureq::get(url);
+        if let Some((etag, _)) = cache.get(url) {
+            request = request.set("If-None-Match", etag);
+        }
+        let response = request
+            .call()
+            .map_err(|e| SchemaFetchError::Network(e.to_string()))?;
+
+        if response.status() == 304 {
+            if let Some((_, validator)) = cache.get(url) {
+                return Ok(validator.clone());
+            }
+        }
+
+        let etag = response.header("ETag").map(|etag| etag.to_string());
+        let body = response
+            .into_string()
+            .map_err(|e| SchemaFetchError::Network(e.to_string()))?;
+        let validator = AS3Validator::from_yaml_str(&body).map_err(SchemaFetchError::Parse)?;
+        if let Some(etag) = etag {
+            cache.put(url, etag, validator.clone());
+        }
+        Ok(validator)
+    }
+}
@@ -0,0 +1,337 @@
+use indexmap::IndexMap;
+use rand::distr::Alphanumeric;
+use rand::{Rng, RngExt};
+
+use crate::{AS3Data, AS3Validator, StringFormat};
+
+/// Attempts at a String or `OneOf` sample before giving up on constraints that fight each
+/// other, such as a regex combined with a length or case, or overlapping branches.
+const STRING_ATTEMPTS: usize = 100;
+
+/// Upper bound on the number of generated list items and map entries.
+const MAX_ITEMS: usize = 4;
+
+impl AS3Validator {
+    /// Generates random data that satisfies the validator, for fixtures and fuzzing.
+    ///
+    /// Objects get every property, and strings are drawn from their regex. Constraints
+    /// that cannot be met together (a regex that contradicts `+Case`, for instance) are
+    /// satisfied on a best-effort basis, so the result may still fail [`AS3Validator::validate`].
+    pub fn sample(&self, rng: &mut impl Rng) -> AS3Data {
+        match self {
+            AS3Validator::Object {
+                properties,
+                require_sorted_keys,
+                ..
+            } => {
+                let mut object = IndexMap::new();
+                for (key, validator) in properties {
+                    match validator {
+                        AS3Validator::Flatten(flattened) => {
+                            if let AS3Data::Object(fields) = flattened.sample(rng) {
+                                object.extend(fields);
+                            }
+                        }
+                        _ => {
+                            object.insert(key.clone(), Box::new(validator.sample(rng)));
+                        }
+                    }
+                }
+                if *require_sorted_keys {
+                    object.sort_keys();
+                }
+                AS3Data::Object(object)
+            }
+            AS3Validator::String { .. } => {
+                let mut string = self.sample_string(rng);
+                for _ in 1..STRING_ATTEMPTS {
+                    if self.validate(&AS3Data::String(string.clone())).is_ok() {
+                        break;
+                    }
+                    string = self.sample_string(rng);
+                }
+                AS3Data::String(string)
+            }
+            AS3Validator::Integer {
+                minimum,
+                maximum,
+                step,
+                step_origin,
+            } => {
+                let low = minimum.unwrap_or(maximum.map_or(-1000, |max| max.saturating_sub(1000)));
+                let high = maximum.unwrap_or(low.saturating_add(1000)).max(low);
+                let number = rng.random_range(low..=high);
+                match step.filter(|step| *step > 0) {
+                    // Snap up to the next step, or down when that would pass the maximum.
+                    Some(step) => {
+                        let origin = step_origin.unwrap_or(0);
+                        let below = number - (number - origin).rem_euclid(step);
+                        let above = below.saturating_add(step);
+                        AS3Data::Integer(if below == number || above > high {
+                            below
+                        } else {
+                            above
+                        })
+                    }
+                    None => AS3Data::Integer(number),
+                }
+            }
+            AS3Validator::Decimal {
+                minimum,
+                maximum,
+                max_decimal_places,
+            } => {
+                let low = minimum.unwrap_or(maximum.map_or(-1000.0, |max| max - 1000.0));
+                let high = maximum.unwrap_or(low + 1000.0).max(low);
+                let scale = 10f64.powi(max_decimal_places.unwrap_or(2) as i32);
+                let number = (rng.random_range(low..=high) * scale).ceil() / scale;
+                AS3Data::Decimal(if number > high {
+                    number - 1.0 / scale
+                } else {
+                    number
+                })
+            }
+            AS3Validator::NumericString { format_mask } => AS3Data::String(match format_mask {
+                Some(mask) => mask
+                    .chars()
+                    .map(|slot| match slot {
+                        '0' => char::from(b'0' + rng.random_range(0..10)),
+                        literal => literal,
+                    })
+                    .collect(),
+                None => rng.random_range(0..1_000_000).to_string(),
+            }),
+            AS3Validator::List {
+                items,
+                max_validated,
+                non_empty,
+            } => {
+                let min = usize::from(*non_empty);
+                let max = max_validated.unwrap_or(MAX_ITEMS).min(MAX_ITEMS).max(min);
+                AS3Data::List(
+                    (0..rng.random_range(min..=max))
+                        .map(|_| items.sample(rng))
+                        .collect(),
+                )
+            }
+            AS3Validator::Tuple { items, rest } => {
+                let mut elements: Vec<_> = items
+                    .iter()
+                    .map(|validator| validator.sample(rng))
+                    .collect();
+                if let Some(rest) = rest {
+                    for _ in 0..rng.random_range(0..=MAX_ITEMS) {
+                        elements.push(rest.sample(rng));
+                    }
+                }
+                AS3Data::List(elements)
+            }
+            AS3Validator::Positional { fields } => AS3Data::List(
+                fields
+                    .iter()
+                    .map(|(_, validator)| validator.sample(rng))
+                    .collect(),
+            ),
+            AS3Validator::Map {
+                key_type,
+                value_type,
+            } => {
+                let mut object = IndexMap::new();
+                for _ in 0..rng.random_range(0..=MAX_ITEMS) {
+                    if let AS3Data::String(key) = key_type.sample(rng) {
+                        object.insert(key, Box::new(value_type.sample(rng)));
+                    }
+                }
+                AS3Data::Object(object)
+            }
+            AS3Validator::Flatten(validator) | AS3Validator::Annotated { validator, .. } => {
+                validator.sample(rng)
+            }
+            #[cfg(feature = "chrono")]
+            AS3Validator::Date { after, before } => {
+                let parse = |date: &Option<String>, default: &str| {
+                    chrono::NaiveDate::parse_from_str(
+                        date.as_deref().unwrap_or(default),
+                        "%Y-%m-%d",
+                    )
+                    .ok()
+                };
+                let date = match (parse(after, "2000-01-01"), parse(before, "2030-12-31")) {
+                    (Some(after), Some(before)) if after <= before => {
+                        let days = (before - after).num_days();
+                        after + chrono::Days::new(rng.random_range(0..=days) as u64)
+                    }
+                    (Some(after), _) => after,
+                    (None, _) => chrono::NaiveDate::default(),
+                };
+                AS3Data::String(date.format("%Y-%m-%d").to_string())
+            }
+            #[cfg(feature = "chrono")]
+            AS3Validator::DateTime {
+                require_timezone,
+                utc_only,
+            } => {
+                let seconds = rng.random_range(946_684_800..=1_924_991_999);
+                let time = chrono::DateTime::from_timestamp(seconds, 0).unwrap_or_default();
+                AS3Data::String(match (require_timezone, utc_only) {
+                    (Some(false), _) => time.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                    _ => time.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                })
+            }
+            #[cfg(feature = "bigdecimal")]
+            AS3Validator::BigNumber { minimum, maximum } => AS3Data::String(
+                minimum
+                    .clone()
+                    .or_else(|| maximum.clone())
+                    .unwrap_or_else(|| rng.random_range(-1000..=1000).to_string()),
+            ),
+            AS3Validator::Const(value) => value.clone(),
+            AS3Validator::Enum(entries) | AS3Validator::AnyOf(entries) => match entries.len() {
+                0 => AS3Data::Null,
+                len => entries[rng.random_range(0..len)].sample(rng),
+            },
+            AS3Validator::OneOf(branches) => {
+                let mut sample = AS3Data::Null;
+                for _ in 0..STRING_ATTEMPTS.min(branches.len() * 10) {
+                    sample = branches[rng.random_range(0..branches.len())].sample(rng);
+                    if self.validate(&sample).is_ok() {
+                        break;
+                    }
+                }
+                sample
+            }
+            // Samples the first validator until one also satisfies the rest.
+            AS3Validator::AllOf(validators) => {
+                let Some(first) = validators.first() else {
+                    return AS3Data::Null;
+                };
+                let mut sample = first.sample(rng);
+                for _ in 1..STRING_ATTEMPTS {
+                    if self.validate(&sample).is_ok() {
+                        break;
+                    }
+                    sample = first.sample(rng);
+                }
+                sample
+            }
+            AS3Validator::Discriminated { key, mapping } => {
+                let mut tags: Vec<_> = mapping.keys().collect();
+                tags.sort();
+                if tags.is_empty() {
+                    return AS3Data::Object(IndexMap::new());
+                }
+                let tag = tags[rng.random_range(0..tags.len())];
+                let mut sample = mapping[tag].sample(rng);
+                if let AS3Data::Object(object) = &mut sample {
+                    object.insert(key.clone(), Box::new(AS3Data::String(tag.clone())));
+                }
+                sample
+            }
+            // The referenced schema is not fetched for sampling.
+            AS3Validator::Ref(_) | AS3Validator::Any => AS3Data::Null,
+        }
+    }
+
+    fn sample_string(&self, rng: &mut impl Rng) -> String {
+        let AS3Validator::String {
+            regex,
+            min_length,
+            max_length,
+            format,
+            min_bytes,
+            max_bytes,
+            ..
+        } = self
+        else {
+            unreachable!("sample_string is only called on String validators")
+        };
+        #[cfg(feature = "base64")]
+        if *format == Some(StringFormat::Base64) {
+            return sample_base64(*min_bytes, *max_bytes, rng);
+        }
+        if let Some(format) = format {
+            return sample_format(*format, rng);
+        }
+        if let Some(generator) = regex
+            .as_deref()
+            .and_then(|regex| rand_regex::Regex::compile(strip_anchors(regex), 8).ok())
+        {
+            return rng.sample::<String, _>(&generator);
+        }
+        // Alphanumeric characters are one byte each, so byte bounds are length bounds.
+        let min = (*min_length).max(*min_bytes).unwrap_or(0);
+        let max = match (max_length, max_bytes) {
+            (Some(length), Some(bytes)) => Some(*length.min(bytes)),
+            (max, None) | (None, max) => *max,
+        };
+        let max = max.unwrap_or(min + 16).max(min);
+        (0..rng.random_range(min..=max))
+            .map(|_| rng.sample(Alphanumeric) as char)
+            .collect()
+    }
+}
+
+/// Drops the outer `^`/`$` anchors the generator rejects. A string generated from the
+/// rest of the pattern still matches with them, as nothing is generated around it.
+fn strip_anchors(regex: &str) -> &str {
+    let regex = regex.strip_prefix('^').unwrap_or(regex);
+    match regex.strip_suffix('$') {
+        // An odd number of backslashes before the `$` escapes it.
+        Some(stripped) if (stripped.len() - stripped.trim_end_matches('\\').len()) % 2 == 0 => {
+            stripped
+        }
+        _ => regex,
+    }
+}
+
+fn sample_format(format: StringFormat, rng: &mut impl Rng) -> String {
+    match format {
+        StringFormat::Email => format!("user{}@example.com", rng.random_range(0..1000)),
+        StringFormat::Uuid => {
+            let hex: String = (0..32)
+                .map(|_| char::from_digit(rng.random_range(0..16), 16).unwrap())
+                .collect();
+            format!(
+                "{}-{}-{}-{}-{}",
+                &hex[..8],
+                &hex[8..12],
+                &hex[12..16],
+                &hex[16..20],
+                &hex[20..]
+            )
+        }
+        StringFormat::Hostname => format!("host{}.example.com", rng.random_range(0..1000)),
+        StringFormat::Ipv4 => (0..4)
+            .map(|_| rng.random_range(0..=255u8).to_string())
+            .collect::<Vec<_>>()
+            .join("."),
+        #[cfg(feature = "chrono")]
+        StringFormat::Duration => format!(
+            "P{}DT{}H{}M",
+            rng.random_range(0..30),
+            rng.random_range(0..24),
+            rng.random_range(0..60)
+        ),
+        #[cfg(feature = "chrono")]
+        StringFormat::Time => format!(
+            "{:02}:{:02}:{:02}",
+            rng.random_range(0..24),
+            rng.random_range(0..60),
+            rng.random_range(0..60)
+        ),
+        #[cfg(feature = "base64")]
+        StringFormat::Base64 => sample_base64(None, None, rng),
+    }
+}
+
+#[cfg(feature = "base64")]
+fn sample_base64(min_bytes: Option<usize>, max_bytes: Option<usize>, rng: &mut impl Rng) -> String {
+    use base64::Engine;
+
+    let min = min_bytes.unwrap_or(0);
+    let max = max_bytes.unwrap_or(min + 16).max(min);
+    let bytes: Vec<u8> = (0..rng.random_range(min..=max))
+        .map(|_| rng.random())
+        .collect();
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
@@ -0,0 +1,243 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::AS3Validator;
+
+/// Rust types with a matching [`AS3Validator`], usually implemented through
+/// `#[derive(AS3Schema)]` (with the `derive` feature).
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use appcovecompiler::{AS3Data, AS3Schema};
+///
+/// #[derive(AS3Schema)]
+/// struct Vehicle {
+///     #[as3(regex = "^[A-Z][a-z]")]
+///     maker: String,
+///     #[as3(min = 1900)]
+///     year: i64,
+/// }
+///
+/// let data = AS3Data::from(&serde_json::json!({ "maker": "Fiat", "year": 1990 }));
+/// assert!(Vehicle::as3_validator().is_valid(&data));
+/// # }
+/// ```
+pub trait AS3Schema {
+    fn as3_validator() -> AS3Validator;
+}
+
+impl AS3Schema for String {
+    fn as3_validator() -> AS3Validator {
+        AS3Validator::any_string()
+    }
+}
+
+impl AS3Schema for bool {
+    fn as3_validator() -> AS3Validator {
+        AS3Validator::any_boolean()
+    }
+}
+
+/// Integers are bounded by the range of their type, where it is narrower than `i64`.
+macro_rules! integer_schema {
+    ($($ty:ty),*) => {$(
+        impl AS3Schema for $ty {
+            fn as3_validator() -> AS3Validator {
+                AS3Validator::Integer {
+                    minimum: i64::try_from(<$ty>::MIN).ok().filter(|min| *min != i64::MIN),
+                    maximum: i64::try_from(<$ty>::MAX).ok().filter(|max| *max != i64::MAX),
+                    step: None,
+                    step_origin: None,
+                }
+            }
+        }
+    )*};
+}
+
+integer_schema!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl AS3Schema for f32 {
+    fn as3_validator() -> AS3Validator {
+        f64::as3_validator()
+    }
+}
+
+impl AS3Schema for f64 {
+    fn as3_validator() -> AS3Validator {
+        AS3Validator::Decimal {
+            minimum: None,
+            maximum: None,
+            max_decimal_places: None,
+        }
+    }
+}
+
+impl<T: AS3Schema> AS3Schema for Vec<T> {
+    fn as3_validator() -> AS3Validator {
+        AS3Validator::list_of(T::as3_validator())
+    }
+}
+
+/// An optional field, which accepts `None` both left out and sent as `null`.
+impl<T: AS3Schema> AS3Schema for Option<T> {
+    fn as3_validator() -> AS3Validator {
+        let mut validator = T::as3_validator();
+        __derive::optional(&mut validator);
+        validator
+    }
+}
+
+impl<T: AS3Schema> AS3Schema for Box<T> {
+    fn as3_validator() -> AS3Validator {
+        T::as3_validator()
+    }
+}
+
+impl<T: AS3Schema> AS3Schema for HashMap<String, T> {
+    fn as3_validator() -> AS3Validator {
+        AS3Validator::Map {
+            key_type: Arc::new(String::as3_validator()),
+            value_type: Arc::new(T::as3_validator()),
+        }
+    }
+}
+
+impl<T: AS3Schema> AS3Schema for BTreeMap<String, T> {
+    fn as3_validator() -> AS3Validator {
+        HashMap::<String, T>::as3_validator()
+    }
+}
+
+/// Setters called by the code `#[derive(AS3Schema)]` generates for `#[as3(...)]`
+/// attributes. Like the `as3_validator!` arguments, a constraint that does not apply to
+/// the field's validator is ignored.
+#[doc(hidden)]
+pub mod __derive {
+    use std::sync::Arc;
+
+    use crate::{AS3Validator, StringCase, StringFormat};
+
+    /// The validator under any annotations, which is the one constraints apply to.
+    fn inner(validator: &mut AS3Validator) -> &mut AS3Validator {
+        match validator {
+            AS3Validator::Annotated { validator, .. } => inner(Arc::make_mut(validator)),
+            validator => validator,
+        }
+    }
+
+    /// A `min` or `max` attribute value. Integer literals arrive as `Integer`, so an
+    /// Integer field gets them exactly rather than rounded through `f64`.
+    #[derive(Debug, Clone, Copy)]
+    pub enum Bound {
+        Integer(i64),
+        Decimal(f64),
+    }
+
+    impl From<i64> for Bound {
+        fn from(value: i64) -> Self {
+            Bound::Integer(value)
+        }
+    }
+
+    impl From<i32> for Bound {
+        fn from(value: i32) -> Self {
+            Bound::Integer(value.into())
+        }
+    }
+
+    impl From<u32> for Bound {
+        fn from(value: u32) -> Self {
+            Bound::Integer(value.into())
+        }
+    }
+
+    impl From<f64> for Bound {
+        fn from(value: f64) -> Self {
+            Bound::Decimal(value)
+        }
+    }
+
+    impl From<f32> for Bound {
+        fn from(value: f32) -> Self {
+            Bound::Decimal(value.into())
+        }
+    }
+
+    impl Bound {
+        fn integer(self) -> i64 {
+            match self {
+                Bound::Integer(value) => value,
+                Bound::Decimal(value) => value as i64,
+            }
+        }
+
+        fn decimal(self) -> f64 {
+            match self {
+                Bound::Integer(value) => value as f64,
+                Bound::Decimal(value) => value,
+            }
+        }
+    }
+
+    pub fn minimum(validator: &mut AS3Validator, value: Bound) {
+        match inner(validator) {
+            AS3Validator::Integer { minimum, .. } => *minimum = Some(value.integer()),
+            AS3Validator::Decimal { minimum, .. } => *minimum = Some(value.decimal()),
+            _ => {}
+        }
+    }
+
+    pub fn maximum(validator: &mut AS3Validator, value: Bound) {
+        match inner(validator) {
+            AS3Validator::Integer { maximum, .. } => *maximum = Some(value.integer()),
+            AS3Validator::Decimal { maximum, .. } => *maximum = Some(value.decimal()),
+            _ => {}
+        }
+    }
+
+    pub fn regex(validator: &mut AS3Validator, value: &str) {
+        if let AS3Validator::String { regex, .. } = inner(validator) {
+            *regex = Some(value.to_string());
+        }
+    }
+
+    pub fn min_length(validator: &mut AS3Validator, value: usize) {
+        if let AS3Validator::String { min_length, .. } = inner(validator) {
+            *min_length = Some(value);
+        }
+    }
+
+    pub fn max_length(validator: &mut AS3Validator, value: usize) {
+        if let AS3Validator::String { max_length, .. } = inner(validator) {
+            *max_length = Some(value);
+        }
+    }
+
+    pub fn min_bytes(validator: &mut AS3Validator, value: usize) {
+        if let AS3Validator::String { min_bytes, .. } = inner(validator) {
+            *min_bytes = Some(value);
+        }
+    }
+
+    pub fn max_bytes(validator: &mut AS3Validator, value: usize) {
+        if let AS3Validator::String { max_bytes, .. } = inner(validator) {
+            *max_bytes = Some(value);
+        }
+    }
+
+    pub fn format(validator: &mut AS3Validator, value: StringFormat) {
+        if let AS3Validator::String { format, .. } = inner(validator) {
+            *format = Some(value);
+        }
+    }
+
+    pub fn case(validator: &mut AS3Validator, value: StringCase) {
+        if let AS3Validator::String { case, .. } = inner(validator) {
+            *case = Some(value);
+        }
+    }
+
+    pub fn optional(validator: &mut AS3Validator) {
+        validator.make_optional();
+    }
+}
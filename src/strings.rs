@@ -0,0 +1,274 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Well-known shapes a String value can be required to have.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StringFormat {
+    Email,
+    Uuid,
+    Hostname,
+    Ipv4,
+    /// An ISO-8601 duration such as `P1Y2M` or `PT1H30M`.
+    #[cfg(feature = "chrono")]
+    Duration,
+    /// An ISO-8601 time of day such as `13:45:00`.
+    #[cfg(feature = "chrono")]
+    Time,
+    /// Standard, padded base64, such as an embedded image or certificate. The String's
+    /// `min_bytes` and `max_bytes` bound its decoded size rather than its own length.
+    #[cfg(feature = "base64")]
+    Base64,
+}
+
+impl StringFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StringFormat::Email => "email",
+            StringFormat::Uuid => "uuid",
+            StringFormat::Hostname => "hostname",
+            StringFormat::Ipv4 => "ipv4",
+            #[cfg(feature = "chrono")]
+            StringFormat::Duration => "duration",
+            #[cfg(feature = "chrono")]
+            StringFormat::Time => "time",
+            #[cfg(feature = "base64")]
+            StringFormat::Base64 => "base64",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<StringFormat> {
+        match name {
+            "email" => Some(StringFormat::Email),
+            "uuid" => Some(StringFormat::Uuid),
+            "hostname" => Some(StringFormat::Hostname),
+            "ipv4" => Some(StringFormat::Ipv4),
+            #[cfg(feature = "chrono")]
+            "duration" => Some(StringFormat::Duration),
+            #[cfg(feature = "chrono")]
+            "time" => Some(StringFormat::Time),
+            #[cfg(feature = "base64")]
+            "base64" => Some(StringFormat::Base64),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn matches(&self, value: &str) -> bool {
+        match self {
+            StringFormat::Email => match value.split_once('@') {
+                Some((local, domain)) => {
+                    !local.is_empty() && domain.contains('.') && is_hostname(domain)
+                }
+                None => false,
+            },
+            StringFormat::Uuid => compiled(
+                "^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+            )
+            .is_some_and(|uuid| uuid.is_match(value)),
+            StringFormat::Hostname => is_hostname(value),
+            StringFormat::Ipv4 => value.parse::<Ipv4Addr>().is_ok(),
+            #[cfg(feature = "chrono")]
+            StringFormat::Duration => crate::dates::is_duration(value),
+            #[cfg(feature = "chrono")]
+            StringFormat::Time => crate::dates::is_time(value),
+            #[cfg(feature = "base64")]
+            StringFormat::Base64 => decoded_len(value).is_some(),
+        }
+    }
+}
+
+/// The number of bytes `value` decodes to, or `None` if it is not valid base64.
+#[cfg(feature = "base64")]
+pub(crate) fn decoded_len(value: &str) -> Option<usize> {
+    use base64::Engine;
+
+    base64::engine::general_purpose::STANDARD
+        .decode(value)
+        .ok()
+        .map(|bytes| bytes.len())
+}
+
+/// `regex` compiled, or `None` if it does not compile. Each thread compiles a pattern
+/// once, rather than once for every value checked against it.
+pub(crate) fn compiled(regex: &str) -> Option<Regex> {
+    thread_local! {
+        static COMPILED: RefCell<HashMap<String, Option<Regex>>> = RefCell::new(HashMap::new());
+    }
+    COMPILED.with_borrow_mut(|compiled| match compiled.get(regex) {
+        Some(re) => re.clone(),
+        None => {
+            let re = Regex::new(regex).ok();
+            compiled.insert(regex.to_string(), re.clone());
+            re
+        }
+    })
+}
+
+/// For a regex anchored with `^`, the character offset in `word` where matching broke
+/// down: the first character no match can continue through, or the length of `word` when
+/// it ended before the pattern was complete. `None` for other regexes, where a match may
+/// start anywhere and so has no single place to fail.
+pub(crate) fn regex_failure(regex: &str, word: &str) -> Option<usize> {
+    use regex_automata::hybrid::dfa::DFA;
+    use regex_automata::{Anchored, Input};
+
+    if !regex.starts_with('^') {
+        return None;
+    }
+    let dfa = DFA::new(regex).ok()?;
+    let mut cache = dfa.create_cache();
+    let input = Input::new(word).anchored(Anchored::Yes);
+    let mut state = dfa.start_state_forward(&mut cache, &input).ok()?;
+    for (offset, byte) in word.bytes().enumerate() {
+        state = dfa.next_state(&mut cache, state, byte).ok()?;
+        if state.is_dead() {
+            // Only characters read in full count, as the dead byte may sit inside one.
+            return Some(
+                word.char_indices()
+                    .take_while(|(start, c)| start + c.len_utf8() <= offset)
+                    .count(),
+            );
+        }
+    }
+    Some(word.chars().count())
+}
+
+/// Whether `value` is an optionally signed run of ASCII digits, such as `-42`.
+pub(crate) fn is_integer_literal(value: &str) -> bool {
+    let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+    !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Whether `value` fills the numeric `mask` exactly: a digit for every `0` in the mask,
+/// and each other character of the mask as is.
+pub(crate) fn matches_mask(value: &str, mask: &str) -> bool {
+    value.chars().count() == mask.chars().count()
+        && value
+            .chars()
+            .zip(mask.chars())
+            .all(|(found, slot)| match slot {
+                '0' => found.is_ascii_digit(),
+                literal => found == literal,
+            })
+}
+
+/// Whether `value` is written as a JSON number, optionally with a leading `+`: digits
+/// with an optional fraction and exponent, such as `-0.5` or `1e10`. Unlike
+/// [`str::parse`], this rejects `inf`, `NaN` and a bare `1.`.
+pub(crate) fn is_decimal_literal(value: &str) -> bool {
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (value, None),
+    };
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit());
+    let mantissa = mantissa.strip_prefix(['+', '-']).unwrap_or(mantissa);
+    let mantissa_ok = match mantissa.split_once('.') {
+        Some((whole, fraction)) => digits(whole) && digits(fraction),
+        None => digits(mantissa),
+    };
+    mantissa_ok
+        && exponent
+            .is_none_or(|exponent| digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent)))
+}
+
+fn is_hostname(value: &str) -> bool {
+    value.len() <= 253
+        && value.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// Unicode normalization form a String value is brought to before it is checked, so that
+/// composed and decomposed spellings of the same text (`é` and `e` + `◌́`) agree.
+/// Applying one requires the `unicode-normalization` feature.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum NormalizationForm {
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+impl NormalizationForm {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NormalizationForm::Nfc => "NFC",
+            NormalizationForm::Nfd => "NFD",
+            NormalizationForm::Nfkc => "NFKC",
+            NormalizationForm::Nfkd => "NFKD",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<NormalizationForm> {
+        match name {
+            "NFC" => Some(NormalizationForm::Nfc),
+            "NFD" => Some(NormalizationForm::Nfd),
+            "NFKC" => Some(NormalizationForm::Nfkc),
+            "NFKD" => Some(NormalizationForm::Nfkd),
+            _ => None,
+        }
+    }
+
+    /// `value` in this form, borrowed when it already is.
+    #[cfg(feature = "unicode-normalization")]
+    pub(crate) fn apply<'a>(&self, value: &'a str) -> std::borrow::Cow<'a, str> {
+        use unicode_normalization::{is_nfc, is_nfd, is_nfkc, is_nfkd, UnicodeNormalization};
+
+        let normalized = match self {
+            NormalizationForm::Nfc => is_nfc(value),
+            NormalizationForm::Nfd => is_nfd(value),
+            NormalizationForm::Nfkc => is_nfkc(value),
+            NormalizationForm::Nfkd => is_nfkd(value),
+        };
+        if normalized {
+            return value.into();
+        }
+        match self {
+            NormalizationForm::Nfc => value.nfc().collect::<String>().into(),
+            NormalizationForm::Nfd => value.nfd().collect::<String>().into(),
+            NormalizationForm::Nfkc => value.nfkc().collect::<String>().into(),
+            NormalizationForm::Nfkd => value.nfkd().collect::<String>().into(),
+        }
+    }
+}
+
+/// Letter case every cased character of a String value must be in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StringCase {
+    Lower,
+    Upper,
+}
+
+impl StringCase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StringCase::Lower => "lower",
+            StringCase::Upper => "upper",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<StringCase> {
+        match name {
+            "lower" => Some(StringCase::Lower),
+            "upper" => Some(StringCase::Upper),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn matches(&self, value: &str) -> bool {
+        match self {
+            StringCase::Lower => !value.chars().any(char::is_uppercase),
+            StringCase::Upper => !value.chars().any(char::is_lowercase),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use serde_json::Value;
+
+use crate::AS3Validator;
+
+impl AS3Validator {
+    /// Draws the schema as a tree for reading in a terminal, one line per validator with
+    /// its constraints in brackets and `?` after optional fields:
+    ///
+    /// ```text
+    /// Object
+    /// ├── age: Integer [minimum: 20]
+    /// └── vehicles: List
+    ///     └── []: String? [max_length: 64]
+    /// ```
+    ///
+    /// The tree is drawn from [`AS3Validator::to_form_descriptor`], so it shows the same
+    /// fields and constraints a generated form does.
+    pub fn to_tree_string(&self) -> String {
+        let mut tree = String::new();
+        draw(&self.to_form_descriptor(), None, "", "", &mut tree);
+        tree
+    }
+}
+
+fn draw(node: &Value, label: Option<&str>, lead: &str, indent: &str, tree: &mut String) {
+    tree.push_str(lead);
+    if let Some(label) = label {
+        tree.push_str(label);
+        tree.push_str(": ");
+    }
+    tree.push_str(node["type"].as_str().unwrap_or_default());
+    if node["required"] == Value::Bool(false) {
+        tree.push('?');
+    }
+    if let Some(constraints) = node["constraints"].as_object() {
+        let constraints: Vec<_> = constraints
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect();
+        tree.push_str(&format!(" [{}]", constraints.join(", ")));
+    }
+    tree.push('\n');
+
+    let children = children(node);
+    for (index, (label, child)) in children.iter().enumerate() {
+        let (lead, more) = match index + 1 == children.len() {
+            true => ("└── ", "    "),
+            false => ("├── ", "│   "),
+        };
+        draw(
+            child,
+            Some(label),
+            &format!("{indent}{lead}"),
+            &format!("{indent}{more}"),
+            tree,
+        );
+    }
+}
+
+/// The nested descriptors of a node, each with the label it is drawn under.
+fn children(node: &Value) -> Vec<(String, &Value)> {
+    let mut children = vec![];
+    let list = |key: &str| node[key].as_array().into_iter().flatten();
+    for field in list("fields") {
+        let name = field["name"].as_str().unwrap_or_default();
+        children.push((name.to_string(), field));
+    }
+    match &node["items"] {
+        Value::Array(items) => children.extend(
+            items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| (format!("[{index}]"), item)),
+        ),
+        Value::Null => {}
+        items => children.push(("[]".to_string(), items)),
+    }
+    if !node["rest"].is_null() {
+        children.push(("[..]".to_string(), &node["rest"]));
+    }
+    if !node["values"].is_null() {
+        children.push(("*".to_string(), &node["values"]));
+    }
+    for branch in list("branches").chain(list("entries")) {
+        children.push(("|".to_string(), branch));
+    }
+    for validator in list("all_of") {
+        children.push(("&".to_string(), validator));
+    }
+    if let Some(variants) = node["variants"].as_object() {
+        let key = node["discriminator"].as_str().unwrap_or_default();
+        for (tag, variant) in variants {
+            children.push((format!("{key} = {tag:?}"), variant));
+        }
+    }
+    children
+}